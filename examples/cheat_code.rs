@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use bevy_input_sequence::prelude::*;
+
+/// Cheat codes as they might appear in a config file.
+const CHEATS: [(&str, &str); 3] = [
+    ("idkfa", "all weapons"),
+    ("iddqd", "god mode"),
+    ("noclip", "walk through walls"),
+];
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputSequencePlugin::default())
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    for (text, effect) in CHEATS {
+        // Convert typed text into key chords.
+        let Some(chords) = text
+            .chars()
+            .map(|c| KeyChord::from_web_key_value(&c.to_string()))
+            .collect::<Option<Vec<_>>>()
+        else {
+            warn!("Cannot type cheat code {text:?}.");
+            continue;
+        };
+        commands.queue(
+            KeySequence::new(move || println!("Cheat enabled: {effect}"), chords)
+                .time_limit(Duration::from_secs(3)),
+        );
+        println!("Type {text:?} for {effect}.");
+    }
+}
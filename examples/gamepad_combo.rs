@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use bevy_input_sequence::{combo_tree::ComboTree, prelude::*};
+
+#[derive(Event, Clone, Debug)]
+#[allow(dead_code)]
+struct Special(&'static str, Entity);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputSequencePlugin::default().match_button(true))
+        .add_event::<Special>()
+        .add_systems(Startup, setup)
+        .add_systems(PostStartup, print_move_list)
+        .add_systems(Update, special_listener)
+        .run();
+}
+
+#[rustfmt::skip]
+fn setup(mut commands: Commands) {
+    // Quarter circle forward + punch within half a second.
+    commands.queue(
+        ButtonSequence::new(
+            action::send_event_with_input(|gamepad| Special("fireball", gamepad)),
+            [GamepadButton::DPadDown,
+             GamepadButton::DPadRight,
+             GamepadButton::West],
+        )
+        .time_limit(Duration::from_millis(500)),
+    );
+
+    // Forward, down, down-forward + punch within 20 frames.
+    commands.queue(
+        ButtonSequence::new(
+            action::send_event_with_input(|gamepad| Special("uppercut", gamepad)),
+            [GamepadButton::DPadRight,
+             GamepadButton::DPadDown,
+             GamepadButton::DPadRight,
+             GamepadButton::West],
+        )
+        .time_limit(TimeLimit::Frames(20)),
+    );
+
+    // Quarter circle back + kick.
+    commands.queue(
+        ButtonSequence::new(
+            action::send_event_with_input(|gamepad| Special("hurricane kick", gamepad)),
+            [GamepadButton::DPadDown,
+             GamepadButton::DPadLeft,
+             GamepadButton::South],
+        )
+        .time_limit(Duration::from_millis(500)),
+    );
+}
+
+fn print_move_list(sequences: Query<&ButtonSequence>) {
+    let report = ComboTree::new(sequences.iter()).report();
+    println!(
+        "{} specials, longest is {} inputs.",
+        report.sequences, report.max_depth
+    );
+    for branch in report.branch_points {
+        println!(
+            "After {:?} there are {} ways to continue.",
+            branch.prefix, branch.branches
+        );
+    }
+}
+
+fn special_listener(mut er: EventReader<Special>) {
+    for Special(name, gamepad) in er.read() {
+        println!("{name}! from gamepad {gamepad}");
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy_input_sequence::{
+    focus::{FocusOwner, SequenceFocus},
+    prelude::*,
+};
+
+/// The leader menu. Its single-key bindings only match while it has focus.
+#[derive(Resource)]
+struct LeaderMenu(Entity);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputSequencePlugin::default())
+        .init_resource::<SequenceFocus>()
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let menu = commands.spawn_empty().id();
+    commands.insert_resource(LeaderMenu(menu));
+
+    // The leader key opens the menu.
+    commands.queue(KeySequence::new(open_menu, keyseq! { Space }));
+
+    // Menu bindings.
+    for (keys, label) in [
+        (keyseq! { F }, "find file"),
+        (keyseq! { S }, "save"),
+        (keyseq! { Q }, "quit"),
+    ] {
+        let id = commands.spawn(FocusOwner(menu)).id();
+        commands.entity(id).queue(KeySequence::new(
+            move |mut focus: ResMut<SequenceFocus>| {
+                println!("{label}");
+                focus.0 = None;
+            },
+            keys,
+        ));
+    }
+    let id = commands.spawn(FocusOwner(menu)).id();
+    commands.entity(id).queue(KeySequence::new(close_menu, keyseq! { Escape }));
+
+    println!("Press Space to open the leader menu.");
+}
+
+fn open_menu(menu: Res<LeaderMenu>, mut focus: ResMut<SequenceFocus>) {
+    println!("Leader menu: [F]ind file, [S]ave, [Q]uit, Escape to close.");
+    focus.0 = Some(menu.0);
+}
+
+fn close_menu(mut focus: ResMut<SequenceFocus>) {
+    println!("Closed leader menu.");
+    focus.0 = None;
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use bevy_input_sequence::prelude::*;
+
+/// The entity holding the current "jump" binding.
+#[derive(Resource)]
+struct JumpBinding(Entity);
+
+/// Set while waiting for the user to press the new binding.
+#[derive(Resource, Default)]
+struct Rebinding(bool);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputSequencePlugin::default())
+        .init_resource::<Rebinding>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, capture_binding)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let id = commands.spawn_empty().id();
+    commands.entity(id).queue(KeySequence::new(jump, keyseq! { Space }));
+    commands.insert_resource(JumpBinding(id));
+    commands.queue(KeySequence::new(start_rebinding, keyseq! { F1 }));
+    println!("Press Space to jump. Press F1 to rebind jump.");
+}
+
+fn jump() {
+    println!("jump");
+}
+
+fn start_rebinding(mut rebinding: ResMut<Rebinding>) {
+    println!("Press the new key chord for jump.");
+    rebinding.0 = true;
+}
+
+fn capture_binding(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut binding: ResMut<JumpBinding>,
+    mut commands: Commands,
+) {
+    if !rebinding.0 {
+        return;
+    }
+    let mods = Modifiers::from(&keys);
+    let Some(key) = keys
+        .get_just_pressed()
+        .find(|key| Modifiers::from(**key).is_empty() && **key != KeyCode::F1)
+    else {
+        return;
+    };
+    let chord = KeyChord(mods, *key);
+    println!("Jump is now bound to {chord}.");
+    commands.entity(binding.0).despawn_recursive();
+    let id = commands.spawn_empty().id();
+    commands.entity(id).queue(KeySequence::new(jump, [chord]));
+    binding.0 = id;
+    rebinding.0 = false;
+}
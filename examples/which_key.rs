@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy_input_sequence::{cache::KeySequenceCache, prelude::*};
+
+/// Marks the overlay text.
+#[derive(Component)]
+struct Overlay;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputSequencePlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(PostUpdate, update_overlay)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands.spawn((Text::new(""), Overlay));
+
+    for (keys, label) in [
+        (keyseq! { Ctrl-X Ctrl-S }, "save"),
+        (keyseq! { Ctrl-X Ctrl-F }, "find file"),
+        (keyseq! { Ctrl-X K }, "kill buffer"),
+        (keyseq! { Ctrl-C Ctrl-C }, "compile"),
+    ] {
+        commands.queue(KeySequence::new(move || println!("{label}"), keys));
+    }
+}
+
+/// Show the possible continuations of a partially typed sequence.
+fn update_overlay(
+    cache: Res<KeySequenceCache>,
+    sequences: Query<&KeySequence>,
+    mut overlay: Query<&mut Text, With<Overlay>>,
+) {
+    if !cache.is_changed() {
+        return;
+    }
+    let prefix = cache.prefix();
+    let mut text = String::new();
+    if !prefix.is_empty() {
+        for seq in &sequences {
+            if let Some(next) = seq
+                .acts
+                .strip_prefix(&prefix[..])
+                .and_then(|rest| rest.first())
+            {
+                text.push_str(&format!("{next} ...\n"));
+            }
+        }
+    }
+    for mut overlay in &mut overlay {
+        overlay.0.clone_from(&text);
+    }
+}
@@ -0,0 +1,174 @@
+//! Match sequences of acts from any source
+//!
+//! Keys and gamepad buttons have their own matchers. Other inputs, like
+//! touchpad gestures, are turned into acts and pushed onto an [ActQueue]. The
+//! matcher for `InputSequence<Act, ()>` consumes the queue each update with the
+//! same time limit, cheat, namespace, and schedule rules as key sequences.
+use crate::{
+    cache::ActSequenceCache,
+    cheat::{Cheat, CheatsEnabled},
+    deferred::drain_deferred_sequences,
+    disabled::register_sequence,
+    frame_time::{FrameTime, PausedTime},
+    guard::InputSequenceGuard,
+    input_sequence::InputSequence,
+    matched::run_matched_held,
+    namespace::{Namespace, Namespaces},
+    plugin::{add_matcher, inc_consume_input, InputSequencePluginSettings},
+    schedule::{enter_schedule, leave_schedule, CurrentSchedule, MatchSchedule, ScheduleCaches},
+};
+use bevy::{
+    app::App,
+    core::FrameCount,
+    ecs::{
+        change_detection::DetectChanges,
+        query::{Added, Has, Or, With},
+        removal_detection::RemovedComponents,
+        schedule::IntoSystemConfigs,
+        system::{Commands, Local, Query, Res, ResMut, Resource},
+    },
+    prelude::{Deref, DerefMut},
+    time::{Real, Time},
+};
+use std::{collections::VecDeque, marker::PhantomData, time::Duration};
+
+/// Acts waiting to be matched against `InputSequence<Act, ()>` sequences.
+#[derive(Resource, Debug, Deref, DerefMut)]
+pub struct ActQueue<Act>(pub VecDeque<Act>);
+
+impl<Act> Default for ActQueue<Act> {
+    fn default() -> Self {
+        Self(VecDeque::new())
+    }
+}
+
+/// How long each act in [ActQueue] was held, in the same order. Only filled
+/// for acts that are held and released, like [HoldAct](crate::hold::HoldAct).
+#[derive(Resource, Debug, Deref, DerefMut)]
+pub(crate) struct ActHeld<Act>(#[deref] pub VecDeque<Duration>, PhantomData<Act>);
+
+impl<Act> Default for ActHeld<Act> {
+    fn default() -> Self {
+        Self(VecDeque::new(), PhantomData)
+    }
+}
+
+/// Add the queue, cache, and matcher for `Act` in every configured schedule.
+/// The `feed` systems run first to fill the queue.
+pub(crate) fn add_act_matcher<Act, M>(
+    app: &mut App,
+    settings: &InputSequencePluginSettings,
+    feed: impl IntoSystemConfigs<M> + Copy,
+) where
+    Act: Ord + Clone + Send + Sync + 'static,
+{
+    app.init_resource::<ActQueue<Act>>()
+        .init_resource::<ActSequenceCache<Act>>()
+        .init_resource::<ScheduleCaches<ActSequenceCache<Act>>>();
+    register_sequence::<InputSequence<Act, ()>>(app);
+    add_matcher(app, settings, |schedule| {
+        (
+            enter_schedule::<ActSequenceCache<Act>>(schedule),
+            drain_deferred_sequences,
+            feed,
+            detect_act_changes::<Act>,
+            act_sequence_matcher::<Act>,
+            leave_schedule::<ActSequenceCache<Act>>(schedule),
+        )
+            .chain()
+    });
+}
+
+#[allow(clippy::type_complexity)]
+fn detect_act_changes<Act>(
+    added: Query<(), Added<InputSequence<Act, ()>>>,
+    mut removals: RemovedComponents<InputSequence<Act, ()>>,
+    (cheats, namespaces): (Res<CheatsEnabled>, Res<Namespaces>),
+    changed: Query<(), (With<InputSequence<Act, ()>>, Or<(Added<Cheat>, Added<Namespace>)>)>,
+    mut cache: ResMut<ActSequenceCache<Act>>,
+) where
+    Act: Ord + Clone + Send + Sync + 'static,
+{
+    if !added.is_empty()
+        || !changed.is_empty()
+        || removals.read().next().is_some()
+        || cheats.is_changed()
+        || namespaces.is_changed()
+    {
+        cache.reset();
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn act_sequence_matcher<Act>(
+    sequences: Query<(
+        &InputSequence<Act, ()>,
+        Has<Cheat>,
+        Option<&Namespace>,
+        Option<&MatchSchedule>,
+    )>,
+    (cheats, namespaces, current): (Res<CheatsEnabled>, Res<Namespaces>, Res<CurrentSchedule>),
+    (time, real_time, frame_count, paused): (
+        Res<Time>,
+        Res<Time<Real>>,
+        Res<FrameCount>,
+        Res<PausedTime>,
+    ),
+    mut last_times: Local<VecDeque<FrameTime>>,
+    mut cache: ResMut<ActSequenceCache<Act>>,
+    (mut queue, mut held): (ResMut<ActQueue<Act>>, Option<ResMut<ActHeld<Act>>>),
+    mut commands: Commands,
+    guard: Res<InputSequenceGuard>,
+    settings: Res<InputSequencePluginSettings>,
+) where
+    Act: Ord + Clone + Send + Sync + 'static,
+{
+    if guard.is_suppressed() {
+        queue.clear();
+        if let Some(held) = held.as_mut() {
+            held.clear();
+        }
+        last_times.clear();
+        cache.forget();
+        return;
+    }
+    if queue.is_empty() {
+        return;
+    }
+    let now = FrameTime::now(&frame_count, &time, &real_time, &paused);
+    let mut search = cache.recall(
+        sequences
+            .iter()
+            .filter(|(_, cheat, ns, schedule)| {
+                (!cheat || cheats.0) && namespaces.allows(*ns) && current.accepts(*schedule)
+            })
+            .map(|(seq, ..)| seq),
+    );
+    for act in queue.drain(..) {
+        let act_held = held.as_mut().and_then(|held| held.pop_front());
+        last_times.push_back(now.clone());
+        let start = &last_times[0];
+        for seq in inc_consume_input(&mut search, std::iter::once(act)) {
+            let elapsed = &now - start;
+            if !seq
+                .time_limit
+                .as_ref()
+                .or(settings.default_time_limit.as_ref())
+                .is_some_and(|limit| elapsed.has_timedout(limit))
+            {
+                run_matched_held(
+                    &mut commands,
+                    seq.system_id,
+                    elapsed.duration(),
+                    seq.acts.len(),
+                    act_held,
+                );
+            }
+        }
+        let prefix_len = search.prefix_len();
+        let l = last_times.len();
+        let _ = last_times.drain(0..l - prefix_len);
+    }
+    let position = search.into();
+    cache.store(position);
+}
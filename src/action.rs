@@ -1,10 +1,14 @@
 //! Common actions to do on key sequence matches
+use crate::matched::{Emit, MatchInfo, MatchSource, Matched};
 use bevy::ecs::{
-    event::{Event, EventWriter},
+    event::{Event, EventWriter, Events},
     observer::TriggerTargets,
     prelude::Commands,
-    system::In,
+    component::Component,
+    system::{In, Query, Res, ResMut, Resource},
 };
+use bevy::tasks::IoTaskPool;
+use std::{future::Future, sync::mpsc};
 
 /// Send this event.
 ///
@@ -21,9 +25,35 @@ use bevy::ecs::{
 ///    action::send_event(MyEvent),
 ///    keyseq! { Space });
 /// ```
-pub fn send_event<E: Event + Clone>(event: E) -> impl FnMut(EventWriter<E>) {
-    move |mut writer: EventWriter<E>| {
-        writer.send(event.clone());
+///
+/// Sends [Matched] instead or as well if the sequence's [Emit] option says so.
+#[allow(clippy::type_complexity)]
+pub fn send_event<E: Event + Clone>(
+    event: E,
+) -> impl FnMut(Option<Res<MatchSource>>, EventWriter<E>, Option<ResMut<Events<Matched<E>>>>) {
+    move |source: Option<Res<MatchSource>>,
+          mut writer: EventWriter<E>,
+          matched: Option<ResMut<Events<Matched<E>>>>| {
+        let info = source.and_then(|source| source.0);
+        let emit = info.map(|info| info.emit).unwrap_or_default();
+        if emit != Emit::Matched {
+            writer.send(event.clone());
+        }
+        if emit != Emit::Event {
+            if let (Some(info), Some(mut matched)) = (info, matched) {
+                matched.send(info.wrap(event.clone()));
+            }
+        }
+    }
+}
+
+/// Send this event wrapped in [Matched] with the entity the sequence is on.
+/// Sends nothing for a sequence that is not on an entity.
+pub fn send_matched<E: Event + Clone>(event: E) -> impl FnMut(Res<MatchSource>, EventWriter<Matched<E>>) {
+    move |source: Res<MatchSource>, mut writer: EventWriter<Matched<E>>| {
+        if let Some(info) = source.0 {
+            writer.send(info.wrap(event.clone()));
+        }
     }
 }
 
@@ -44,6 +74,125 @@ pub fn trigger_targets<E: Event + Clone, T: TriggerTargets + Clone + Send + Sync
     }
 }
 
+/// Flip a bool in resource `R` and send `on` or `off` for its new value,
+/// e.g., to toggle a debug overlay.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_input_sequence::prelude::*;
+///
+/// #[derive(Resource, Default)]
+/// struct Debug { overlay: bool }
+///
+/// #[derive(Event, Clone)]
+/// enum Overlay { Shown, Hidden }
+///
+/// KeySequence::new(
+///     action::toggle(|debug: &mut Debug| &mut debug.overlay, Overlay::Shown, Overlay::Hidden),
+///     keyseq! { F3 });
+/// ```
+pub fn toggle<R, E, F>(field: F, on: E, off: E) -> impl FnMut(ResMut<R>, EventWriter<E>)
+where
+    R: Resource,
+    E: Event + Clone,
+    F: Fn(&mut R) -> &mut bool + Send + Sync + 'static,
+{
+    move |mut resource: ResMut<R>, mut writer: EventWriter<E>| {
+        let flag = field(&mut resource);
+        *flag = !*flag;
+        writer.send(if *flag { on.clone() } else { off.clone() });
+    }
+}
+
+/// Flip a bool in component `C` on the entity the sequence is on and send
+/// `on` or `off` for its new value. Does nothing for a sequence that is not
+/// on an entity with `C`.
+pub fn toggle_component<C, E, F>(
+    field: F,
+    on: E,
+    off: E,
+) -> impl FnMut(Res<MatchSource>, Query<&mut C>, EventWriter<E>)
+where
+    C: Component,
+    E: Event + Clone,
+    F: Fn(&mut C) -> &mut bool + Send + Sync + 'static,
+{
+    move |source: Res<MatchSource>, mut query: Query<&mut C>, mut writer: EventWriter<E>| {
+        let Some(mut component) = source.0.and_then(|info| query.get_mut(info.entity).ok()) else {
+            return;
+        };
+        let flag = field(&mut component);
+        *flag = !*flag;
+        writer.send(if *flag { on.clone() } else { off.clone() });
+    }
+}
+
+/// A channel's sending half that [send_to] can deliver matches into.
+///
+/// Implemented for `std::sync::mpsc` senders; implement it for other
+/// channels, e.g., crossbeam's, to use them.
+pub trait MatchSender<T>: Send + Sync + 'static {
+    /// Send `message`. A closed channel is not an error for the sequence.
+    fn send_match(&self, message: T);
+}
+
+impl<T: Send + 'static> MatchSender<T> for mpsc::Sender<T> {
+    fn send_match(&self, message: T) {
+        let _ = self.send(message);
+    }
+}
+
+impl<T: Send + 'static> MatchSender<T> for mpsc::SyncSender<T> {
+    fn send_match(&self, message: T) {
+        let _ = self.send(message);
+    }
+}
+
+/// Send this message into a channel instead of the event system, e.g., to an
+/// audio thread that owns the reaction.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_input_sequence::prelude::*;
+/// use std::sync::mpsc;
+///
+/// let (sender, receiver) = mpsc::channel();
+/// KeySequence::new(action::send_to(sender, "chime"), keyseq! { C C });
+/// # drop(receiver);
+/// ```
+pub fn send_to<T, S>(sender: S, message: T) -> impl FnMut()
+where
+    T: Clone + Send + Sync + 'static,
+    S: MatchSender<T>,
+{
+    move || sender.send_match(message.clone())
+}
+
+/// Spawn the future `f` returns on the [IoTaskPool], e.g., to submit
+/// telemetry. `f` receives the details of the match if the sequence is on an
+/// entity.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_input_sequence::prelude::*;
+///
+/// KeySequence::new(
+///     action::spawn_task(|_match| async move {
+///         info!("secret level unlocked");
+///     }),
+///     keyseq! { X Y Z Z Y });
+/// ```
+pub fn spawn_task<F, Fut>(f: F) -> impl FnMut(Option<Res<MatchSource>>)
+where
+    F: Fn(Option<MatchInfo>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    move |source: Option<Res<MatchSource>>| {
+        let info = source.and_then(|source| source.0);
+        IoTaskPool::get().spawn(f(info)).detach();
+    }
+}
+
 /// Sends an event with input, .e.g, [ButtonSequence](crate::input_sequence::ButtonSequence) provides a [Gamepad](bevy::input::gamepad::Gamepad) identifier.
 pub fn send_event_with_input<E: Event, Input: 'static, F: FnMut(Input) -> E>(
     mut f: F,
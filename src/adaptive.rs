@@ -0,0 +1,192 @@
+//! Widen a sequence's time limit for players who keep just missing it
+//!
+//! Give a sequence an [AdaptiveWindow] with the builder's
+//! [adaptive_window](crate::input_sequence::InputSequenceBuilder::adaptive_window)
+//! option. Each time its partial match times out counts as a near miss; after
+//! [after](AdaptiveWindow::after) near misses in a row, its time limit grows by
+//! [step](AdaptiveWindow::step). The same number of matches in a row shrinks
+//! it again. The limit stays between [min](AdaptiveWindow::min) and
+//! [max](AdaptiveWindow::max), and each change is sent as a [WindowAdjusted]
+//! event.
+//!
+//! Only [KeySequence]s and [ButtonSequence]s adapt. The builder drops an
+//! adaptive window given to any other sequence type with a warning.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, adaptive::AdaptiveWindow};
+//! use std::time::Duration;
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn_empty().queue(
+//!         KeySequence::new(|| info!("shoryuken"), keyseq! { D S D P })
+//!             .adaptive_window(AdaptiveWindow::new(
+//!                 Duration::from_millis(300),
+//!                 Duration::from_millis(600),
+//!             )));
+//! }
+//! ```
+use crate::{
+    input_sequence::{ButtonSequence, KeySequence},
+    matched::{AbortReason, SequenceAborted, SequenceComplete},
+    TimeLimit,
+};
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, EventReader, EventWriter},
+    observer::Trigger,
+    system::Query,
+};
+use std::{any::TypeId, time::Duration};
+
+/// Bounds and pace of a sequence's adaptive time limit. Only key and button
+/// sequences have one.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdaptiveWindow {
+    /// Shortest time limit.
+    pub min: Duration,
+    /// Longest time limit.
+    pub max: Duration,
+    /// Change in time limit per adjustment.
+    pub step: Duration,
+    /// Near misses or matches in a row before an adjustment.
+    pub after: u32,
+    misses: u32,
+    hits: u32,
+}
+
+impl AdaptiveWindow {
+    /// Start at `min` and adjust by a quarter of the range after three near
+    /// misses or matches in a row.
+    pub fn new(min: Duration, max: Duration) -> Self {
+        Self {
+            min,
+            max,
+            step: max.saturating_sub(min) / 4,
+            after: 3,
+            misses: 0,
+            hits: 0,
+        }
+    }
+
+    /// Set the change per adjustment.
+    pub fn step(mut self, step: Duration) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set how many near misses or matches in a row cause an adjustment.
+    pub fn after(mut self, count: u32) -> Self {
+        self.after = count;
+        self
+    }
+
+    /// Return the time limit after a near miss if `missed`, otherwise after a
+    /// match, or `None` if it doesn't change.
+    fn adjust(&mut self, current: Duration, missed: bool) -> Option<Duration> {
+        let (count, other) = if missed {
+            (&mut self.misses, &mut self.hits)
+        } else {
+            (&mut self.hits, &mut self.misses)
+        };
+        *other = 0;
+        *count += 1;
+        if *count < self.after.max(1) {
+            return None;
+        }
+        *count = 0;
+        let new = if missed {
+            current.saturating_add(self.step)
+        } else {
+            current.saturating_sub(self.step)
+        }
+        .clamp(self.min, self.max);
+        (new != current).then_some(new)
+    }
+}
+
+/// Return true if sequences of type `T` adapt their time limits.
+pub(crate) fn adapts<T: 'static>() -> bool {
+    let id = TypeId::of::<T>();
+    id == TypeId::of::<KeySequence>() || id == TypeId::of::<ButtonSequence>()
+}
+
+/// Sent when an [AdaptiveWindow] changes a sequence's time limit.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowAdjusted {
+    /// Entity the sequence is on.
+    pub entity: Entity,
+    /// The new time limit.
+    pub time_limit: Duration,
+}
+
+/// Adjust `time_limit` by `window`, returning the new limit if it changed.
+fn set_limit(
+    time_limit: &mut Option<TimeLimit>,
+    window: &mut AdaptiveWindow,
+    missed: bool,
+) -> Option<Duration> {
+    let current = match time_limit {
+        Some(TimeLimit::Duration(d)) => *d,
+        _ => window.min,
+    };
+    let new = window.adjust(current, missed)?;
+    *time_limit = Some(TimeLimit::Duration(new));
+    Some(new)
+}
+
+/// Widen windows after near misses.
+pub(crate) fn widen_windows(
+    mut aborted: EventReader<SequenceAborted>,
+    mut sequences: Query<(
+        &mut AdaptiveWindow,
+        Option<&mut KeySequence>,
+        Option<&mut ButtonSequence>,
+    )>,
+    mut adjusted: EventWriter<WindowAdjusted>,
+) {
+    for abort in aborted.read() {
+        if abort.reason != AbortReason::TimedOut {
+            continue;
+        }
+        let Ok((mut window, key, button)) = sequences.get_mut(abort.entity) else {
+            continue;
+        };
+        let time_limit = match (key, button) {
+            (Some(key), _) => set_limit(&mut key.into_inner().time_limit, &mut window, true),
+            (_, Some(button)) => set_limit(&mut button.into_inner().time_limit, &mut window, true),
+            _ => None,
+        };
+        if let Some(time_limit) = time_limit {
+            adjusted.send(WindowAdjusted {
+                entity: abort.entity,
+                time_limit,
+            });
+        }
+    }
+}
+
+/// Narrow windows after matches.
+pub(crate) fn narrow_window(
+    trigger: Trigger<SequenceComplete>,
+    mut sequences: Query<(
+        &mut AdaptiveWindow,
+        Option<&mut KeySequence>,
+        Option<&mut ButtonSequence>,
+    )>,
+    mut adjusted: EventWriter<WindowAdjusted>,
+) {
+    let entity = trigger.entity();
+    let Ok((mut window, key, button)) = sequences.get_mut(entity) else {
+        return;
+    };
+    let time_limit = match (key, button) {
+        (Some(key), _) => set_limit(&mut key.into_inner().time_limit, &mut window, false),
+        (_, Some(button)) => set_limit(&mut button.into_inner().time_limit, &mut window, false),
+        _ => None,
+    };
+    if let Some(time_limit) = time_limit {
+        adjusted.send(WindowAdjusted { entity, time_limit });
+    }
+}
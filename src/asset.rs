@@ -0,0 +1,224 @@
+//! Load sequences from `.keyseq.ron` files
+//!
+//! Requires the "asset" feature. A `.keyseq.ron` file holds a
+//! [Keymap](crate::keymap::Keymap) in RON. Spawn [AssetSequences] with a handle
+//! to it and the events each named binding sends. Its sequences are spawned as
+//! children once the file loads and respawned whenever it changes, so with
+//! Bevy's `file_watcher` feature cheat codes can be tweaked without
+//! recompiling.
+//!
+//! ```ron
+//! (bindings: [
+//!     (name: "god mode", acts: ["I", "D", "D", "Q", "D"]),
+//!     (name: "save", acts: ["Ctrl-X", "Ctrl-S"], time_limit: Some(Frames(60))),
+//! ])
+//! ```
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, asset::{AssetSequences, SequenceAssetPlugin}};
+//!
+//! #[derive(Event, Clone)]
+//! struct GodMode;
+//!
+//! fn setup(assets: Res<AssetServer>, mut commands: Commands) {
+//!     commands.spawn(
+//!         AssetSequences::new(assets.load("cheats.keyseq.ron"))
+//!             .event("god mode", GodMode));
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(AssetPlugin::default())
+//!    .add_plugins(InputSequencePlugin::default())
+//!    .add_plugins(SequenceAssetPlugin)
+//!    .add_event::<GodMode>()
+//!    .add_systems(Startup, setup);
+//! ```
+use crate::{action, keymap::{Binding, Keymap}};
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::{io::Reader, Asset, AssetApp, AssetEvent, AssetLoader, Assets, Handle, LoadContext},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader},
+        query::Added,
+        system::{Commands, EntityCommand, Query, Res},
+        world::World,
+    },
+    hierarchy::{BuildChildren, DespawnRecursiveExt},
+    log::warn,
+    prelude::Deref,
+    reflect::TypePath,
+};
+use std::{collections::HashSet, fmt};
+
+/// A keymap loaded from a `.keyseq.ron` file.
+#[derive(Asset, TypePath, Clone, Debug, Deref)]
+pub struct SequenceAsset(pub Keymap);
+
+/// Loads `.keyseq.ron` files.
+#[derive(Default)]
+pub struct SequenceAssetLoader;
+
+/// Error loading a [SequenceAsset].
+#[derive(Debug)]
+pub enum SequenceAssetError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not a valid keymap.
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for SequenceAssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceAssetError::Io(e) => write!(f, "could not read keymap: {e}"),
+            SequenceAssetError::Ron(e) => write!(f, "invalid keymap: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SequenceAssetError {}
+
+impl From<std::io::Error> for SequenceAssetError {
+    fn from(e: std::io::Error) -> Self {
+        SequenceAssetError::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for SequenceAssetError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        SequenceAssetError::Ron(e)
+    }
+}
+
+impl AssetLoader for SequenceAssetLoader {
+    type Asset = SequenceAsset;
+    type Settings = ();
+    type Error = SequenceAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(SequenceAsset(ron::de::from_bytes(&bytes)?))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["keyseq.ron"]
+    }
+}
+
+type Spawner = Box<dyn Fn(&Binding, Entity, &mut World) + Send + Sync>;
+
+/// Spawns the sequences of a [SequenceAsset] as children of this entity.
+#[derive(Component)]
+pub struct AssetSequences {
+    /// Keymap to spawn sequences from.
+    pub handle: Handle<SequenceAsset>,
+    events: Vec<(String, Spawner)>,
+    spawned: Vec<Entity>,
+}
+
+impl AssetSequences {
+    /// Spawn sequences from the keymap `handle`.
+    pub fn new(handle: Handle<SequenceAsset>) -> Self {
+        Self {
+            handle,
+            events: Vec::new(),
+            spawned: Vec::new(),
+        }
+    }
+
+    /// Send `event` when the binding called `name` matches.
+    pub fn event<E: Event + Clone>(mut self, name: impl Into<String>, event: E) -> Self {
+        self.events.push((
+            name.into(),
+            Box::new(move |binding, id, world| {
+                EntityCommand::apply(
+                    binding.key_sequence(action::send_event(event.clone())),
+                    id,
+                    world,
+                );
+            }),
+        ));
+        self
+    }
+}
+
+/// Adds the [SequenceAsset] type and its loader. Requires `AssetPlugin`.
+pub struct SequenceAssetPlugin;
+
+impl Plugin for SequenceAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<SequenceAsset>()
+            .init_asset_loader::<SequenceAssetLoader>()
+            .add_systems(Update, reload_asset_sequences);
+    }
+}
+
+fn reload_asset_sequences(
+    mut events: EventReader<AssetEvent<SequenceAsset>>,
+    owners: Query<(Entity, &AssetSequences)>,
+    added: Query<Entity, Added<AssetSequences>>,
+    assets: Res<Assets<SequenceAsset>>,
+    mut commands: Commands,
+) {
+    let changed: HashSet<_> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    for (id, sequences) in &owners {
+        let asset = sequences.handle.id();
+        if changed.contains(&asset) || (added.contains(id) && assets.contains(asset)) {
+            commands.queue(move |world: &mut World| respawn_sequences(id, world));
+        }
+    }
+}
+
+fn respawn_sequences(owner: Entity, world: &mut World) {
+    // Take the fields instead of the component so it does not count as added
+    // again.
+    let Some((handle, events, spawned)) = world.get_mut::<AssetSequences>(owner).map(|mut s| {
+        (
+            s.handle.clone(),
+            std::mem::take(&mut s.events),
+            std::mem::take(&mut s.spawned),
+        )
+    }) else {
+        return;
+    };
+    for id in spawned {
+        if let Ok(entity) = world.get_entity_mut(id) {
+            entity.despawn_recursive();
+        }
+    }
+    let keymap = world
+        .resource::<Assets<SequenceAsset>>()
+        .get(&handle)
+        .map(|asset| asset.0.clone())
+        .unwrap_or_default();
+    let mut spawned = Vec::new();
+    for binding in &keymap.bindings {
+        let Some((_, spawn)) = events.iter().find(|(name, _)| *name == binding.name) else {
+            warn!("No event for sequence binding {:?}.", binding.name);
+            continue;
+        };
+        let id = world.spawn_empty().set_parent(owner).id();
+        spawn(binding, id, world);
+        spawned.push(id);
+    }
+    if let Some(mut sequences) = world.get_mut::<AssetSequences>(owner) {
+        sequences.events = events;
+        sequences.spawned = spawned;
+    }
+}
@@ -0,0 +1,57 @@
+//! Lint keymap files.
+//!
+//! Usage: `keyseq-lint FILE...`
+//!
+//! Files ending in `.ron` hold a RON keymap, files ending in `.bin` hold a
+//! binary keymap, and any other file holds a text keymap. Exits with status 1
+//! if any file has lints or can't be read.
+use bevy_input_sequence::{
+    keymap::Keymap,
+    lint::{lint, lint_text, Lint},
+};
+use std::{env, fs, process::ExitCode};
+
+fn lint_file(path: &str) -> Result<Vec<Lint>, String> {
+    if path.ends_with(".ron") {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let keymap: Keymap = ron::from_str(&text).map_err(|e| e.to_string())?;
+        Ok(lint(&keymap))
+    } else if path.ends_with(".bin") {
+        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+        let keymap = Keymap::from_bytes(&bytes).map_err(|e| e.to_string())?;
+        Ok(lint(&keymap))
+    } else {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        lint_text(&text)
+            .map(|(_, lints)| lints)
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn main() -> ExitCode {
+    let paths: Vec<String> = env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: keyseq-lint FILE...");
+        return ExitCode::from(2);
+    }
+    let mut clean = true;
+    for path in &paths {
+        match lint_file(path) {
+            Ok(lints) => {
+                for lint in &lints {
+                    println!("{path}: {lint}");
+                }
+                clean &= lints.is_empty();
+            }
+            Err(error) => {
+                eprintln!("{path}: {error}");
+                clean = false;
+            }
+        }
+    }
+    if clean {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
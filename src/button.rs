@@ -0,0 +1,216 @@
+//! Acts for gamepad button sequences
+//!
+//! A [ButtonAct] is a press of a gamepad button by default. Analog buttons like
+//! the triggers can also be soft pulled or hard pulled, and any button can be
+//! released, tapped, or held; see [TapHoldCalibration] for the timing. Releases,
+//! pulls, taps, and holds are only fed to the matcher if some sequence uses
+//! them, so they don't interrupt sequences that don't mention them. The same
+//! goes for presses of a button that some sequence pulls, taps, or holds.
+//!
+//! [ButtonAct::Any] stands for a press of any button in a [ButtonGroup], e.g.,
+//! "mash any face button" as a single step. A press is fed to the matcher as
+//! its group's act if some sequence uses the group, unless some sequence uses
+//! the press of that very button, which takes precedence.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, button::{ButtonAct, ButtonGroup}};
+//!
+//! // Half pull to aim, pull through to fire, and let go.
+//! let aimed_shot = ButtonSequence::new(
+//!     |In(_gamepad): In<Entity>| info!("aimed shot"),
+//!     [ButtonAct::SoftPull(GamepadButton::RightTrigger2),
+//!      ButtonAct::HardPull(GamepadButton::RightTrigger2),
+//!      ButtonAct::Release(GamepadButton::RightTrigger2)],
+//! );
+//!
+//! // Tap, tap, and hold.
+//! let charge = ButtonSequence::new(
+//!     |In(_gamepad): In<Entity>| info!("charge"),
+//!     [ButtonAct::Tap(GamepadButton::South),
+//!      ButtonAct::Tap(GamepadButton::South),
+//!      ButtonAct::Hold(GamepadButton::South)],
+//! );
+//!
+//! // Any face button, three times.
+//! let mash = ButtonSequence::new(
+//!     |In(_gamepad): In<Entity>| info!("break free"),
+//!     [ButtonAct::Any(ButtonGroup::Face); 3],
+//! );
+//! ```
+use crate::stick::{Direction, Stick};
+use bevy::{
+    ecs::{reflect::ReflectResource, system::Resource},
+    input::gamepad::GamepadButton,
+    reflect::{std_traits::ReflectDefault, Reflect},
+};
+use std::time::Duration;
+
+/// An act of a gamepad button sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ButtonAct {
+    /// The button is pressed past its press threshold.
+    Press(GamepadButton),
+    /// An analog button is pulled past the soft threshold.
+    SoftPull(GamepadButton),
+    /// An analog button is pulled past the hard threshold.
+    HardPull(GamepadButton),
+    /// The button is released.
+    Release(GamepadButton),
+    /// The button is released within [TapHoldCalibration::tap] of its press.
+    Tap(GamepadButton),
+    /// The button has been held for [TapHoldCalibration::hold].
+    Hold(GamepadButton),
+    /// The stick engages or rolls into the direction; see
+    /// [StickCalibration](crate::stick::StickCalibration).
+    Stick(Stick, Direction),
+    /// Any button of the group is pressed.
+    Any(ButtonGroup),
+}
+
+/// A category of gamepad buttons for [ButtonAct::Any].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ButtonGroup {
+    /// South, East, North, and West.
+    Face,
+    /// The bumpers and triggers.
+    Shoulder,
+    /// The four D-pad directions.
+    Dpad,
+}
+
+impl ButtonGroup {
+    /// Return the buttons of the group.
+    pub fn buttons(&self) -> [GamepadButton; 4] {
+        use GamepadButton::*;
+        match self {
+            ButtonGroup::Face => [South, East, North, West],
+            ButtonGroup::Shoulder => [LeftTrigger, RightTrigger, LeftTrigger2, RightTrigger2],
+            ButtonGroup::Dpad => [DPadUp, DPadDown, DPadLeft, DPadRight],
+        }
+    }
+
+    /// Return the group `button` belongs to, if any.
+    pub fn of(button: GamepadButton) -> Option<ButtonGroup> {
+        [ButtonGroup::Face, ButtonGroup::Shoulder, ButtonGroup::Dpad]
+            .into_iter()
+            .find(|group| group.buttons().contains(&button))
+    }
+}
+
+impl From<GamepadButton> for ButtonAct {
+    #[inline(always)]
+    fn from(button: GamepadButton) -> Self {
+        ButtonAct::Press(button)
+    }
+}
+
+impl ButtonAct {
+    /// Return the button of this act; for a stick direction, the stick's
+    /// thumb button; for a group, its first button.
+    pub fn button(&self) -> GamepadButton {
+        match self {
+            ButtonAct::Press(b)
+            | ButtonAct::SoftPull(b)
+            | ButtonAct::HardPull(b)
+            | ButtonAct::Release(b)
+            | ButtonAct::Tap(b)
+            | ButtonAct::Hold(b) => *b,
+            ButtonAct::Stick(stick, _) => stick.thumb_button(),
+            ButtonAct::Any(group) => group.buttons()[0],
+        }
+    }
+}
+
+/// How far an analog button is pulled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PullLevel {
+    /// Not pulled.
+    #[default]
+    Rest,
+    /// Pulled past the soft threshold.
+    Soft,
+    /// Pulled past the hard threshold.
+    Hard,
+}
+
+/// Thresholds for soft and hard pulls of analog buttons. Each level engages
+/// at its press threshold and disengages below its release threshold.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct TriggerCalibration {
+    /// Value at which a soft pull engages.
+    pub soft_press: f32,
+    /// Value below which a soft pull disengages.
+    pub soft_release: f32,
+    /// Value at which a hard pull engages.
+    pub hard_press: f32,
+    /// Value below which a hard pull disengages.
+    pub hard_release: f32,
+}
+
+impl Default for TriggerCalibration {
+    fn default() -> Self {
+        Self {
+            soft_press: 0.3,
+            soft_release: 0.2,
+            hard_press: 0.9,
+            hard_release: 0.8,
+        }
+    }
+}
+
+impl TriggerCalibration {
+    /// Return the pull level for `value` given the previous level.
+    ///
+    /// ```rust
+    /// use bevy_input_sequence::button::{PullLevel, TriggerCalibration};
+    /// let calibration = TriggerCalibration::default();
+    /// assert_eq!(calibration.level(0.5, PullLevel::Rest), PullLevel::Soft);
+    /// assert_eq!(calibration.level(0.85, PullLevel::Soft), PullLevel::Soft);
+    /// assert_eq!(calibration.level(0.85, PullLevel::Hard), PullLevel::Hard);
+    /// assert_eq!(calibration.level(0.25, PullLevel::Soft), PullLevel::Soft);
+    /// assert_eq!(calibration.level(0.25, PullLevel::Rest), PullLevel::Rest);
+    /// ```
+    pub fn level(&self, value: f32, previous: PullLevel) -> PullLevel {
+        let hard = if previous == PullLevel::Hard {
+            self.hard_release
+        } else {
+            self.hard_press
+        };
+        let soft = if previous >= PullLevel::Soft {
+            self.soft_release
+        } else {
+            self.soft_press
+        };
+        if value >= hard {
+            PullLevel::Hard
+        } else if value >= soft {
+            PullLevel::Soft
+        } else {
+            PullLevel::Rest
+        }
+    }
+}
+
+/// How long a press may last to be a tap and must last to be a hold, in
+/// virtual time.
+#[derive(Resource, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct TapHoldCalibration {
+    /// Longest press that is a tap.
+    pub tap: Duration,
+    /// Shortest press that is a hold.
+    pub hold: Duration,
+}
+
+impl Default for TapHoldCalibration {
+    fn default() -> Self {
+        Self {
+            tap: Duration::from_millis(200),
+            hold: Duration::from_millis(400),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Cache the trie for reuse.
+use crate::input_sequence::InputSequence;
+use bevy::ecs::system::Resource;
+use trie_rs::{
+    inc_search::{IncSearch, Position},
+    map::{Trie, TrieBuilder},
+};
+
+/// Contains the trie for sequences of acts fed through an
+/// [ActQueue](crate::act_queue::ActQueue).
+#[derive(Resource)]
+pub struct ActSequenceCache<Act: Send + Sync + 'static> {
+    trie: Option<Trie<Act, InputSequence<Act, ()>>>,
+    position: Option<Position>,
+}
+
+impl<Act: Send + Sync + 'static> Default for ActSequenceCache<Act> {
+    fn default() -> Self {
+        Self {
+            trie: None,
+            position: None,
+        }
+    }
+}
+
+impl<Act> ActSequenceCache<Act>
+where
+    Act: Ord + Clone + Send + Sync + 'static,
+{
+    /// Retrieve the cached trie without iterating through `sequences`. Or if
+    /// the cache has been invalidated, build and cache a new trie using the
+    /// `sequences` iterator.
+    pub fn trie<'a>(
+        &mut self,
+        sequences: impl Iterator<Item = &'a InputSequence<Act, ()>>,
+    ) -> &Trie<Act, InputSequence<Act, ()>> {
+        self.trie.get_or_insert_with(|| {
+            let mut builder: TrieBuilder<Act, InputSequence<Act, ()>> = TrieBuilder::new();
+            for sequence in sequences {
+                builder.insert(sequence.acts.clone(), sequence.clone());
+            }
+            builder.build()
+        })
+    }
+
+    /// Store a search.
+    pub fn store(&mut self, position: Position) {
+        self.position = Some(position)
+    }
+
+    /// Recall a search OR create a new search.
+    pub fn recall<'a, 'b>(
+        &'b mut self,
+        sequences: impl Iterator<Item = &'a InputSequence<Act, ()>>,
+    ) -> IncSearch<'a, Act, InputSequence<Act, ()>>
+    where
+        'b: 'a,
+    {
+        let position = self.position;
+        let trie = self.trie(sequences);
+        position
+            .map(move |p| IncSearch::resume(trie, p))
+            .unwrap_or_else(move || trie.inc_search())
+    }
+
+    /// Return the acts of the current partial match.
+    pub fn prefix(&self) -> Vec<Act> {
+        match (&self.trie, self.position) {
+            (Some(trie), Some(position)) => IncSearch::resume(trie, position).prefix(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Clears the cache.
+    pub fn reset(&mut self) {
+        self.trie = None;
+        self.position = None;
+    }
+
+    /// Forget the partial match but keep the trie.
+    pub fn forget(&mut self) {
+        self.position = None;
+    }
+}
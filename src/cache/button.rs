@@ -1,7 +1,7 @@
 //! Cache the trie for reuse.
-use crate::input_sequence::InputSequence;
-use bevy::{ecs::system::Resource, prelude::{In, Entity, GamepadButton}};
-use std::collections::HashMap;
+use crate::{button::ButtonAct, input_sequence::InputSequence, stick::dpad_variants, EvictionPolicy};
+use bevy::{ecs::system::Resource, prelude::{In, Entity}};
+use std::collections::{HashMap, HashSet};
 use trie_rs::{
     inc_search::{IncSearch, Position},
     map::{Trie, TrieBuilder},
@@ -10,8 +10,13 @@ use trie_rs::{
 /// Contains the trie for gamepad button sequences.
 #[derive(Resource, Default)]
 pub struct ButtonSequenceCache {
-    trie: Option<Trie<GamepadButton, InputSequence<GamepadButton, In<Entity>>>>,
+    trie: Option<Trie<ButtonAct, InputSequence<ButtonAct, In<Entity>>>>,
     position: HashMap<Entity, Position>,
+    acts: HashSet<ButtonAct>,
+    /// When each partial match started and how many acts it has matched.
+    progress: HashMap<Entity, (u64, usize)>,
+    tick: u64,
+    evictions: usize,
 }
 
 impl ButtonSequenceCache
@@ -21,12 +26,30 @@ impl ButtonSequenceCache
     /// `sequences` iterator.
     pub fn trie<'a>(
         &mut self,
-        sequences: impl Iterator<Item = &'a InputSequence<GamepadButton, In<Entity>>>,
-    ) -> &Trie<GamepadButton, InputSequence<GamepadButton, In<Entity>>> {
+        sequences: impl Iterator<Item = &'a InputSequence<ButtonAct, In<Entity>>>,
+    ) -> &Trie<ButtonAct, InputSequence<ButtonAct, In<Entity>>> {
+        self.trie_with_stick_as_dpad(sequences.map(|sequence| (sequence, false)))
+    }
+
+    /// Like [trie](Self::trie), but the sequences paired with `true` also
+    /// match with D-pad presses and left stick directions swapped; see
+    /// [StickAsDpad](crate::stick::StickAsDpad).
+    pub fn trie_with_stick_as_dpad<'a>(
+        &mut self,
+        sequences: impl Iterator<Item = (&'a InputSequence<ButtonAct, In<Entity>>, bool)>,
+    ) -> &Trie<ButtonAct, InputSequence<ButtonAct, In<Entity>>> {
         self.trie.get_or_insert_with(|| {
-            let mut builder: TrieBuilder<GamepadButton, InputSequence<GamepadButton, In<Entity>>> = TrieBuilder::new();
-            for sequence in sequences {
-                builder.insert(sequence.acts.clone(), sequence.clone());
+            let mut builder: TrieBuilder<ButtonAct, InputSequence<ButtonAct, In<Entity>>> = TrieBuilder::new();
+            for (sequence, stick_as_dpad) in sequences {
+                let variants = if stick_as_dpad {
+                    dpad_variants(&sequence.acts)
+                } else {
+                    vec![sequence.acts.clone()]
+                };
+                for acts in variants {
+                    self.acts.extend(acts.iter().copied());
+                    builder.insert(acts, sequence.clone());
+                }
             }
             // info!(
             //     "Building trie for {} input sequences.",
@@ -40,17 +63,80 @@ impl ButtonSequenceCache
         })
     }
 
+    /// Return true if any sequence uses `act`. Only accurate once the trie
+    /// has been built.
+    pub fn uses(&self, act: &ButtonAct) -> bool {
+        self.acts.contains(act)
+    }
+
+    /// Return the acts used by any sequence. Only accurate once the trie has
+    /// been built.
+    pub fn acts(&self) -> impl Iterator<Item = &ButtonAct> {
+        self.acts.iter()
+    }
+
     /// Store a search.
     pub fn store(&mut self, key: Entity, position: Position) {
         self.position.insert(key, position);
     }
 
+    /// Store a search that has matched `matched` acts. A search that has
+    /// matched nothing is dropped.
+    pub fn store_progress(&mut self, key: Entity, position: Position, matched: usize) {
+        if matched == 0 {
+            self.position.remove(&key);
+            self.progress.remove(&key);
+            return;
+        }
+        self.position.insert(key, position);
+        let tick = &mut self.tick;
+        self.progress
+            .entry(key)
+            .and_modify(|(_, m)| *m = matched)
+            .or_insert_with(|| {
+                *tick += 1;
+                (*tick, matched)
+            });
+    }
+
+    /// Return the number of partial matches being tracked.
+    pub fn partial_matches(&self) -> usize {
+        self.progress.len()
+    }
+
+    /// Drop partial matches chosen by `policy` until at most `max` remain.
+    /// Returns the keys of the dropped matches.
+    pub fn evict(&mut self, max: usize, policy: EvictionPolicy) -> Vec<Entity> {
+        let mut evicted = Vec::new();
+        while self.progress.len() > max {
+            let victim = self
+                .progress
+                .iter()
+                .min_by_key(|(_, (started, matched))| match policy {
+                    EvictionPolicy::Oldest => (*started, 0),
+                    EvictionPolicy::LeastProgress => (*matched as u64, *started),
+                })
+                .map(|(key, _)| *key)
+                .unwrap();
+            self.progress.remove(&victim);
+            self.position.remove(&victim);
+            evicted.push(victim);
+        }
+        self.evictions += evicted.len();
+        evicted
+    }
+
+    /// Return the number of partial matches evicted so far.
+    pub fn evictions(&self) -> usize {
+        self.evictions
+    }
+
     /// Recall a search OR create a new search.
     pub fn recall<'a, 'b>(
         &'b mut self,
         key: Entity,
-        sequences: impl Iterator<Item = &'a InputSequence<GamepadButton, In<Entity>>>,
-    ) -> IncSearch<'a, GamepadButton, InputSequence<GamepadButton, In<Entity>>>
+        sequences: impl Iterator<Item = &'a InputSequence<ButtonAct, In<Entity>>>,
+    ) -> IncSearch<'a, ButtonAct, InputSequence<ButtonAct, In<Entity>>>
     where
         'b: 'a,
     {
@@ -117,5 +203,19 @@ impl ButtonSequenceCache
     pub fn reset(&mut self) {
         self.trie = None;
         self.position.clear();
+        self.acts.clear();
+        self.progress.clear();
+    }
+
+    /// Forget the partial match for `key`.
+    pub fn forget_key(&mut self, key: Entity) {
+        self.position.remove(&key);
+        self.progress.remove(&key);
+    }
+
+    /// Forget all partial matches but keep the trie.
+    pub fn forget(&mut self) {
+        self.position.clear();
+        self.progress.clear();
     }
 }
@@ -11,6 +11,7 @@ use trie_rs::{
 pub struct KeySequenceCache {
     trie: Option<Trie<KeyChord, InputSequence<KeyChord, ()>>>,
     position: Option<Position>,
+    handoff: Option<Vec<KeyChord>>,
 }
 
 impl KeySequenceCache
@@ -22,7 +23,7 @@ impl KeySequenceCache
         &mut self,
         sequences: impl Iterator<Item = &'a InputSequence<KeyChord, ()>>,
     ) -> &Trie<KeyChord, InputSequence<KeyChord, ()>> {
-        self.trie.get_or_insert_with(|| {
+        if self.trie.is_none() {
             let mut builder: TrieBuilder<KeyChord, InputSequence<KeyChord, ()>> = TrieBuilder::new();
             for sequence in sequences {
                 builder.insert(sequence.acts.clone(), sequence.clone());
@@ -31,8 +32,31 @@ impl KeySequenceCache
             //     "Building trie for {} input sequences.",
             //     A::short_type_path()
             // );
-            builder.build()
-        })
+            let trie = builder.build();
+            if let Some(prefix) = self.handoff.take() {
+                let mut search = trie.inc_search();
+                if search.query_until(&prefix).is_ok_and(|answer| answer.is_prefix()) {
+                    self.position = Some(search.into());
+                }
+            }
+            self.trie = Some(trie);
+        }
+        self.trie.as_ref().unwrap()
+    }
+
+    /// Retrieve the trie, building it if necessary, and the stored search
+    /// position if any.
+    pub fn trie_and_position<'a>(
+        &mut self,
+        sequences: impl Iterator<Item = &'a InputSequence<KeyChord, ()>>,
+    ) -> (&Trie<KeyChord, InputSequence<KeyChord, ()>>, Option<Position>) {
+        self.trie(sequences);
+        (self.trie.as_ref().unwrap(), self.position)
+    }
+
+    /// Return the stored search position if any.
+    pub fn position(&self) -> Option<Position> {
+        self.position
     }
 
     /// Store a search.
@@ -48,8 +72,7 @@ impl KeySequenceCache
     where
         'b: 'a,
     {
-        let position = self.position;
-        let trie = self.trie(sequences);
+        let (trie, position) = self.trie_and_position(sequences);
         position
             .map(move |p| IncSearch::resume(trie, p))
             .unwrap_or_else(move || trie.inc_search())
@@ -59,5 +82,28 @@ impl KeySequenceCache
     pub fn reset(&mut self) {
         self.trie = None;
         self.position = None;
+        self.handoff = None;
+    }
+
+    /// Forget the partial match but keep the trie.
+    pub fn forget(&mut self) {
+        self.position = None;
+        self.handoff = None;
+    }
+
+    /// Clears the cache but keeps the partial match. When the trie is rebuilt,
+    /// matching resumes from the same prefix if the new trie has it.
+    pub fn rebuild(&mut self) {
+        let prefix = self.prefix();
+        self.reset();
+        self.handoff = Some(prefix);
+    }
+
+    /// Return the key chords of the current partial match.
+    pub fn prefix(&self) -> Vec<KeyChord> {
+        match (&self.trie, self.position) {
+            (Some(trie), Some(position)) => IncSearch::resume(trie, position).prefix(),
+            _ => Vec::new(),
+        }
     }
 }
@@ -3,3 +3,5 @@ mod button;
 pub use button::*;
 mod key;
 pub use key::*;
+mod act;
+pub use act::*;
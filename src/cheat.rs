@@ -0,0 +1,60 @@
+//! Turn all cheat codes on or off
+//!
+//! Mark a sequence as a cheat with [Cheat] and it only matches while
+//! [CheatsEnabled] is true. Cheats are enabled by default in debug builds and
+//! disabled in release builds.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, cheat::CheatsEnabled};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(
+//!         KeySequence::new(|| info!("god mode"), keyseq! { I D D Q D })
+//!             .cheat(),
+//!     );
+//!     // Allow cheats in release builds too, e.g., from a console command.
+//!     commands.insert_resource(CheatsEnabled(true));
+//! }
+//! ```
+use bevy::ecs::{component::Component, system::Resource};
+
+/// Marks an input sequence as a cheat.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Cheat;
+
+/// Whether sequences marked [Cheat] can match.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheatsEnabled(pub bool);
+
+impl Default for CheatsEnabled {
+    fn default() -> Self {
+        Self(cfg!(debug_assertions))
+    }
+}
+
+/// Compile the enclosed statements only in debug builds.
+///
+/// Use it for sequences that must not be discoverable in a shipped binary.
+/// Since the statements are removed before compilation, nothing of them,
+/// including their acts, ends up in a release build.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_input_sequence::{prelude::*, debug_only};
+///
+/// fn setup(mut commands: Commands) {
+///     debug_only! {
+///         commands.queue(KeySequence::new(|| info!("noclip"), keyseq! { N O C L I P }));
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! debug_only {
+    ($($body:tt)*) => {
+        #[cfg(debug_assertions)]
+        {
+            $($body)*
+        }
+    };
+}
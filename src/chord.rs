@@ -1,7 +1,9 @@
 use bevy::{
-    input::keyboard::KeyCode,
+    input::keyboard::{Key, KeyCode, NativeKeyCode},
     prelude::{Deref, DerefMut, Resource, ReflectResource},
-    reflect::{Enum, Reflect},
+    reflect::{
+        DynamicEnum, DynamicVariant, Enum, FromReflect, Reflect, TypeInfo, Typed, VariantInfo,
+    },
 };
 
 use std::{collections::VecDeque, fmt};
@@ -14,29 +16,55 @@ pub struct KeyChord(pub Modifiers, pub KeyCode);
 
 impl fmt::Display for KeyChord {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use KeyCode::*;
         self.0.fmt(f)?;
         if !self.0.is_empty() {
             f.write_str("-")?;
         }
-        let key_repr = match self.1 {
-            Semicolon => ";",
-            Period => ".",
-            Equal => "=",
-            Slash => "/",
-            Minus => "-",
-            BracketLeft => "[",
-            BracketRight => "]",
-            Quote => "'",
-            Backquote => "`",
-            key_code => {
-                let mut key = key_code.variant_name();
-                key = key.strip_prefix("Key").unwrap_or(key);
-                key = key.strip_prefix("Digit").unwrap_or(key);
-                return f.write_str(key);
-            }
-        };
-        f.write_str(key_repr)
+        f.write_str(key_label(self.1))
+    }
+}
+
+/// Return the label of a key as shown on a US keyboard.
+pub(crate) fn key_label(key: KeyCode) -> &'static str {
+    use KeyCode::*;
+    match key {
+        Semicolon => ";",
+        Period => ".",
+        Equal => "=",
+        Slash => "/",
+        Minus => "-",
+        BracketLeft => "[",
+        BracketRight => "]",
+        Quote => "'",
+        Backquote => "`",
+        key_code => {
+            let TypeInfo::Enum(info) = KeyCode::type_info() else {
+                unreachable!("KeyCode is an enum");
+            };
+            // Take the name from the type info, which lives for 'static.
+            let key = info.variant_at(key_code.variant_index()).unwrap().name();
+            let key = key.strip_prefix("Key").unwrap_or(key);
+            key.strip_prefix("Digit").unwrap_or(key)
+        }
+    }
+}
+
+// Key chords are written in shorthand, e.g., "Ctrl-A".
+#[cfg(feature = "serde")]
+impl serde::Serialize for KeyChord {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let shorthand = crate::shorthand::key_chord(self).ok_or_else(|| {
+            serde::ser::Error::custom(format!("{self:?} has no shorthand"))
+        })?;
+        serializer.serialize_str(&shorthand)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for KeyChord {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let shorthand = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        crate::shorthand::parse_key_chord(&shorthand).map_err(serde::de::Error::custom)
     }
 }
 
@@ -47,6 +75,16 @@ impl From<(Modifiers, KeyCode)> for KeyChord {
     }
 }
 
+/// Take a raw modifier mask as found in `keyseq!` output, e.g., `(1,
+/// KeyCode::KeyW)` for Ctrl-W. Modifiers held beyond the mask keep the chord
+/// from matching.
+impl From<(u8, KeyCode)> for KeyChord {
+    #[inline(always)]
+    fn from((mods, key): (u8, KeyCode)) -> Self {
+        KeyChord(Modifiers::from(mods), key)
+    }
+}
+
 impl From<KeyCode> for KeyChord {
     #[inline(always)]
     fn from(key: KeyCode) -> Self {
@@ -54,6 +92,142 @@ impl From<KeyCode> for KeyChord {
     }
 }
 
+impl KeyChord {
+    /// Convert a web `KeyboardEvent.key` value like "ArrowUp", "a", or "!" into
+    /// a key chord.
+    ///
+    /// Key values are logical, so characters are mapped to the physical key
+    /// that produces them on a US layout; shifted characters like "A" or "!"
+    /// include [Modifiers::SHIFT]. Returns `None` for unknown values.
+    ///
+    /// ```
+    /// use bevy::prelude::KeyCode;
+    /// use bevy_input_sequence::{KeyChord, Modifiers};
+    /// assert_eq!(KeyChord::from_web_key_value("ArrowUp"),
+    ///            Some(KeyChord(Modifiers::empty(), KeyCode::ArrowUp)));
+    /// assert_eq!(KeyChord::from_web_key_value("?"),
+    ///            Some(KeyChord(Modifiers::SHIFT, KeyCode::Slash)));
+    /// ```
+    pub fn from_web_key_value(value: &str) -> Option<KeyChord> {
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_char(c),
+            _ => match value {
+                "Control" => Some(KeyCode::ControlLeft),
+                "Shift" => Some(KeyCode::ShiftLeft),
+                "Alt" => Some(KeyCode::AltLeft),
+                "Meta" | "Super" => Some(KeyCode::SuperLeft),
+                "Spacebar" => Some(KeyCode::Space),
+                name => key_code_from_name(name),
+            }
+            .map(KeyChord::from),
+        }
+    }
+
+    /// Convert a character into the key chord that produces it on a US layout.
+    fn from_char(c: char) -> Option<KeyChord> {
+        use KeyCode::*;
+        let (shift, key) = match c {
+            'a'..='z' => (false, key_code_from_name(&format!("Key{}", c.to_ascii_uppercase()))?),
+            'A'..='Z' => (true, key_code_from_name(&format!("Key{c}"))?),
+            '0'..='9' => (false, key_code_from_name(&format!("Digit{c}"))?),
+            ')' => (true, Digit0),
+            '!' => (true, Digit1),
+            '@' => (true, Digit2),
+            '#' => (true, Digit3),
+            '$' => (true, Digit4),
+            '%' => (true, Digit5),
+            '^' => (true, Digit6),
+            '&' => (true, Digit7),
+            '*' => (true, Digit8),
+            '(' => (true, Digit9),
+            ' ' => (false, Space),
+            '-' => (false, Minus),
+            '_' => (true, Minus),
+            '=' => (false, Equal),
+            '+' => (true, Equal),
+            '[' => (false, BracketLeft),
+            '{' => (true, BracketLeft),
+            ']' => (false, BracketRight),
+            '}' => (true, BracketRight),
+            '\\' => (false, Backslash),
+            '|' => (true, Backslash),
+            ';' => (false, Semicolon),
+            ':' => (true, Semicolon),
+            '\'' => (false, Quote),
+            '"' => (true, Quote),
+            ',' => (false, Comma),
+            '<' => (true, Comma),
+            '.' => (false, Period),
+            '>' => (true, Period),
+            '/' => (false, Slash),
+            '?' => (true, Slash),
+            '`' => (false, Backquote),
+            '~' => (true, Backquote),
+            _ => return None,
+        };
+        let mods = if shift {
+            Modifiers::SHIFT
+        } else {
+            Modifiers::empty()
+        };
+        Some(KeyChord(mods, key))
+    }
+}
+
+/// Convert a logical key, bevy's counterpart to winit's `Key`, into a key
+/// chord using the same rules as [KeyChord::from_web_key_value].
+impl TryFrom<&Key> for KeyChord {
+    type Error = ();
+
+    fn try_from(key: &Key) -> Result<Self, Self::Error> {
+        match key {
+            Key::Character(s) => KeyChord::from_web_key_value(s),
+            Key::Space => Some(KeyChord::from(KeyCode::Space)),
+            named => KeyChord::from_web_key_value(named.variant_name()),
+        }
+        .ok_or(())
+    }
+}
+
+/// Look up a [KeyCode] by its variant name, e.g., "KeyA" or "ArrowUp".
+pub(crate) fn key_code_from_name(name: &str) -> Option<KeyCode> {
+    let TypeInfo::Enum(info) = KeyCode::type_info() else {
+        unreachable!("KeyCode is an enum");
+    };
+    match info.variant(name)? {
+        VariantInfo::Unit(_) => {
+            KeyCode::from_reflect(&DynamicEnum::new(name, DynamicVariant::Unit))
+        }
+        _ => None,
+    }
+}
+
+/// Return the platform tag and scan code of an unidentified key, the stable
+/// encoding of [NativeKeyCode] used with the [KeyCode] variant name wherever
+/// keys are stored or hashed.
+pub(crate) fn native_key_code_parts(native: &NativeKeyCode) -> (u8, u32) {
+    match native {
+        NativeKeyCode::Unidentified => (0, 0),
+        NativeKeyCode::Android(code) => (1, *code),
+        NativeKeyCode::MacOS(code) => (2, *code as u32),
+        NativeKeyCode::Windows(code) => (3, *code as u32),
+        NativeKeyCode::Xkb(code) => (4, *code),
+    }
+}
+
+/// Inverse of [native_key_code_parts].
+pub(crate) fn native_key_code_from_parts(tag: u8, code: u32) -> Option<NativeKeyCode> {
+    Some(match tag {
+        0 => NativeKeyCode::Unidentified,
+        1 => NativeKeyCode::Android(code),
+        2 => NativeKeyCode::MacOS(u16::try_from(code).ok()?),
+        3 => NativeKeyCode::Windows(u16::try_from(code).ok()?),
+        4 => NativeKeyCode::Xkb(code),
+        _ => return None,
+    })
+}
+
 pub(crate) fn is_modifier(key: KeyCode) -> bool {
     !Modifiers::from(key).is_empty()
 }
@@ -0,0 +1,170 @@
+//! Merge sequences into a combo tree and report on its shape
+//!
+//! Designers with many sequences can inspect the shape of their entire control
+//! scheme: which prefixes are shared, where the sequences branch, and how many
+//! sequences end under each node.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, combo_tree::ComboTree};
+//!
+//! let mut world = World::new();
+//! let sequences = [
+//!     KeySequence::new(|| {}, keyseq! { W A S D }).build(&mut world),
+//!     KeySequence::new(|| {}, keyseq! { W D S A }).build(&mut world),
+//!     KeySequence::new(|| {}, keyseq! { Space }).build(&mut world),
+//! ];
+//! let tree = ComboTree::new(sequences.iter());
+//! let report = tree.report();
+//! assert_eq!(report.sequences, 3);
+//! assert_eq!(report.max_depth, 4);
+//! // The root and `W` are branch points.
+//! assert_eq!(report.branch_points.len(), 2);
+//! assert_eq!(report.branch_points[1].prefix, keyseq! { W }.map(KeyChord::from));
+//! ```
+use crate::input_sequence::InputSequence;
+use bevy::ecs::system::SystemInput;
+use std::collections::BTreeMap;
+
+/// A node in the combo tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComboNode<Act> {
+    /// Act that leads to this node. `None` for the root.
+    pub act: Option<Act>,
+    /// Number of acts from the root.
+    pub depth: usize,
+    /// Number of sequences that end exactly at this node.
+    pub terminals: usize,
+    /// Number of sequences that end at this node or below it.
+    pub leaves: usize,
+    /// Children ordered by act.
+    pub children: Vec<ComboNode<Act>>,
+}
+
+/// Sequences merged by their shared prefixes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComboTree<Act> {
+    /// Root node, which represents the empty prefix.
+    pub root: ComboNode<Act>,
+}
+
+/// A node where sequences diverge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchPoint<Act> {
+    /// Acts leading to this node.
+    pub prefix: Vec<Act>,
+    /// Number of distinct next acts.
+    pub branches: usize,
+    /// Number of sequences that end at or below this node.
+    pub leaves: usize,
+}
+
+/// Summary of a combo tree's shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComboTreeReport<Act> {
+    /// Number of sequences.
+    pub sequences: usize,
+    /// Number of nodes excluding the root.
+    pub nodes: usize,
+    /// Length of the longest sequence.
+    pub max_depth: usize,
+    /// Non-empty prefixes shared by more than one sequence.
+    pub shared_prefixes: Vec<Vec<Act>>,
+    /// Nodes with more than one child in depth-first order.
+    pub branch_points: Vec<BranchPoint<Act>>,
+}
+
+struct Builder<Act> {
+    terminals: usize,
+    children: BTreeMap<Act, Builder<Act>>,
+}
+
+impl<Act: Ord + Clone> Builder<Act> {
+    fn insert(&mut self, acts: &[Act]) {
+        match acts.split_first() {
+            None => self.terminals += 1,
+            Some((first, rest)) => self
+                .children
+                .entry(first.clone())
+                .or_insert_with(|| Builder {
+                    terminals: 0,
+                    children: BTreeMap::new(),
+                })
+                .insert(rest),
+        }
+    }
+
+    fn build(self, act: Option<Act>, depth: usize) -> ComboNode<Act> {
+        let children: Vec<_> = self
+            .children
+            .into_iter()
+            .map(|(act, builder)| builder.build(Some(act), depth + 1))
+            .collect();
+        let leaves = self.terminals + children.iter().map(|c| c.leaves).sum::<usize>();
+        ComboNode {
+            act,
+            depth,
+            terminals: self.terminals,
+            leaves,
+            children,
+        }
+    }
+}
+
+impl<Act: Ord + Clone> ComboTree<Act> {
+    /// Merge the given sequences into a tree.
+    pub fn new<'a, I>(sequences: impl IntoIterator<Item = &'a InputSequence<Act, I>>) -> Self
+    where
+        Act: 'a,
+        I: SystemInput + 'static,
+    {
+        let mut builder = Builder {
+            terminals: 0,
+            children: BTreeMap::new(),
+        };
+        for sequence in sequences {
+            builder.insert(&sequence.acts);
+        }
+        ComboTree {
+            root: builder.build(None, 0),
+        }
+    }
+
+    /// Summarize the shape of the tree.
+    pub fn report(&self) -> ComboTreeReport<Act> {
+        let mut report = ComboTreeReport {
+            sequences: self.root.leaves,
+            nodes: 0,
+            max_depth: 0,
+            shared_prefixes: Vec::new(),
+            branch_points: Vec::new(),
+        };
+        let mut prefix = Vec::new();
+        Self::visit(&self.root, &mut prefix, &mut report);
+        report
+    }
+
+    fn visit(node: &ComboNode<Act>, prefix: &mut Vec<Act>, report: &mut ComboTreeReport<Act>) {
+        if let Some(act) = &node.act {
+            prefix.push(act.clone());
+            report.nodes += 1;
+            if node.leaves > 1 {
+                report.shared_prefixes.push(prefix.clone());
+            }
+        }
+        report.max_depth = report.max_depth.max(node.depth);
+        if node.children.len() > 1 {
+            report.branch_points.push(BranchPoint {
+                prefix: prefix.clone(),
+                branches: node.children.len(),
+                leaves: node.leaves,
+            });
+        }
+        for child in &node.children {
+            Self::visit(child, prefix, report);
+        }
+        if node.act.is_some() {
+            prefix.pop();
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Keep a sequence from firing again too soon
+//!
+//! Give a sequence a [Cooldown] with the builder's
+//! [cooldown](crate::input_sequence::InputSequenceBuilder::cooldown) option
+//! and, after it fires, it can't fire again until the cooldown has elapsed,
+//! e.g., so a held key's auto-repeat doesn't spam its event. Matches during
+//! the cooldown are dropped.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(
+//!         KeySequence::new(|| info!("fireball"), keyseq! { ArrowDown ArrowRight F })
+//!             .cooldown(Duration::from_secs(2)),
+//!     );
+//! }
+//! ```
+use bevy::{
+    ecs::{component::Component, entity::Entity, world::World},
+    time::Time,
+};
+use std::time::Duration;
+
+/// Least game time between two firings of the sequence on this entity.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cooldown {
+    /// Length of the cooldown.
+    pub duration: Duration,
+    ready_at: Option<Duration>,
+}
+
+impl Cooldown {
+    /// Wait `duration` after each firing.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            ready_at: None,
+        }
+    }
+
+    /// Return true if the sequence may fire at game time `now`.
+    pub fn is_ready(&self, now: Duration) -> bool {
+        self.ready_at.is_none_or(|ready_at| now >= ready_at)
+    }
+}
+
+/// Return true if the sequence on `entity` may fire now, and start its
+/// cooldown if so.
+pub(crate) fn start_cooldown(world: &mut World, entity: Entity) -> bool {
+    let now = world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default();
+    let Some(mut cooldown) = world.get_mut::<Cooldown>(entity) else {
+        return true;
+    };
+    if !cooldown.is_ready(now) {
+        return false;
+    }
+    cooldown.ready_at = Some(now + cooldown.duration);
+    true
+}
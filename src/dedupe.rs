@@ -0,0 +1,143 @@
+//! Drop presses a device reports as both keyboard and gamepad input
+//!
+//! Some devices, like the Steam Deck in desktop mode, report one physical
+//! press as a gamepad button and as a key, e.g., A as `Enter`. Insert a
+//! [DeviceDedupe] resource listing such pairs. When both halves of a pair are
+//! pressed within its window, only the first one reaches the matchers; on a
+//! tie, the [preferred](DeviceDedupe::prefer) device wins.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, dedupe::DeviceDedupe};
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(InputSequencePlugin::default().match_button(true))
+//!    .insert_resource(DeviceDedupe::steam_deck());
+//! ```
+use bevy::{
+    core::FrameCount,
+    ecs::{
+        entity::Entity,
+        system::{Query, Res, ResMut, Resource},
+    },
+    input::{
+        gamepad::{Gamepad, GamepadButton},
+        keyboard::KeyCode,
+        ButtonInput,
+    },
+};
+use std::collections::{HashMap, HashSet};
+
+/// Which device's press counts when both are pressed in the same frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PreferDevice {
+    /// Keep the gamepad press.
+    #[default]
+    Gamepad,
+    /// Keep the key press.
+    Keyboard,
+}
+
+/// Pairs of gamepad buttons and keys that one physical press reports.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DeviceDedupe {
+    /// Buttons and the keys they also report.
+    pub pairs: Vec<(GamepadButton, KeyCode)>,
+    /// Most frames between the two reports of one press.
+    pub window: u32,
+    /// Device that wins a tie.
+    pub prefer: PreferDevice,
+}
+
+impl Default for DeviceDedupe {
+    fn default() -> Self {
+        Self {
+            pairs: Vec::new(),
+            window: 1,
+            prefer: PreferDevice::default(),
+        }
+    }
+}
+
+impl DeviceDedupe {
+    /// Pairs the Steam Deck reports in desktop mode.
+    pub fn steam_deck() -> Self {
+        use GamepadButton::*;
+        Self {
+            pairs: vec![
+                (South, KeyCode::Enter),
+                (East, KeyCode::Escape),
+                (DPadUp, KeyCode::ArrowUp),
+                (DPadDown, KeyCode::ArrowDown),
+                (DPadLeft, KeyCode::ArrowLeft),
+                (DPadRight, KeyCode::ArrowRight),
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+/// Presses dropped this frame.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct DuplicatePresses {
+    frame: Option<u32>,
+    pub(crate) keys: HashSet<KeyCode>,
+    pub(crate) buttons: HashSet<(Entity, GamepadButton)>,
+    /// Frame of the last kept press of each key and button.
+    keys_seen: HashMap<KeyCode, u32>,
+    buttons_seen: HashMap<GamepadButton, u32>,
+}
+
+/// Find the presses to drop this frame. Runs once per frame even if added to
+/// several schedules.
+pub(crate) fn detect_duplicate_presses(
+    dedupe: Option<Res<DeviceDedupe>>,
+    frame_count: Res<FrameCount>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut duplicates: ResMut<DuplicatePresses>,
+) {
+    let frame = frame_count.0;
+    if duplicates.frame == Some(frame) {
+        return;
+    }
+    duplicates.frame = Some(frame);
+    duplicates.keys.clear();
+    duplicates.buttons.clear();
+    let Some(dedupe) = dedupe else {
+        return;
+    };
+    let pressed_keys: HashSet<KeyCode> = keys
+        .map(|keys| keys.get_just_pressed().copied().collect())
+        .unwrap_or_default();
+    let pressed_buttons: Vec<(Entity, GamepadButton)> = gamepads
+        .iter()
+        .flat_map(|(id, gamepad)| gamepad.get_just_pressed().map(move |b| (id, *b)))
+        .collect();
+    let within = |seen: Option<&u32>| seen.is_some_and(|f| frame - f <= dedupe.window);
+    for (button, key) in &dedupe.pairs {
+        let key_pressed = pressed_keys.contains(key);
+        let pads: Vec<Entity> = pressed_buttons
+            .iter()
+            .filter(|(_, b)| b == button)
+            .map(|(id, _)| *id)
+            .collect();
+        let drop_key = key_pressed
+            && (within(duplicates.buttons_seen.get(button))
+                || (!pads.is_empty() && dedupe.prefer == PreferDevice::Gamepad));
+        let drop_buttons = !pads.is_empty()
+            && (within(duplicates.keys_seen.get(key))
+                || (key_pressed && dedupe.prefer == PreferDevice::Keyboard));
+        if drop_key {
+            duplicates.keys.insert(*key);
+        } else if key_pressed {
+            duplicates.keys_seen.insert(*key, frame);
+        }
+        if drop_buttons {
+            duplicates.buttons.extend(pads.iter().map(|id| (*id, *button)));
+        } else if !pads.is_empty() {
+            duplicates.buttons_seen.insert(*button, frame);
+        }
+    }
+}
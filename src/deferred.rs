@@ -0,0 +1,81 @@
+//! Register sequences from other threads
+//!
+//! Asset loaders and network tasks can build sequences off the main thread and
+//! submit them through a [SequenceSender]. The plugin drains the queue each
+//! frame before matching.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, deferred::DeferredSequences};
+//!
+//! fn load_keymap(deferred: Res<DeferredSequences>) {
+//!     let sender = deferred.sender();
+//!     std::thread::spawn(move || {
+//!         sender.send(KeySequence::new(|| info!("save"), keyseq! { Ctrl-S }));
+//!     });
+//! }
+//! ```
+use bevy::ecs::{
+    system::Resource,
+    world::{Command, World},
+};
+use std::sync::{
+    mpsc::{channel, Receiver, Sender},
+    Mutex,
+};
+
+type Deferred = Box<dyn FnOnce(&mut World) + Send>;
+
+/// Queue of sequence registrations submitted from any thread.
+#[derive(Resource)]
+pub struct DeferredSequences {
+    sender: Sender<Deferred>,
+    receiver: Mutex<Receiver<Deferred>>,
+}
+
+impl Default for DeferredSequences {
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            sender,
+            receiver: Mutex::new(receiver),
+        }
+    }
+}
+
+impl DeferredSequences {
+    /// Return a sender that can be moved to another thread.
+    pub fn sender(&self) -> SequenceSender {
+        SequenceSender(self.sender.clone())
+    }
+}
+
+/// Submits sequence registrations to [DeferredSequences].
+#[derive(Clone, Debug)]
+pub struct SequenceSender(Sender<Deferred>);
+
+impl SequenceSender {
+    /// Queue a command, usually an input sequence builder, to be applied on
+    /// the next frame. Return false if the app is gone.
+    pub fn send(&self, command: impl Command) -> bool {
+        self.0
+            .send(Box::new(move |world: &mut World| command.apply(world)))
+            .is_ok()
+    }
+}
+
+/// Apply all queued registrations.
+pub(crate) fn drain_deferred_sequences(world: &mut World) {
+    let Some(queued) = world.get_resource::<DeferredSequences>().map(|deferred| {
+        deferred
+            .receiver
+            .lock()
+            .map(|receiver| receiver.try_iter().collect::<Vec<_>>())
+            .unwrap_or_default()
+    }) else {
+        return;
+    };
+    for apply in queued {
+        apply(world);
+    }
+}
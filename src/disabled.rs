@@ -0,0 +1,170 @@
+//! Switch sequences off without despawning them
+//!
+//! Insert [Disabled] on an entity and its sequences stop matching, e.g.,
+//! during a cutscene; their partial matches are dropped. Remove it and they
+//! match again from scratch.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, disabled::Disabled};
+//!
+//! #[derive(Resource)]
+//! struct Pause(Entity);
+//!
+//! fn setup(mut commands: Commands) {
+//!     let pause = commands.spawn_empty().id();
+//!     commands.entity(pause).queue(
+//!         KeySequence::new(|| info!("pause"), keyseq! { Escape }));
+//!     commands.insert_resource(Pause(pause));
+//! }
+//!
+//! fn start_cutscene(pause: Res<Pause>, mut commands: Commands) {
+//!     commands.entity(pause.0).insert(Disabled);
+//! }
+//!
+//! fn end_cutscene(pause: Res<Pause>, mut commands: Commands) {
+//!     commands.entity(pause.0).remove::<Disabled>();
+//! }
+//! ```
+//!
+//! A sequence built with the
+//! [once](crate::input_sequence::InputSequenceBuilder::once) option gets
+//! [Once] and disables itself after it fires.
+use crate::matched::SequenceComplete;
+use bevy::{
+    app::App,
+    ecs::{
+        component::{Component, ComponentHooks, ComponentId, StorageType},
+        entity::Entity,
+        observer::Trigger,
+        query::With,
+        system::{Commands, Query, Resource},
+        world::{DeferredWorld, EntityWorldMut, World},
+    },
+};
+use std::any::TypeId;
+
+type Restore = Box<dyn FnOnce(&mut EntityWorldMut) + Send + Sync>;
+type Take = fn(&mut EntityWorldMut, &mut Vec<Restore>);
+
+/// Keep the sequences on this entity from matching.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Disabled;
+
+impl Component for Disabled {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks
+            .on_add(|mut world: DeferredWorld, entity: Entity, _: ComponentId| {
+                world
+                    .commands()
+                    .queue(move |world: &mut World| park_sequences(world, entity));
+            })
+            .on_remove(|mut world: DeferredWorld, entity: Entity, _: ComponentId| {
+                world
+                    .commands()
+                    .queue(move |world: &mut World| restore_sequences(world, entity));
+            });
+    }
+}
+
+/// Insert [Disabled] on this entity once its sequence fires.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Once;
+
+/// Disable [Once] sequences that completed.
+pub(crate) fn disable_once(
+    trigger: Trigger<SequenceComplete>,
+    once: Query<(), With<Once>>,
+    mut commands: Commands,
+) {
+    let id = trigger.entity();
+    if once.contains(id) {
+        commands.entity(id).insert(Disabled);
+    }
+}
+
+/// Sequence components [Disabled] takes off an entity.
+#[derive(Resource, Default)]
+pub(crate) struct SequenceComponents(Vec<(TypeId, Take)>);
+
+/// Register `C` as a sequence component that [Disabled] parks and
+/// [ResetTag](crate::tag::ResetTag) resets. Every sequence type the plugin
+/// matches must be registered alongside its matcher.
+pub(crate) fn register_sequence<C: Component>(app: &mut App) {
+    let mut components = app
+        .world_mut()
+        .get_resource_or_insert_with(SequenceComponents::default);
+    let id = TypeId::of::<C>();
+    if !components.0.iter().any(|(other, _)| *other == id) {
+        components.0.push((id, take::<C>));
+    }
+}
+
+/// Sequences taken off a [Disabled] entity.
+#[derive(Component)]
+struct Parked(Vec<Restore>);
+
+fn take<C: Component>(entity: &mut EntityWorldMut, parked: &mut Vec<Restore>) {
+    if let Some(component) = entity.take::<C>() {
+        parked.push(Box::new(move |entity| {
+            entity.insert(component);
+        }));
+    }
+}
+
+fn park_sequences(world: &mut World, id: Entity) {
+    let takes = sequence_components(world);
+    let Ok(mut entity) = world.get_entity_mut(id) else {
+        return;
+    };
+    // It may have been enabled again meanwhile.
+    if !entity.contains::<Disabled>() {
+        return;
+    }
+    let mut parked = entity.take::<Parked>().map(|p| p.0).unwrap_or_default();
+    for take in takes {
+        take(&mut entity, &mut parked);
+    }
+    entity.insert(Parked(parked));
+}
+
+fn sequence_components(world: &World) -> Vec<Take> {
+    world
+        .get_resource::<SequenceComponents>()
+        .map(|components| components.0.iter().map(|(_, take)| *take).collect())
+        .unwrap_or_default()
+}
+
+/// Take the sequences off entity `id` and put them right back, so the
+/// matchers see them as new and drop their partial matches.
+pub(crate) fn reset_sequences(world: &mut World, id: Entity) {
+    let takes = sequence_components(world);
+    let Ok(mut entity) = world.get_entity_mut(id) else {
+        return;
+    };
+    let mut taken = Vec::new();
+    for take in takes {
+        take(&mut entity, &mut taken);
+    }
+    for restore in taken {
+        restore(&mut entity);
+    }
+}
+
+fn restore_sequences(world: &mut World, id: Entity) {
+    let Ok(mut entity) = world.get_entity_mut(id) else {
+        return;
+    };
+    // It may have been disabled again meanwhile.
+    if entity.contains::<Disabled>() {
+        return;
+    }
+    let Some(Parked(parked)) = entity.take::<Parked>() else {
+        return;
+    };
+    for restore in parked {
+        restore(&mut entity);
+    }
+}
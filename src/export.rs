@@ -0,0 +1,139 @@
+//! Write key sequences as bindings for other programs
+//!
+//! The converse of [import](crate::import): turn key chords into a VS Code
+//! keybinding like `"ctrl+k ctrl+b"` or an Emacs `kbd` string like
+//! `"C-x C-s"`, e.g., to document an app's keymap or hand it to an external
+//! tool.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, export};
+//!
+//! let acts: Vec<KeyChord> = keyseq! { Ctrl-X Ctrl-S }
+//!     .into_iter()
+//!     .map(KeyChord::from)
+//!     .collect();
+//! assert_eq!(export::vscode(&acts).as_deref(), Some("ctrl+x ctrl+s"));
+//! assert_eq!(export::emacs(&acts).as_deref(), Some("C-x C-s"));
+//! ```
+use crate::{modifier, KeyChord, Modifiers};
+use bevy::{input::keyboard::KeyCode, reflect::Enum};
+
+/// Return the lowercase name editors use for `key`.
+fn key_name(key: KeyCode) -> Option<String> {
+    use KeyCode::*;
+    let name = match key {
+        Enter => "enter",
+        Escape => "escape",
+        Space => "space",
+        Tab => "tab",
+        Backspace => "backspace",
+        Delete => "delete",
+        Insert => "insert",
+        Home => "home",
+        End => "end",
+        PageUp => "pageup",
+        PageDown => "pagedown",
+        ArrowUp => "up",
+        ArrowDown => "down",
+        ArrowLeft => "left",
+        ArrowRight => "right",
+        BracketLeft => "[",
+        BracketRight => "]",
+        Quote => "'",
+        Backquote => "`",
+        Backslash => "\\",
+        Semicolon => ";",
+        Comma => ",",
+        Period => ".",
+        Equal => "=",
+        Slash => "/",
+        Minus => "-",
+        key => {
+            let name = key.variant_name();
+            let short = name
+                .strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+                .filter(|rest| rest.len() == 1)
+                .map(str::to_string)
+                .or_else(|| {
+                    let number = name
+                        .strip_prefix('F')
+                        .or_else(|| name.strip_prefix("Numpad"))?;
+                    number.parse::<u8>().ok()?;
+                    Some(name.to_string())
+                })?;
+            return Some(short.to_ascii_lowercase());
+        }
+    };
+    Some(name.to_string())
+}
+
+fn sequence(acts: &[KeyChord], chord: impl Fn(&KeyChord) -> Option<String>) -> Option<String> {
+    let chords: Option<Vec<String>> = acts.iter().map(chord).collect();
+    Some(chords?.join(" "))
+}
+
+/// Write a VS Code keybinding, e.g., "ctrl+k ctrl+shift+b". Returns `None` if
+/// a chord uses game-defined modifiers or a key VS Code has no name for.
+pub fn vscode(acts: &[KeyChord]) -> Option<String> {
+    sequence(acts, |KeyChord(mods, key)| {
+        if mods.intersects(modifier::CUSTOM) {
+            return None;
+        }
+        let mut chord = String::new();
+        for (modifier, name) in [
+            (Modifiers::CONTROL, "ctrl+"),
+            (Modifiers::SHIFT, "shift+"),
+            (Modifiers::ALT, "alt+"),
+            (Modifiers::SUPER, "meta+"),
+        ] {
+            if mods.contains(modifier) {
+                chord.push_str(name);
+            }
+        }
+        chord.push_str(&key_name(*key)?);
+        Some(chord)
+    })
+}
+
+/// Write an Emacs `kbd` string, e.g., "C-x C-s". Shifted letters are written
+/// in uppercase. Returns `None` if a chord uses game-defined modifiers or a
+/// key Emacs has no name for.
+pub fn emacs(acts: &[KeyChord]) -> Option<String> {
+    sequence(acts, |KeyChord(mods, key)| {
+        if mods.intersects(modifier::CUSTOM) {
+            return None;
+        }
+        let name = key_name(*key)?;
+        let letter = name.len() == 1 && name.chars().all(|c| c.is_ascii_lowercase());
+        let mut chord = String::new();
+        for (modifier, prefix) in [
+            (Modifiers::CONTROL, "C-"),
+            (Modifiers::ALT, "M-"),
+            (Modifiers::SUPER, "s-"),
+        ] {
+            if mods.contains(modifier) {
+                chord.push_str(prefix);
+            }
+        }
+        if mods.contains(Modifiers::SHIFT) && !letter {
+            chord.push_str("S-");
+        }
+        let name = match name.as_str() {
+            _ if letter && mods.contains(Modifiers::SHIFT) => name.to_ascii_uppercase(),
+            "enter" => "RET".into(),
+            "space" => "SPC".into(),
+            "tab" => "TAB".into(),
+            "escape" => "ESC".into(),
+            "backspace" => "DEL".into(),
+            _ if name.chars().count() == 1 => name,
+            _ => match name.strip_prefix("numpad") {
+                Some(number) => format!("<kp-{number}>"),
+                None => format!("<{name}>"),
+            },
+        };
+        chord.push_str(&name);
+        Some(chord)
+    })
+}
@@ -0,0 +1,192 @@
+//! Stable content hashes of sequences
+//!
+//! [fingerprint] hashes a sequence's acts, time limit, and press requirements,
+//! or a whole [Keymap] including binding names, to the same `u64` in every
+//! process, on every platform, and across runs, unlike `std`'s randomly seeded
+//! hasher. Use it to cache derived data, to detect duplicates, or to check that
+//! a client and server agree on a keymap.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, fingerprint::fingerprint};
+//!
+//! let mut world = World::new();
+//! let a = KeySequence::new(|| {}, keyseq! { Ctrl-X Ctrl-S }).build(&mut world);
+//! let b = KeySequence::new(|| info!("save"), keyseq! { Ctrl-X Ctrl-S }).build(&mut world);
+//! assert_eq!(fingerprint(&a), fingerprint(&b));
+//! ```
+use crate::{
+    button::ButtonAct,
+    input_sequence::{InputSequence, Press},
+    keymap::{Binding, Keymap},
+    KeyChord, TimeLimit,
+};
+use bevy::{ecs::system::SystemInput, input::gamepad::GamepadButton};
+
+/// A 64-bit FNV-1a hasher with a fixed seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl StableHasher {
+    /// Add bytes.
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// Add an integer in little-endian order.
+    pub fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+
+    /// Add a string prefixed by its length.
+    pub fn write_str(&mut self, s: &str) {
+        self.write_u64(s.len() as u64);
+        self.write(s.as_bytes());
+    }
+
+    /// Return the hash.
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// A value with a hash that doesn't depend on the process or platform.
+pub trait StableHash {
+    /// Add this value to `hasher`.
+    fn stable_hash(&self, hasher: &mut StableHasher);
+}
+
+/// Return the stable hash of `value`.
+pub fn fingerprint<T: StableHash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.stable_hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<T: StableHash> StableHash for [T] {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_u64(self.len() as u64);
+        for item in self {
+            item.stable_hash(hasher);
+        }
+    }
+}
+
+impl<T: StableHash> StableHash for Option<T> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        match self {
+            None => hasher.write(&[0]),
+            Some(value) => {
+                hasher.write(&[1]);
+                value.stable_hash(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for KeyChord {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        let KeyChord(mods, key) = self;
+        hasher.write(&[mods.bits()]);
+        // Variant names are stable where discriminants may not be.
+        hasher.write_str(&format!("{key:?}"));
+    }
+}
+
+impl StableHash for GamepadButton {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_str(&format!("{self:?}"));
+    }
+}
+
+impl StableHash for ButtonAct {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        let tag = match self {
+            ButtonAct::Press(_) => 0,
+            ButtonAct::SoftPull(_) => 1,
+            ButtonAct::HardPull(_) => 2,
+            ButtonAct::Release(_) => 3,
+            ButtonAct::Tap(_) => 4,
+            ButtonAct::Hold(_) => 5,
+            ButtonAct::Stick(_, direction) => 6 + *direction as u8,
+            ButtonAct::Any(group) => 32 + *group as u8,
+        };
+        hasher.write(&[tag]);
+        self.button().stable_hash(hasher);
+    }
+}
+
+impl StableHash for TimeLimit {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        match self {
+            TimeLimit::Frames(frames) => {
+                hasher.write(&[0]);
+                hasher.write_u64(u64::from(*frames));
+            }
+            TimeLimit::Duration(duration) | TimeLimit::RealDuration(duration) => {
+                hasher.write(&[if matches!(self, TimeLimit::Duration(_)) { 1 } else { 2 }]);
+                hasher.write_u64(duration.as_secs());
+                hasher.write_u64(u64::from(duration.subsec_nanos()));
+            }
+        }
+    }
+}
+
+impl StableHash for Press {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        match self {
+            Press::JustPressed => hasher.write(&[0]),
+            Press::Pressed => hasher.write(&[1]),
+            Press::HeldUntilNext => hasher.write(&[2]),
+            Press::HeldFor(duration) => {
+                hasher.write(&[3]);
+                hasher.write_u64(duration.as_secs());
+                hasher.write_u64(u64::from(duration.subsec_nanos()));
+            }
+        }
+    }
+}
+
+/// Hashes the acts, time limit, press requirements, and intervals but not
+/// the system.
+impl<Act: StableHash, I: SystemInput> StableHash for InputSequence<Act, I> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.acts.stable_hash(hasher);
+        self.time_limit.stable_hash(hasher);
+        // Missing press requirements are the default ones.
+        for i in 0..self.acts.len() {
+            self.press(i).stable_hash(hasher);
+        }
+        // Only limited intervals, so sequences without any hash as before.
+        for i in 0..self.acts.len() {
+            if let Some(max) = self.interval(i) {
+                hasher.write_u64(i as u64);
+                hasher.write_u64(max.as_secs());
+                hasher.write_u64(u64::from(max.subsec_nanos()));
+            }
+        }
+    }
+}
+
+impl StableHash for Binding {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_str(&self.name);
+        self.acts.stable_hash(hasher);
+        self.time_limit.stable_hash(hasher);
+    }
+}
+
+impl StableHash for Keymap {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.bindings.stable_hash(hasher);
+    }
+}
@@ -0,0 +1,70 @@
+//! Sequences of a length known at compile time
+//!
+//! A [FixedSequence] keeps its acts in an array, so bindings known at compile
+//! time can be `const` items without allocating. The output of `keyseq!` is an
+//! array and can be used directly. Register one with
+//! [InputSequence::from_fixed].
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, fixed::FixedSequence};
+//!
+//! const KONAMI: FixedSequence<(Modifiers, KeyCode), 4> =
+//!     FixedSequence::new(keyseq! { ArrowUp ArrowUp ArrowDown ArrowDown });
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(KeySequence::from_fixed(|| info!("konami"), &KONAMI));
+//! }
+//! ```
+//!
+//! An empty sequence is rejected at compile time.
+//!
+//! ```compile_fail
+//! use bevy_input_sequence::{prelude::*, fixed::FixedSequence};
+//!
+//! const EMPTY: FixedSequence<KeyChord, 0> = FixedSequence::new([]);
+//! ```
+use crate::{
+    cond_system::IntoCondSystem,
+    input_sequence::{InputSequence, InputSequenceBuilder},
+};
+use bevy::ecs::system::SystemInput;
+
+/// A sequence of exactly `N` acts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FixedSequence<T, const N: usize> {
+    /// Acts in order.
+    pub acts: [T; N],
+}
+
+impl<T, const N: usize> FixedSequence<T, N> {
+    /// Number of acts.
+    pub const LEN: usize = N;
+
+    /// Create a fixed sequence. Fails to compile if `N` is zero.
+    pub const fn new(acts: [T; N]) -> Self {
+        const { assert!(N > 0, "An input sequence needs at least one act") };
+        Self { acts }
+    }
+}
+
+impl<Act, In> InputSequence<Act, In>
+where
+    In: SystemInput + Send + Sync + 'static,
+{
+    /// Create new input sequence from a fixed sequence. Not operant until
+    /// added to an entity.
+    pub fn from_fixed<T, C, M, const N: usize>(
+        system: C,
+        fixed: &FixedSequence<T, N>,
+    ) -> InputSequenceBuilder<Act, C::System, In>
+    where
+        T: Clone,
+        C: IntoCondSystem<In, (), M> + 'static,
+        Act: From<T>,
+    {
+        let mut builder = InputSequenceBuilder::new(system);
+        builder.acts = fixed.acts.iter().cloned().map(Act::from).collect();
+        builder
+    }
+}
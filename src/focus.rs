@@ -0,0 +1,32 @@
+//! Route key sequences by focus
+//!
+//! Editor-like apps may bind the same key sequence in different panes. Give
+//! each pane's sequences a [FocusOwner] and set [SequenceFocus] to the pane
+//! that holds focus. Only sequences without a focus owner or whose owner has
+//! focus will match. A focus-owned sequence takes precedence over a global one
+//! with the same acts.
+//!
+//! When focus changes, partial progress is handed off: if the newly focused
+//! keymap shares the prefix typed so far, matching continues from there.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, focus::{FocusOwner, SequenceFocus}};
+//!
+//! fn setup(mut commands: Commands) {
+//!     let left = commands.spawn_empty().id();
+//!     commands.entity(left).insert(FocusOwner(left)).queue(
+//!         KeySequence::new(|| info!("close left pane"), keyseq! { Ctrl-W Q }));
+//!     commands.insert_resource(SequenceFocus(Some(left)));
+//! }
+//! ```
+use bevy::ecs::{component::Component, entity::Entity, system::Resource};
+
+/// The entity that currently holds focus, if any.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SequenceFocus(pub Option<Entity>);
+
+/// Only match the key sequence on this entity while the given entity holds
+/// [SequenceFocus].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusOwner(pub Entity);
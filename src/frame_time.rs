@@ -1,9 +1,16 @@
 use crate::time_limit::TimeLimit;
+use bevy::{
+    core::FrameCount,
+    ecs::system::{Res, ResMut, Resource},
+    time::{Real, Time, Virtual},
+};
+use std::time::Duration;
 
 #[derive(Clone, Debug)]
 pub(crate) struct FrameTime {
     pub(crate) frame: u32,
     pub(crate) time: f32,
+    pub(crate) real: f32,
 }
 
 impl std::ops::Sub for &FrameTime {
@@ -13,15 +20,61 @@ impl std::ops::Sub for &FrameTime {
         FrameTime {
             frame: self.frame - other.frame,
             time: self.time - other.time,
+            real: self.real - other.real,
         }
     }
 }
 
 impl FrameTime {
+    /// Return the current frame time, leaving out the frames and real time
+    /// spent paused.
+    pub(crate) fn now(
+        frame_count: &FrameCount,
+        time: &Time,
+        real_time: &Time<Real>,
+        paused: &PausedTime,
+    ) -> Self {
+        FrameTime {
+            frame: frame_count.0.wrapping_sub(paused.frames),
+            time: time.elapsed_secs(),
+            real: real_time.elapsed().saturating_sub(paused.real).as_secs_f32(),
+        }
+    }
+
+    /// Return the elapsed virtual time of a difference of frame times.
+    pub(crate) fn duration(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f32(self.time.max(0.0))
+    }
+
     pub(crate) fn has_timedout(&self, time_limit: &TimeLimit) -> bool {
         match time_limit {
             TimeLimit::Frames(f) => self.frame > *f,
             TimeLimit::Duration(d) => self.time > d.as_secs_f32(),
+            TimeLimit::RealDuration(d) => self.real > d.as_secs_f32(),
         }
     }
 }
+
+/// Frames and real time that passed while virtual time was paused.
+///
+/// Time limits don't count them, so pausing the game freezes partial matches
+/// instead of letting them time out. Game time already stands still while
+/// paused.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct PausedTime {
+    pub(crate) frames: u32,
+    pub(crate) real: Duration,
+}
+
+/// Count the frames and real time of the current update if virtual time is
+/// paused.
+pub(crate) fn track_paused_time(
+    virtual_time: Res<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+    mut paused: ResMut<PausedTime>,
+) {
+    if virtual_time.is_paused() {
+        paused.frames = paused.frames.wrapping_add(1);
+        paused.real += real_time.delta();
+    }
+}
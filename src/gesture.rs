@@ -0,0 +1,136 @@
+//! Touchpad gesture acts
+//!
+//! On platforms that report touchpad gestures, e.g., macOS, two-finger swipes,
+//! pinches, and double taps become [GestureAct]s that gesture sequences can
+//! match. Enable with
+//! [match_gesture](crate::InputSequencePlugin::match_gesture).
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, gesture::{GestureAct, GestureSequence}};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(GestureSequence::new(
+//!         || info!("next page"),
+//!         [GestureAct::SwipeLeft, GestureAct::SwipeLeft],
+//!     ));
+//! }
+//! ```
+use crate::{act_queue::ActQueue, input_sequence::InputSequence};
+use bevy::{
+    ecs::{
+        event::EventReader,
+        reflect::ReflectResource,
+        system::{Local, Res, ResMut, Resource},
+    },
+    input::gestures::{DoubleTapGesture, PanGesture, PinchGesture},
+    math::Vec2,
+    reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+/// An act of a touchpad gesture sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum GestureAct {
+    /// Two fingers swipe up.
+    SwipeUp,
+    /// Two fingers swipe down.
+    SwipeDown,
+    /// Two fingers swipe left.
+    SwipeLeft,
+    /// Two fingers swipe right.
+    SwipeRight,
+    /// Two fingers move together.
+    PinchIn,
+    /// Two fingers move apart.
+    PinchOut,
+    /// Two fingers tap twice.
+    DoubleTap,
+}
+
+/// Represents a touchpad gesture sequence
+pub type GestureSequence = InputSequence<GestureAct, ()>;
+
+/// How far a gesture must go to count.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct GestureCalibration {
+    /// Distance in logical pixels a pan must travel to count as a swipe.
+    pub swipe_distance: f32,
+    /// Change in magnification a pinch must reach.
+    pub pinch_amount: f32,
+}
+
+impl Default for GestureCalibration {
+    fn default() -> Self {
+        Self {
+            swipe_distance: 50.0,
+            pinch_amount: 0.1,
+        }
+    }
+}
+
+/// Progress of the gesture in flight.
+#[derive(Default)]
+pub(crate) struct GestureProgress {
+    pan: Vec2,
+    pinch: f32,
+    swiped: bool,
+    pinched: bool,
+}
+
+/// Turn gesture events into acts. A gesture yields at most one swipe and one
+/// pinch; it ends on the first update without its events.
+pub(crate) fn queue_gesture_acts(
+    mut pans: EventReader<PanGesture>,
+    mut pinches: EventReader<PinchGesture>,
+    mut taps: EventReader<DoubleTapGesture>,
+    calibration: Res<GestureCalibration>,
+    mut progress: Local<GestureProgress>,
+    mut queue: ResMut<ActQueue<GestureAct>>,
+) {
+    let mut panned = false;
+    for PanGesture(delta) in pans.read() {
+        panned = true;
+        progress.pan += *delta;
+    }
+    if !panned {
+        progress.pan = Vec2::ZERO;
+        progress.swiped = false;
+    } else if !progress.swiped && progress.pan.length() >= calibration.swipe_distance {
+        progress.swiped = true;
+        let pan = progress.pan;
+        // Screen coordinates: y grows downward.
+        queue.push_back(if pan.x.abs() > pan.y.abs() {
+            if pan.x > 0.0 {
+                GestureAct::SwipeRight
+            } else {
+                GestureAct::SwipeLeft
+            }
+        } else if pan.y > 0.0 {
+            GestureAct::SwipeDown
+        } else {
+            GestureAct::SwipeUp
+        });
+    }
+
+    let mut pinching = false;
+    for PinchGesture(delta) in pinches.read() {
+        pinching = true;
+        progress.pinch += *delta;
+    }
+    if !pinching {
+        progress.pinch = 0.0;
+        progress.pinched = false;
+    } else if !progress.pinched && progress.pinch.abs() >= calibration.pinch_amount {
+        progress.pinched = true;
+        queue.push_back(if progress.pinch > 0.0 {
+            GestureAct::PinchOut
+        } else {
+            GestureAct::PinchIn
+        });
+    }
+
+    for _ in taps.read() {
+        queue.push_back(GestureAct::DoubleTap);
+    }
+}
@@ -0,0 +1,46 @@
+//! Add app-wide hotkeys without managing entities
+//!
+//! Sequences that are global and never change, e.g., debug hotkeys, can be
+//! added while building the app with
+//! [add_input_sequence](AddInputSequence::add_input_sequence). It sends the
+//! given event whenever the chords are entered.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, global::AddInputSequence};
+//!
+//! #[derive(Event, Clone)]
+//! struct ToggleBorders;
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(InputSequencePlugin::default())
+//!    .add_input_sequence(keyseq! { Ctrl-K Ctrl-B }, ToggleBorders);
+//! ```
+use crate::{action::send_event, input_sequence::KeySequence, KeyChord};
+use bevy::{
+    app::App,
+    ecs::{event::Event, world::Command},
+};
+
+/// Add global key sequences to an app.
+pub trait AddInputSequence {
+    /// Send `event` whenever `acts` are entered.
+    fn add_input_sequence<T, E>(&mut self, acts: impl IntoIterator<Item = T>, event: E) -> &mut Self
+    where
+        KeyChord: From<T>,
+        E: Event + Clone;
+}
+
+impl AddInputSequence for App {
+    fn add_input_sequence<T, E>(&mut self, acts: impl IntoIterator<Item = T>, event: E) -> &mut Self
+    where
+        KeyChord: From<T>,
+        E: Event + Clone,
+    {
+        self.add_event::<E>();
+        // The sequence still lives on an entity, but nothing refers to it.
+        KeySequence::new(send_event(event), acts).apply(self.world_mut());
+        self
+    }
+}
@@ -0,0 +1,59 @@
+//! Suppress sequence matching while a token is held
+//!
+//! Dialogs, pause menus, and text fields can ask [InputSequenceGuard] for a
+//! [SuppressToken]. No sequence matches while any token is alive, and partial
+//! matches are dropped. Dropping the last token resumes matching. Because
+//! tokens are counted, independent plugins can suppress matching without
+//! stepping on each other's flags.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::guard::{InputSequenceGuard, SuppressToken};
+//!
+//! /// Sequences are suppressed for as long as a dialog exists.
+//! #[derive(Component)]
+//! struct Dialog(SuppressToken);
+//!
+//! fn open_dialog(guard: Res<InputSequenceGuard>, mut commands: Commands) {
+//!     commands.spawn(Dialog(guard.suppress()));
+//! }
+//! ```
+use bevy::ecs::system::Resource;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Hands out tokens that suppress sequence matching.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct InputSequenceGuard {
+    count: Arc<AtomicUsize>,
+}
+
+impl InputSequenceGuard {
+    /// Suppress sequence matching until the returned token is dropped.
+    pub fn suppress(&self) -> SuppressToken {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        SuppressToken {
+            count: self.count.clone(),
+        }
+    }
+
+    /// Return true if any token is alive.
+    pub fn is_suppressed(&self) -> bool {
+        self.count.load(Ordering::SeqCst) > 0
+    }
+}
+
+/// Suppresses sequence matching while alive.
+#[derive(Debug)]
+#[must_use = "matching resumes as soon as the token is dropped"]
+pub struct SuppressToken {
+    count: Arc<AtomicUsize>,
+}
+
+impl Drop for SuppressToken {
+    fn drop(&mut self) {
+        self.count.fetch_sub(1, Ordering::SeqCst);
+    }
+}
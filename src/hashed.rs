@@ -0,0 +1,329 @@
+//! Match key sequences stored as salted hashes
+//!
+//! A [HashedKeySequence] stores only the length and a salted hash of its
+//! chords, which obscures the secret code from anyone reading the binary's
+//! strings. It does not protect it: the hash is fast and the salt is stored
+//! beside it, so short codes can be recovered by trying every sequence.
+//!
+//! The matcher keeps a rolling hash of the most recent chords for each
+//! sequence length and compares it with the stored hashes, which costs the
+//! same per input no matter how long the codes are.
+//!
+//! Plain key sequences can use the same matcher with
+//! [rolling()](crate::input_sequence::InputSequenceBuilder::rolling).
+//!
+//! # Discovery protection
+//!
+//! Hashed sequences are meant for content that players shouldn't find by
+//! probing, e.g., unreleased characters in a competitive game. Besides
+//! obscuring the chords, the matcher keeps no per-sequence progress, so entering
+//! part of a code is indistinguishable from entering anything else:
+//!
+//! - No [SequenceAborted](crate::matched::SequenceAborted),
+//!   [SequenceProgress](crate::progress::SequenceProgress), or
+//!   [TutorialHint](crate::tutorial::TutorialHint) is produced for them, and
+//!   they are not in the [KeySequenceCache](crate::cache::KeySequenceCache)
+//!   or the [registry](crate::registry).
+//! - The work per chord is one rolling hash update and one lookup per
+//!   distinct sequence length, however much of a code has been entered.
+//! - The only observable effect is the sequence's system running once the
+//!   whole code is entered.
+//!
+//! Rolling plain key sequences get the same treatment from the matcher but
+//! keep their chords in memory in the clear.
+//!
+//! Compute the digest ahead of time, e.g., in a test, and paste its numbers
+//! into the game:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, hashed::{HashedKeySequence, SequenceDigest}};
+//!
+//! const SECRET: SequenceDigest = SequenceDigest::from_parts(5, 0x5eed, 0xbf34_3eb2_55b4_8f3d);
+//!
+//! let acts: Vec<KeyChord> = keyseq! { I D K F A }.into_iter().map(KeyChord::from).collect();
+//! assert_eq!(SequenceDigest::new(&acts, 0x5eed), SECRET);
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(HashedKeySequence::new(|| info!("secret"), SECRET));
+//! }
+//! ```
+use crate::{
+    chord::native_key_code_parts, cond_system::IntoCondSystem, frame_time::FrameTime,
+    input_sequence::sequence_builder_commands, time_limit::TimeLimit, KeyChord,
+};
+use bevy::{
+    ecs::{
+        component::Component,
+        system::{IntoSystem, System, SystemId},
+        world::World,
+    },
+    input::keyboard::KeyCode,
+    reflect::Enum,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+/// Modulus of the rolling hash, the Mersenne prime 2^61 - 1.
+const MODULUS: u64 = (1 << 61) - 1;
+/// Base of the rolling hash.
+const BASE: u64 = 0x1000_0000_01b3;
+
+/// The length and salted hash of a sequence of key chords.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SequenceDigest {
+    /// Number of chords.
+    pub len: usize,
+    /// Salt mixed into the hash.
+    pub salt: u64,
+    /// Salted hash of the chords.
+    pub hash: u64,
+}
+
+impl SequenceDigest {
+    /// Hash the given chords with `salt`.
+    pub fn new(acts: &[KeyChord], salt: u64) -> Self {
+        let sum = acts
+            .iter()
+            .fold(0, |sum, chord| add(mul(sum, BASE), chord_code(chord)));
+        Self {
+            len: acts.len(),
+            salt,
+            hash: mix(sum, salt),
+        }
+    }
+
+    /// Create a digest from previously computed parts.
+    pub const fn from_parts(len: usize, salt: u64, hash: u64) -> Self {
+        Self { len, salt, hash }
+    }
+}
+
+/// Marks a key sequence to be matched by the rolling window matcher instead of
+/// the trie.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Rolling;
+
+/// A key sequence stored as a digest of its chords.
+#[derive(Component, Clone, Debug)]
+pub struct HashedKeySequence {
+    /// System to run when the sequence matches.
+    pub system_id: SystemId,
+    /// Digest of the chords.
+    pub digest: SequenceDigest,
+    /// Optional time limit from the first chord.
+    pub time_limit: Option<TimeLimit>,
+}
+
+/// Builder for a [HashedKeySequence].
+///
+/// Besides a time limit it has no options of its own. Of the
+/// [InputSequenceBuilder](crate::input_sequence::InputSequenceBuilder)
+/// options, [Cheat](crate::cheat::Cheat),
+/// [Namespace](crate::namespace::Namespace), [Once](crate::disabled::Once),
+/// [Cooldown](crate::cooldown::Cooldown), [Tags](crate::tag::Tags), and
+/// [MatchSchedule](crate::schedule::MatchSchedule) are honored when inserted
+/// on the sequence's entity; the rest are ignored.
+pub struct HashedKeySequenceBuilder<S> {
+    system: S,
+    digest: SequenceDigest,
+    time_limit: Option<TimeLimit>,
+}
+
+impl HashedKeySequence {
+    /// Create a hashed key sequence. Not operant until added to an entity.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<C, M>(system: C, digest: SequenceDigest) -> HashedKeySequenceBuilder<C::System>
+    where
+        C: IntoCondSystem<(), (), M> + 'static,
+    {
+        HashedKeySequenceBuilder {
+            system: IntoSystem::into_system(system),
+            digest,
+            time_limit: None,
+        }
+    }
+}
+
+impl<S> HashedKeySequenceBuilder<S>
+where
+    S: System<In = (), Out = ()>,
+{
+    /// Specify a time limit from the start of the first matching input.
+    pub fn time_limit(mut self, time_limit: impl Into<TimeLimit>) -> Self {
+        self.time_limit = Some(time_limit.into());
+        self
+    }
+
+    /// Build the sequence. Requires world to register the system.
+    pub fn build(self, world: &mut World) -> HashedKeySequence {
+        HashedKeySequence {
+            system_id: world.register_system(self.system),
+            digest: self.digest,
+            time_limit: self.time_limit,
+        }
+    }
+}
+
+sequence_builder_commands!(HashedKeySequenceBuilder);
+
+/// A matchable sequence known to the rolling matcher.
+#[derive(Clone, Debug)]
+pub(crate) struct RollingEntry {
+    pub(crate) system_id: SystemId,
+    pub(crate) digest: SequenceDigest,
+    pub(crate) time_limit: Option<TimeLimit>,
+}
+
+/// Rolling hashes of the most recent chords for each sequence length.
+#[derive(Default)]
+pub(crate) struct RollingWindow {
+    /// Entries grouped by length, salt, and hash.
+    index: HashMap<(usize, u64, u64), Vec<RollingEntry>>,
+    /// Distinct lengths and salts.
+    groups: Vec<(usize, u64)>,
+    /// Recent chord codes and their times, at most the longest length plus one.
+    recent: VecDeque<(u64, FrameTime)>,
+    /// Rolling hash and base power for each length.
+    sums: HashMap<usize, (u64, u64)>,
+}
+
+impl RollingWindow {
+    /// Replace the entries to match.
+    pub(crate) fn set_entries(&mut self, entries: impl IntoIterator<Item = RollingEntry>) {
+        self.index.clear();
+        for entry in entries {
+            let d = entry.digest;
+            self.index.entry((d.len, d.salt, d.hash)).or_default().push(entry);
+        }
+        self.groups = self.index.keys().map(|(len, salt, _)| (*len, *salt)).collect();
+        self.groups.sort();
+        self.groups.dedup();
+        // Recompute the sums for the new lengths from the recent chords.
+        self.sums.clear();
+        for (len, _) in &self.groups {
+            let skip = self.recent.len().saturating_sub(*len);
+            let sum = self
+                .recent
+                .iter()
+                .skip(skip)
+                .fold(0, |sum, (code, _)| add(mul(sum, BASE), *code));
+            self.sums.insert(*len, (sum, pow(BASE, *len)));
+        }
+    }
+
+    /// Return true if there is nothing to match.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Forget the recent chords.
+    pub(crate) fn clear(&mut self) {
+        self.recent.clear();
+        for (sum, _) in self.sums.values_mut() {
+            *sum = 0;
+        }
+    }
+
+    /// Push a chord and return the entries that match the recent chords along
+    /// with how long they took. A match forgets the recent chords.
+    pub(crate) fn push(&mut self, chord: &KeyChord, now: &FrameTime) -> Vec<(&RollingEntry, Duration)> {
+        let code = chord_code(chord);
+        self.recent.push_back((code, now.clone()));
+        let n = self.recent.len();
+        for (len, (sum, power)) in self.sums.iter_mut() {
+            *sum = add(mul(*sum, BASE), code);
+            if n > *len {
+                let (out, _) = self.recent[n - 1 - *len];
+                *sum = sub(*sum, mul(out, *power));
+            }
+        }
+        let max_len = self.groups.iter().map(|(len, _)| *len).max().unwrap_or(0);
+        while self.recent.len() > max_len + 1 {
+            self.recent.pop_front();
+        }
+        let n = self.recent.len();
+        let mut matches = Vec::new();
+        for (len, salt) in &self.groups {
+            if n < *len {
+                continue;
+            }
+            let (sum, _) = self.sums[len];
+            let Some(entries) = self.index.get(&(*len, *salt, mix(sum, *salt))) else {
+                continue;
+            };
+            let (_, start) = &self.recent[n - *len];
+            let elapsed = now - start;
+            matches.extend(
+                entries
+                    .iter()
+                    .filter(|entry| {
+                        !entry
+                            .time_limit
+                            .as_ref()
+                            .is_some_and(|limit| elapsed.has_timedout(limit))
+                    })
+                    .map(|entry| (entry, elapsed.duration())),
+            );
+        }
+        if !matches.is_empty() {
+            // Start over so the chords of a match don't count toward another,
+            // as with the trie.
+            self.recent.clear();
+            for (sum, _) in self.sums.values_mut() {
+                *sum = 0;
+            }
+        }
+        matches
+    }
+}
+
+/// Code a chord by its modifiers and key name. The variant index would be
+/// shorter but changes when Bevy adds keys, which would break digests pasted
+/// into a game.
+fn chord_code(KeyChord(mods, key): &KeyChord) -> u64 {
+    let mut hash = fnv1a(FNV_OFFSET, &[mods.bits()]);
+    hash = fnv1a(hash, key.variant_name().as_bytes());
+    if let KeyCode::Unidentified(native) = key {
+        let (tag, code) = native_key_code_parts(native);
+        hash = fnv1a(hash, &[tag]);
+        hash = fnv1a(hash, &code.to_le_bytes());
+    }
+    // Offset by one so no chord hashes like an empty prefix.
+    hash % (MODULUS - 1) + 1
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn add(a: u64, b: u64) -> u64 {
+    (a + b) % MODULUS
+}
+
+fn sub(a: u64, b: u64) -> u64 {
+    (a + MODULUS - b) % MODULUS
+}
+
+fn mul(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+fn pow(base: u64, exp: usize) -> u64 {
+    (0..exp).fold(1, |acc, _| mul(acc, base))
+}
+
+/// Mix the salt into the hash so equal sequences with different salts differ.
+fn mix(sum: u64, salt: u64) -> u64 {
+    // SplitMix64 finalizer.
+    let mut z = sum ^ salt.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
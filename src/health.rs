@@ -0,0 +1,143 @@
+//! Disable sequences on gamepads with a critical battery or bad connection
+//!
+//! Bevy doesn't report battery level or link quality, so the platform layer
+//! attaches a [GamepadHealth] component to each gamepad and keeps it current.
+//! Insert a [GamepadGate] resource and gamepads it deems unhealthy stop
+//! contributing acts; their partial matches are dropped. Each change is sent
+//! as a [GamepadGateChanged] event, e.g., to show a "low battery" notice on a
+//! kiosk.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, health::GamepadGate};
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(InputSequencePlugin::default().match_button(true))
+//!    .insert_resource(GamepadGate::default());
+//! ```
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, EventWriter},
+    query::With,
+    system::{Query, Res, ResMut, Resource},
+};
+use bevy::input::gamepad::Gamepad;
+use std::collections::HashMap;
+
+/// Quality of a gamepad's connection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Connection {
+    /// Input arrives reliably.
+    #[default]
+    Stable,
+    /// Input may be dropped or delayed.
+    Unstable,
+}
+
+/// Health a platform reports for a gamepad.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct GamepadHealth {
+    /// Charge from 0 to 1, if known.
+    pub battery: Option<f32>,
+    /// Connection quality.
+    pub connection: Connection,
+}
+
+/// Why a gamepad is gated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GateReason {
+    /// Battery at or below [GamepadGate::critical_battery].
+    CriticalBattery,
+    /// Connection reported as [Connection::Unstable].
+    UnstableConnection,
+}
+
+/// When to disable sequences on a gamepad.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct GamepadGate {
+    /// Battery level at or below which a gamepad is gated.
+    pub critical_battery: f32,
+    /// Gate gamepads with an unstable connection.
+    pub unstable_connection: bool,
+}
+
+impl Default for GamepadGate {
+    fn default() -> Self {
+        Self {
+            critical_battery: 0.05,
+            unstable_connection: true,
+        }
+    }
+}
+
+impl GamepadGate {
+    /// Return why `health` is gated, if it is.
+    pub fn reason(&self, health: &GamepadHealth) -> Option<GateReason> {
+        if health
+            .battery
+            .is_some_and(|level| level <= self.critical_battery)
+        {
+            Some(GateReason::CriticalBattery)
+        } else if self.unstable_connection && health.connection == Connection::Unstable {
+            Some(GateReason::UnstableConnection)
+        } else {
+            None
+        }
+    }
+}
+
+/// Sent when a gamepad becomes gated or ungated.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GamepadGateChanged {
+    /// The gamepad.
+    pub gamepad: Entity,
+    /// Why it is gated, or `None` if it no longer is.
+    pub reason: Option<GateReason>,
+}
+
+/// Gamepads currently gated.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct GatedGamepads(HashMap<Entity, GateReason>);
+
+impl GatedGamepads {
+    /// Return true if `gamepad` is gated.
+    pub(crate) fn contains(&self, gamepad: &Entity) -> bool {
+        self.0.contains_key(gamepad)
+    }
+}
+
+/// Gate or ungate gamepads according to their health.
+pub(crate) fn update_gamepad_gates(
+    gate: Option<Res<GamepadGate>>,
+    gamepads: Query<(Entity, Option<&GamepadHealth>), With<Gamepad>>,
+    mut gated: ResMut<GatedGamepads>,
+    mut changes: EventWriter<GamepadGateChanged>,
+) {
+    let mut now = HashMap::new();
+    if let Some(gate) = gate {
+        for (id, health) in &gamepads {
+            if let Some(reason) = health.and_then(|health| gate.reason(health)) {
+                now.insert(id, reason);
+            }
+        }
+    }
+    for (gamepad, reason) in &now {
+        if gated.0.get(gamepad) != Some(reason) {
+            changes.send(GamepadGateChanged {
+                gamepad: *gamepad,
+                reason: Some(*reason),
+            });
+        }
+    }
+    for gamepad in gated.0.keys() {
+        if !now.contains_key(gamepad) {
+            changes.send(GamepadGateChanged {
+                gamepad: *gamepad,
+                reason: None,
+            });
+        }
+    }
+    gated.0 = now;
+}
@@ -0,0 +1,120 @@
+//! Display a key sequence with live progress
+//!
+//! Requires the "ui" feature. Add [KeySequenceHint] to an entity and it becomes
+//! a text node that shows the chords of the key sequence on the given entity.
+//! Chords that have been typed so far are drawn in the matched color. The hint
+//! is rebuilt when its sequence is added or changed, so it may be spawned
+//! before the sequence.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, hint::KeySequenceHint};
+//!
+//! fn setup(mut commands: Commands) {
+//!     let save = commands.spawn_empty().id();
+//!     commands.entity(save).queue(
+//!         KeySequence::new(|| info!("save"), keyseq! { Ctrl-X Ctrl-S }));
+//!     commands.spawn(KeySequenceHint::new(save));
+//! }
+//! ```
+use bevy::{
+    color::{palettes::css, Color},
+    ecs::{
+        change_detection::{DetectChanges, Ref},
+        component::Component,
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res},
+    },
+    hierarchy::{BuildChildren, ChildBuild, Children, DespawnRecursiveExt},
+    text::{TextColor, TextSpan},
+    ui::widget::Text,
+};
+
+use crate::{cache::KeySequenceCache, input_sequence::KeySequence, region::KeyboardRegion};
+
+/// Show the chords of the key sequence on `sequence` and highlight the ones
+/// matched so far.
+#[derive(Component, Debug, Clone)]
+#[require(Text)]
+pub struct KeySequenceHint {
+    /// Entity with the [KeySequence] to show.
+    pub sequence: Entity,
+    /// Color of chords matched so far.
+    pub matched_color: Color,
+    /// Color of chords not yet matched.
+    pub pending_color: Color,
+}
+
+impl KeySequenceHint {
+    /// Show the key sequence on `sequence` with the default colors.
+    pub fn new(sequence: Entity) -> Self {
+        Self {
+            sequence,
+            matched_color: css::GOLD.into(),
+            pending_color: css::GRAY.into(),
+        }
+    }
+}
+
+/// Create one text span per chord whenever a hint is added or changed, its
+/// sequence changes, or the [KeyboardRegion] changes.
+pub(crate) fn build_hints(
+    hints: Query<(Entity, Ref<KeySequenceHint>)>,
+    sequences: Query<Ref<KeySequence>>,
+    region: Option<Res<KeyboardRegion>>,
+    mut commands: Commands,
+) {
+    let region_changed = region.as_ref().is_some_and(|region| region.is_changed());
+    let region = region.map(|region| *region).unwrap_or_default();
+    for (id, hint) in &hints {
+        let Ok(sequence) = sequences.get(hint.sequence) else {
+            continue;
+        };
+        if !hint.is_changed() && !sequence.is_changed() && !region_changed {
+            continue;
+        }
+        let mut entity = commands.entity(id);
+        entity.despawn_descendants().with_children(|parent| {
+            for (i, chord) in sequence.acts.iter().enumerate() {
+                let chord = chord.display(region);
+                let text = if i == 0 {
+                    chord.to_string()
+                } else {
+                    format!(" {chord}")
+                };
+                parent.spawn((TextSpan::new(text), TextColor(hint.pending_color)));
+            }
+        });
+    }
+}
+
+/// Color the spans according to the partial match.
+pub(crate) fn update_hints(
+    hints: Query<(&KeySequenceHint, &Children)>,
+    sequences: Query<&KeySequence>,
+    cache: Res<KeySequenceCache>,
+    mut spans: Query<&mut TextColor, With<TextSpan>>,
+) {
+    let prefix = cache.prefix();
+    for (hint, children) in &hints {
+        let matched = sequences
+            .get(hint.sequence)
+            .ok()
+            .filter(|sequence| sequence.acts.starts_with(&prefix))
+            .map(|_| prefix.len())
+            .unwrap_or(0);
+        for (i, child) in children.iter().enumerate() {
+            if let Ok(mut color) = spans.get_mut(*child) {
+                let new = if i < matched {
+                    hint.matched_color
+                } else {
+                    hint.pending_color
+                };
+                if color.0 != new {
+                    color.0 = new;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Timed hold-and-release acts
+//!
+//! A [HoldAct] is a key held down and released within a target window, e.g.,
+//! the power meter of a golf swing. The act is entered when the key is
+//! released, so a sequence of them reads as "hold A for about a second, then
+//! tap B quickly". Enable with
+//! [match_hold](crate::InputSequencePlugin::match_hold).
+//!
+//! Only keyboard keys can be held; gamepad buttons have
+//! [ButtonAct::Hold](crate::button::ButtonAct::Hold) instead.
+//!
+//! How long the last key was held is in the `held` field of
+//! [SequenceComplete](crate::matched::SequenceComplete) and
+//! [Matched](crate::matched::Matched), so a completed sequence can tell how
+//! close the player came.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{
+//!     prelude::*,
+//!     hold::{HoldAct, HoldSequence},
+//!     matched::SequenceComplete,
+//! };
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands
+//!         .spawn_empty()
+//!         .queue(HoldSequence::new(
+//!             || {},
+//!             [HoldAct::new(
+//!                 KeyCode::Space,
+//!                 Duration::from_millis(900),
+//!                 Duration::from_millis(1100),
+//!             )],
+//!         ))
+//!         .observe(|trigger: Trigger<SequenceComplete>| {
+//!             info!("swing held for {:?}", trigger.held);
+//!         });
+//! }
+//!
+//! App::new()
+//!     .add_plugins(MinimalPlugins)
+//!     .add_plugins(InputSequencePlugin::default().match_hold(true))
+//!     .add_systems(Startup, setup);
+//! ```
+use crate::{
+    act_queue::{ActHeld, ActQueue},
+    input_sequence::InputSequence,
+};
+use bevy::{
+    ecs::system::{Local, Query, Res, ResMut},
+    input::{keyboard::KeyCode, ButtonInput},
+    reflect::Reflect,
+    time::Time,
+};
+use std::{collections::HashMap, time::Duration};
+
+/// A key held for at least `min` and at most `max` before it is released.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub struct HoldAct {
+    /// Key to hold
+    pub key: KeyCode,
+    /// Shortest hold accepted
+    pub min: Duration,
+    /// Longest hold accepted
+    pub max: Duration,
+}
+
+impl HoldAct {
+    /// Hold `key` between `min` and `max`.
+    pub fn new(key: KeyCode, min: Duration, max: Duration) -> Self {
+        Self { key, min, max }
+    }
+
+    /// Return true if holding `key` for `held` satisfies this act.
+    pub fn accepts(&self, key: KeyCode, held: Duration) -> bool {
+        self.key == key && self.min <= held && held <= self.max
+    }
+}
+
+/// Represents a sequence of timed holds
+pub type HoldSequence = InputSequence<HoldAct, ()>;
+
+/// Queue a [HoldAct] for each key released this update, along with how long it
+/// was held in game time. The act is the first one found among the registered
+/// sequences that accepts the hold, so windows for the same key should not
+/// overlap. A hold no window accepts is queued as an act
+/// of its own, which matches nothing.
+pub(crate) fn queue_hold_acts(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    sequences: Query<&HoldSequence>,
+    mut pressed_at: Local<HashMap<KeyCode, Duration>>,
+    mut queue: ResMut<ActQueue<HoldAct>>,
+    mut queue_held: ResMut<ActHeld<HoldAct>>,
+) {
+    let now = time.elapsed();
+    for key in keys.get_just_pressed() {
+        pressed_at.insert(*key, now);
+    }
+    for key in keys.get_just_released() {
+        let Some(start) = pressed_at.remove(key) else {
+            continue;
+        };
+        let held = now - start;
+        let act = sequences
+            .iter()
+            .flat_map(|seq| seq.acts.iter())
+            .find(|act| act.accepts(*key, held))
+            .copied()
+            .unwrap_or(HoldAct::new(*key, held, held));
+        queue.push_back(act);
+        queue_held.push_back(held);
+    }
+}
@@ -0,0 +1,145 @@
+//! Read key bindings written for other programs
+//!
+//! Convert a VS Code keybinding like `"ctrl+k ctrl+b"` or an Emacs `kbd`
+//! string like `"C-x C-s"` into key chords, so keymaps users already know can
+//! be carried over as is.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, import};
+//!
+//! let save = import::emacs("C-x C-s").unwrap();
+//! assert_eq!(save, import::vscode("ctrl+x ctrl+s").unwrap());
+//! KeySequence::new(|| info!("save"), save);
+//! ```
+use crate::{
+    shorthand::{key_code_table, parse_key_code, ParseError},
+    KeyChord, Modifiers,
+};
+use bevy::input::keyboard::KeyCode;
+
+/// Return the key a lowercase name common to editors refers to.
+fn named_key(name: &str) -> Option<KeyCode> {
+    use KeyCode::*;
+    let key = match name {
+        "enter" | "return" | "ret" => Enter,
+        "escape" | "esc" => Escape,
+        "space" | "spc" => Space,
+        "tab" => Tab,
+        "backspace" => Backspace,
+        "delete" | "deletechar" => Delete,
+        "insert" => Insert,
+        "home" => Home,
+        "end" => End,
+        "pageup" | "prior" => PageUp,
+        "pagedown" | "next" => PageDown,
+        "up" => ArrowUp,
+        "down" => ArrowDown,
+        "left" => ArrowLeft,
+        "right" => ArrowRight,
+        name => {
+            let (prefix, number) = name
+                .strip_prefix('f')
+                .map(|n| ("F", n))
+                .or_else(|| name.strip_prefix("numpad").map(|n| ("Numpad", n)))
+                .or_else(|| name.strip_prefix("kp-").map(|n| ("Numpad", n)))?;
+            number.parse::<u8>().ok()?;
+            return crate::chord::key_code_from_name(&format!("{prefix}{number}"));
+        }
+    };
+    Some(key)
+}
+
+fn key(name: &str, table: &[(KeyCode, String)]) -> Option<KeyCode> {
+    if name.chars().count() == 1 {
+        parse_key_code(name, table)
+    } else {
+        named_key(&name.to_ascii_lowercase())
+    }
+}
+
+fn chords(
+    text: &str,
+    chord: impl Fn(&str, &[(KeyCode, String)]) -> Result<KeyChord, ParseError>,
+) -> Result<Vec<KeyChord>, ParseError> {
+    let table = key_code_table();
+    let acts = text
+        .split_whitespace()
+        .map(|token| chord(token, &table))
+        .collect::<Result<Vec<_>, _>>()?;
+    if acts.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(acts)
+}
+
+/// Parse a VS Code keybinding, e.g., "ctrl+k ctrl+shift+b". "cmd", "meta",
+/// and "win" map to [Modifiers::SUPER].
+pub fn vscode(text: &str) -> Result<Vec<KeyChord>, ParseError> {
+    chords(text, |token, table| {
+        let mut mods = Modifiers::empty();
+        let mut parts = token.split('+').peekable();
+        let mut rest = "";
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                rest = part;
+                break;
+            }
+            mods |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" => Modifiers::CONTROL,
+                "alt" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                "cmd" | "meta" | "win" | "super" => Modifiers::SUPER,
+                _ => return Err(ParseError::UnknownKey(part.to_string())),
+            };
+        }
+        if rest.is_empty() {
+            return Err(ParseError::MissingKey(token.to_string()));
+        }
+        key(rest, table)
+            .map(|key| KeyChord(mods, key))
+            .ok_or_else(|| ParseError::UnknownKey(rest.to_string()))
+    })
+}
+
+/// Parse an Emacs `kbd` string, e.g., "C-x C-s" or "M-<f1>". "M-" maps to
+/// [Modifiers::ALT] and "s-" to [Modifiers::SUPER]; an uppercase letter adds
+/// [Modifiers::SHIFT]. "DEL" is the backspace key as in Emacs.
+pub fn emacs(text: &str) -> Result<Vec<KeyChord>, ParseError> {
+    chords(text, |token, table| {
+        let mut mods = Modifiers::empty();
+        let mut rest = token;
+        loop {
+            let modifier = match rest.get(..2) {
+                Some("C-") => Modifiers::CONTROL,
+                Some("M-") | Some("A-") => Modifiers::ALT,
+                Some("S-") => Modifiers::SHIFT,
+                Some("s-") => Modifiers::SUPER,
+                _ => break,
+            };
+            // The key itself may be "-".
+            if rest.len() == 2 {
+                break;
+            }
+            mods |= modifier;
+            rest = &rest[2..];
+        }
+        if rest.is_empty() {
+            return Err(ParseError::MissingKey(token.to_string()));
+        }
+        let name = rest
+            .strip_prefix('<')
+            .and_then(|name| name.strip_suffix('>'))
+            .unwrap_or(rest);
+        if name.len() == 1 && name.chars().all(|c| c.is_ascii_uppercase()) {
+            mods |= Modifiers::SHIFT;
+        }
+        let key = if name == "DEL" {
+            Some(KeyCode::Backspace)
+        } else {
+            key(name, table)
+        };
+        key.map(|key| KeyChord(mods, key))
+            .ok_or_else(|| ParseError::UnknownKey(rest.to_string()))
+    })
+}
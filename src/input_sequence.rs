@@ -1,8 +1,29 @@
 //! Input sequences for keys and gamepad buttons
-use crate::{cond_system::IntoCondSystem, time_limit::TimeLimit, KeyChord};
+use crate::{
+    adaptive::{adapts, AdaptiveWindow},
+    button::ButtonAct,
+    cheat::Cheat,
+    cond_system::IntoCondSystem,
+    cooldown::Cooldown,
+    disabled::Once,
+    hashed::Rolling,
+    matched::{Emit, MatchPriority},
+    namespace::Namespace,
+    prerequisite::Prerequisite,
+    progress::ReportProgress,
+    schedule::MatchSchedule,
+    score::Scoring,
+    stick::StickAsDpad,
+    tag::Tags,
+    time_limit::TimeLimit,
+    tolerance::{MatchMode, Tolerance},
+    tutorial::Tutorial,
+    KeyChord,
+};
 use std::{
     fmt,
     marker::PhantomData,
+    time::Duration,
 };
 
 use bevy::{
@@ -11,30 +32,113 @@ use bevy::{
         prelude::In,
         component::Component,
         entity::Entity,
+        reflect::ReflectComponent,
+        schedule::ScheduleLabel,
         system::{IntoSystem, System, SystemId, SystemInput},
         world::World,
     },
-    input::gamepad::GamepadButton,
-    reflect::Reflect,
+    log::warn,
+    reflect::{utility::GenericTypePathCell, Reflect, TypePath},
 };
 
 /// An input sequence is a series of acts that fires an event when matched with
 /// inputs within the given time limit.
 ///
 /// InputSequence<KeyChord, ()>
-/// InputSequence<GamepadButton, In<Entity>>
+/// InputSequence<ButtonAct, In<Entity>>
 #[derive(Component, Reflect)]
-#[reflect(from_reflect = false)]
+#[reflect(Component, type_path = false, where Act: TypePath)]
 pub struct InputSequence<Act, I: SystemInput + 'static> {
     /// Event emitted
-    #[reflect(ignore)]
+    ///
+    /// Systems are not reflected; a sequence built from reflection, e.g., one
+    /// loaded from a scene, runs nothing until this is assigned.
+    #[reflect(ignore, default = "unassigned_system")]
     pub system_id: SystemId<I>,
     /// Sequence of acts that trigger input sequence
     pub acts: Vec<Act>,
     /// Optional time limit after first match
     pub time_limit: Option<TimeLimit>,
+    /// How each act must be pressed; acts without an entry are
+    /// [Press::JustPressed].
+    pub presses: Vec<Press>,
+    /// Longest time allowed since the previous act for each act; acts
+    /// without an entry have no limit.
+    pub intervals: Vec<Option<Duration>>,
 }
 
+// System inputs like `In<Entity>` do not implement [TypePath], so name them by
+// their Rust type name instead.
+impl<Act: TypePath, I: SystemInput + 'static> TypePath for InputSequence<Act, I> {
+    fn type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            format!(
+                "bevy_input_sequence::input_sequence::InputSequence<{}, {}>",
+                Act::type_path(),
+                std::any::type_name::<I>()
+            )
+        })
+    }
+
+    fn short_type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            format!(
+                "InputSequence<{}, {}>",
+                Act::short_type_path(),
+                std::any::type_name::<I>()
+            )
+        })
+    }
+
+    fn type_ident() -> Option<&'static str> {
+        Some("InputSequence")
+    }
+
+    fn crate_name() -> Option<&'static str> {
+        Some("bevy_input_sequence")
+    }
+
+    fn module_path() -> Option<&'static str> {
+        Some("bevy_input_sequence::input_sequence")
+    }
+}
+
+fn unassigned_system<I: SystemInput>() -> SystemId<I> {
+    SystemId::from_entity(Entity::PLACEHOLDER)
+}
+
+/// How the input for a step must be pressed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Press {
+    /// Require a fresh press.
+    #[default]
+    JustPressed,
+    /// Accept a fresh press or an input that is already held. A held input
+    /// satisfies at most one step per press.
+    Pressed,
+    /// Require a fresh press that is still held when the next step is pressed.
+    HeldUntilNext,
+    /// Require a fresh press that is held for at least this long and is still
+    /// held when the next step is pressed, e.g., to charge a move. On the last
+    /// step it only requires a fresh press.
+    HeldFor(std::time::Duration),
+}
+
+impl<Act, I: SystemInput> InputSequence<Act, I> {
+    /// Return how the act at `index` must be pressed.
+    pub fn press(&self, index: usize) -> Press {
+        self.presses.get(index).copied().unwrap_or_default()
+    }
+
+    /// Return the longest time allowed between the acts at `index - 1` and
+    /// `index`, if limited.
+    pub fn interval(&self, index: usize) -> Option<Duration> {
+        self.intervals.get(index).copied().flatten()
+    }
+}
 
 impl<Act: Clone> Clone for InputSequence<Act, ()> {
     fn clone(&self) -> Self {
@@ -42,6 +146,8 @@ impl<Act: Clone> Clone for InputSequence<Act, ()> {
             system_id: self.system_id,
             acts: self.acts.clone(),
             time_limit: self.time_limit.clone(),
+            presses: self.presses.clone(),
+            intervals: self.intervals.clone(),
         }
     }
 }
@@ -52,6 +158,8 @@ impl<Act: Clone> Clone for InputSequence<Act, In<Entity>> {
             system_id: self.system_id,
             acts: self.acts.clone(),
             time_limit: self.time_limit.clone(),
+            presses: self.presses.clone(),
+            intervals: self.intervals.clone(),
         }
     }
 }
@@ -64,15 +172,27 @@ impl<Act: fmt::Debug, In: SystemInput + Clone> fmt::Debug for InputSequence<Act,
             // system_id: SystemId<In>,
             acts: &'a Vec<Act>,
             time_limit: &'a Option<TimeLimit>,
+            presses: &'a Vec<Press>,
+            intervals: &'a Vec<Option<Duration>>,
         }
 
         let Self {
             acts,
             time_limit,
+            presses,
+            intervals,
             system_id: _,
         } = self;
 
-        fmt::Debug::fmt(&InputSequence { acts, time_limit }, f)
+        fmt::Debug::fmt(
+            &InputSequence {
+                acts,
+                time_limit,
+                presses,
+                intervals,
+            },
+            f,
+        )
     }
 }
 
@@ -84,6 +204,44 @@ pub struct InputSequenceBuilder<Act, S, I> {
     pub acts: Vec<Act>,
     /// Optional time limit after first match
     pub time_limit: Option<TimeLimit>,
+    /// How each act must be pressed
+    pub presses: Vec<Press>,
+    /// Longest time since the previous act for each act
+    pub intervals: Vec<Option<Duration>>,
+    /// Mark the sequence as a [Cheat]
+    pub cheat: bool,
+    /// Only register the sequence in debug builds
+    pub debug_only: bool,
+    /// Match the sequence with the rolling window matcher
+    pub rolling: bool,
+    /// Origin of the sequence
+    pub namespace: Option<Namespace>,
+    /// Which events [send_event](crate::action::send_event) sends
+    pub emit: Option<Emit>,
+    /// Wrong inputs to forgive
+    pub tolerance: Option<Tolerance>,
+    /// Par time to score matches against
+    pub scoring: Option<Scoring>,
+    /// Report progress in a [TutorialHint](crate::tutorial::TutorialHint)
+    pub tutorial: bool,
+    /// Send [SequenceProgress](crate::progress::SequenceProgress) events
+    pub report_progress: bool,
+    /// Adjust the time limit to the player's near misses
+    pub adaptive_window: Option<AdaptiveWindow>,
+    /// Sequence that must have matched recently
+    pub prerequisite: Option<Prerequisite>,
+    /// Let the left stick and D-pad satisfy each other's steps
+    pub stick_as_dpad: bool,
+    /// Which sequences win when this one completes along with others
+    pub priority: Option<MatchPriority>,
+    /// Disable the sequence after it fires
+    pub once: bool,
+    /// Least time between firings
+    pub cooldown: Option<Cooldown>,
+    /// Schedule to match in instead of the plugin's first one
+    pub schedule: Option<MatchSchedule>,
+    /// Tags to classify the sequence by
+    pub tags: Option<Tags>,
     input: PhantomData<I>,
 }
 
@@ -98,6 +256,25 @@ impl<Act> InputSequenceBuilder<Act, (), ()> {
             acts: Vec::new(),
             system: IntoSystem::into_system(system),
             time_limit: None,
+            presses: Vec::new(),
+            intervals: Vec::new(),
+            cheat: false,
+            debug_only: false,
+            rolling: false,
+            namespace: None,
+            emit: None,
+            tolerance: None,
+            scoring: None,
+            tutorial: false,
+            report_progress: false,
+            adaptive_window: None,
+            prerequisite: None,
+            stick_as_dpad: false,
+            priority: None,
+            once: false,
+            cooldown: None,
+            schedule: None,
+            tags: None,
             input: PhantomData,
         }
     }
@@ -113,13 +290,310 @@ where
         self
     }
 
+    /// Specify how the act at `index` must be pressed. Acts are
+    /// [Press::JustPressed] by default.
+    ///
+    /// Only key sequences honor this setting; gamepad button steps always
+    /// require a fresh press.
+    pub fn press(mut self, index: usize, press: Press) -> Self {
+        if self.presses.len() <= index {
+            self.presses.resize(index + 1, Press::default());
+        }
+        self.presses[index] = press;
+        self
+    }
+
+    /// Allow at most `max` between the acts at `index - 1` and `index`.
+    ///
+    /// Only key sequences honor this setting.
+    pub fn interval(mut self, index: usize, max: Duration) -> Self {
+        if self.intervals.len() <= index {
+            self.intervals.resize(index + 1, None);
+        }
+        self.intervals[index] = Some(max);
+        self
+    }
+
+    /// Allow at most each of `windows` between successive acts: the first
+    /// between the first and second act, and so on, e.g., to tighten a combo
+    /// step by step.
+    ///
+    /// Only key sequences honor this setting.
+    pub fn intervals(mut self, windows: impl IntoIterator<Item = Duration>) -> Self {
+        for (i, max) in windows.into_iter().enumerate() {
+            self = self.interval(i + 1, max);
+        }
+        self
+    }
+
+    /// Require the act at `index` `times` times in a row, e.g., to triple-tap
+    /// a key, with at most `max_interval` between taps if given.
+    ///
+    /// Only key sequences honor the interval.
+    pub fn repeat(mut self, index: usize, times: usize, max_interval: Option<Duration>) -> Self
+    where
+        Act: Clone,
+    {
+        let extra = times.saturating_sub(1);
+        if index >= self.acts.len() || extra == 0 {
+            return self;
+        }
+        let act = self.acts[index].clone();
+        self.acts
+            .splice(index + 1..index + 1, std::iter::repeat_n(act, extra));
+        if self.presses.len() > index {
+            let press = self.presses[index];
+            self.presses
+                .splice(index + 1..index + 1, std::iter::repeat_n(press, extra));
+        }
+        if self.intervals.len() > index + 1 {
+            self.intervals
+                .splice(index + 1..index + 1, std::iter::repeat_n(None, extra));
+        }
+        if let Some(max) = max_interval {
+            for i in index + 1..=index + extra {
+                self = self.interval(i, max);
+            }
+        }
+        self
+    }
+
+    /// Mark the sequence as a [Cheat], which only matches while
+    /// [CheatsEnabled](crate::cheat::CheatsEnabled) is true.
+    pub fn cheat(mut self) -> Self {
+        self.cheat = true;
+        self
+    }
+
+    /// Only register the sequence in debug builds. Its acts are still
+    /// compiled into release builds; wrap the definition in
+    /// [debug_only!](crate::debug_only) to remove them.
+    pub fn debug_only(mut self) -> Self {
+        self.debug_only = true;
+        self
+    }
+
+    /// Match the sequence by hashing a rolling window of recent input instead
+    /// of tracking it in the trie, so each input costs the same no matter how
+    /// long the sequence is. Suited to long codes without a time limit.
+    ///
+    /// Only key sequences honor this setting. A rolling sequence ignores
+    /// [press](Self::press) requirements.
+    pub fn rolling(mut self) -> Self {
+        self.rolling = true;
+        self
+    }
+
+    /// Put the sequence in a [Namespace], typically the name of the crate
+    /// that registers it.
+    pub fn namespace(mut self, name: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        self.namespace = Some(Namespace::new(name));
+        self
+    }
+
+    /// Choose whether [send_event](crate::action::send_event) sends the plain
+    /// event, a [Matched](crate::matched::Matched) event, or both. Only takes
+    /// effect for sequences added to an entity.
+    pub fn emit(mut self, emit: Emit) -> Self {
+        self.emit = Some(emit);
+        self
+    }
+
+    /// Forgive up to `mistakes` wrong inputs once the sequence has started.
+    /// See [Tolerance].
+    ///
+    /// Only key sequences honor this setting. A tolerant sequence ignores
+    /// [press](Self::press) requirements.
+    pub fn tolerate(mut self, mistakes: u32) -> Self {
+        self.tolerance = Some(Tolerance(mistakes));
+        self
+    }
+
+    /// Choose whether wrong inputs reset the sequence once it has started.
+    /// Sequences are [MatchMode::Strict] by default; [MatchMode::Lenient] is
+    /// the same as an unlimited [tolerate](Self::tolerate).
+    ///
+    /// Only key sequences honor [MatchMode::Lenient].
+    pub fn match_mode(mut self, mode: MatchMode) -> Self {
+        self.tolerance = match mode {
+            MatchMode::Strict => None,
+            MatchMode::Lenient => Some(Tolerance(u32::MAX)),
+        };
+        self
+    }
+
+    /// Score matches against a `par` time. See [Scoring]. Only takes effect
+    /// for sequences added to an entity.
+    pub fn score(mut self, par: std::time::Duration) -> Self {
+        self.scoring = Some(Scoring { par });
+        self
+    }
+
+    /// Keep a [TutorialHint](crate::tutorial::TutorialHint) on the
+    /// sequence's entity up to date with the player's progress.
+    ///
+    /// Only key sequences honor this setting.
+    pub fn tutorial(mut self) -> Self {
+        self.tutorial = true;
+        self
+    }
+
+    /// Send a [SequenceProgress](crate::progress::SequenceProgress) event as
+    /// each step of the sequence matches.
+    ///
+    /// Only key sequences honor this setting.
+    pub fn report_progress(mut self) -> Self {
+        self.report_progress = true;
+        self
+    }
+
+    /// Widen the time limit after near misses and narrow it after matches,
+    /// within the bounds of `window`. See [AdaptiveWindow].
+    ///
+    /// Only key and button sequences honor this setting; on other sequences
+    /// it is dropped with a warning.
+    pub fn adaptive_window(mut self, window: AdaptiveWindow) -> Self {
+        self.adaptive_window = Some(window);
+        self
+    }
+
+    /// Only match within `within` after the sequence on entity `after`
+    /// matched. See [Prerequisite].
+    pub fn requires(mut self, after: Entity, within: std::time::Duration) -> Self {
+        self.prerequisite = Some(Prerequisite::new(after, within));
+        self
+    }
+
+    /// Let left stick directions satisfy D-pad steps and vice versa. See
+    /// [StickAsDpad].
+    ///
+    /// Only button sequences honor this setting.
+    pub fn stick_as_dpad(mut self) -> Self {
+        self.stick_as_dpad = true;
+        self
+    }
+
+    /// Decide whether this sequence fires when others complete in the same
+    /// frame, overriding the plugin's setting. See [MatchPriority].
+    pub fn priority(mut self, priority: MatchPriority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Fire only once: after the sequence's system runs, its entity is
+    /// [Disabled](crate::disabled::Disabled), e.g., for a one-time easter egg.
+    /// Remove [Disabled](crate::disabled::Disabled) to arm it again.
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    /// Keep the sequence from firing again until `duration` has passed since
+    /// it last fired. See [Cooldown].
+    pub fn cooldown(mut self, duration: Duration) -> Self {
+        self.cooldown = Some(Cooldown::new(duration));
+        self
+    }
+
+    /// Match the sequence in `schedule`, which the plugin must also
+    /// [run_in](crate::InputSequencePlugin::run_in). See [MatchSchedule].
+    ///
+    /// Only key and button sequences honor this setting.
+    pub fn match_in(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = Some(MatchSchedule::new(schedule));
+        self
+    }
+
+    /// Tag the sequence with `tag`. A sequence may have any number of tags.
+    /// See [Tags].
+    pub fn tag(mut self, tag: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        self.tags.get_or_insert_with(Tags::default).0.insert(tag.into());
+        self
+    }
+
     /// Build the InputSequence. Requires world to register the system.
     pub fn build(self, world: &mut World) -> InputSequence<Act, S::In> {
         InputSequence {
             system_id: world.register_system(self.system),
             acts: self.acts,
             time_limit: self.time_limit,
+            presses: self.presses,
+            intervals: self.intervals,
+        }
+    }
+}
+
+impl<Act, S, I> InputSequenceBuilder<Act, S, I>
+where
+    Act: Send + Sync + 'static,
+    S: System<In = I, Out = ()> + Send + Sync + 'static,
+    I: SystemInput + Send + Sync + 'static,
+{
+    /// Build the sequence and insert it on entity `id` along with the
+    /// components its options call for.
+    fn insert_into(self, id: Entity, world: &mut World) {
+        let system_id = world.register_system(self.system);
+        let mut entity = world.entity_mut(id);
+        entity.insert(InputSequence {
+            system_id,
+            acts: self.acts,
+            time_limit: self.time_limit,
+            presses: self.presses,
+            intervals: self.intervals,
+        });
+        if self.cheat {
+            entity.insert(Cheat);
+        }
+        if self.rolling {
+            entity.insert(Rolling);
+        }
+        if self.tutorial {
+            entity.insert(Tutorial);
+        }
+        if self.report_progress {
+            entity.insert(ReportProgress);
+        }
+        if self.stick_as_dpad {
+            entity.insert(StickAsDpad);
+        }
+        if self.once {
+            entity.insert(Once);
+        }
+        if let Some(namespace) = self.namespace {
+            entity.insert(namespace);
         }
+        if let Some(emit) = self.emit {
+            entity.insert(emit);
+        }
+        if let Some(tolerance) = self.tolerance {
+            entity.insert(tolerance);
+        }
+        if let Some(scoring) = self.scoring {
+            entity.insert(scoring);
+        }
+        if let Some(window) = self.adaptive_window {
+            if adapts::<InputSequence<Act, I>>() {
+                entity.insert(window);
+            } else {
+                warn!("Only key and button sequences have adaptive windows; ignoring it.");
+            }
+        }
+        if let Some(prerequisite) = self.prerequisite {
+            entity.insert(prerequisite);
+        }
+        if let Some(priority) = self.priority {
+            entity.insert(priority);
+        }
+        if let Some(cooldown) = self.cooldown {
+            entity.insert(cooldown);
+        }
+        if let Some(schedule) = self.schedule {
+            entity.insert(schedule);
+        }
+        if let Some(tags) = self.tags {
+            entity.insert(tags);
+        }
+        world.entity_mut(system_id.entity()).set_parent(id);
     }
 }
 
@@ -130,11 +604,11 @@ where
     I: SystemInput + Send + Sync + 'static,
 {
     fn apply(self, world: &mut World) {
-        let act = self.build(world);
-        let system_entity = act.system_id.entity();
-        let id = world.spawn(act).id();
-        world.entity_mut(system_entity)
-            .set_parent(id);
+        if self.debug_only && !cfg!(debug_assertions) {
+            return;
+        }
+        let id = world.spawn_empty().id();
+        self.insert_into(id, world);
     }
 }
 
@@ -145,15 +619,46 @@ where
     I: SystemInput + Send + Sync + 'static,
 {
     fn apply(self, id: Entity, world: &mut World) {
-        let act = self.build(world);
-        let system_entity = act.system_id.entity();
-        let mut entity = world.get_entity_mut(id).unwrap();
-        entity.insert(act);
-        world.entity_mut(system_entity)
-            .set_parent(id);
+        if self.debug_only && !cfg!(debug_assertions) {
+            return;
+        }
+        self.insert_into(id, world);
     }
 }
 
+/// Implement `Command` and `EntityCommand` for the builder of a sequence
+/// type other than [InputSequence]. Its `build` returns a component with a
+/// `system_id`, and the system becomes a child of the sequence's entity, as
+/// with [InputSequenceBuilder], so options kept as components on that entity,
+/// like [Once] and [Cooldown], apply to it too.
+macro_rules! sequence_builder_commands {
+    ($builder:ident) => {
+        impl<S> ::bevy::ecs::world::Command for $builder<S>
+        where
+            S: ::bevy::ecs::system::System<In = (), Out = ()> + Send + Sync + 'static,
+        {
+            fn apply(self, world: &mut ::bevy::ecs::world::World) {
+                let id = world.spawn_empty().id();
+                ::bevy::ecs::system::EntityCommand::apply(self, id, world);
+            }
+        }
+
+        impl<S> ::bevy::ecs::system::EntityCommand for $builder<S>
+        where
+            S: ::bevy::ecs::system::System<In = (), Out = ()> + Send + Sync + 'static,
+        {
+            fn apply(self, id: ::bevy::ecs::entity::Entity, world: &mut ::bevy::ecs::world::World) {
+                use ::bevy::hierarchy::BuildChildren;
+                let seq = self.build(world);
+                let system_entity = seq.system_id.entity();
+                world.entity_mut(id).insert(seq);
+                world.entity_mut(system_entity).set_parent(id);
+            }
+        }
+    };
+}
+pub(crate) use sequence_builder_commands;
+
 impl<Act, In: SystemInput + Send + Sync + 'static> InputSequence<Act, In>
 where
     In: 'static,
@@ -181,4 +686,4 @@ pub type KeySequence = InputSequence<KeyChord, ()>;
 pub type KeySequenceBuilder = InputSequenceBuilder<KeyChord, (), ()>;
 
 /// Represents a gamepad button sequence
-pub type ButtonSequence = InputSequence<GamepadButton, In<Entity>>;
+pub type ButtonSequence = InputSequence<ButtonAct, In<Entity>>;
@@ -0,0 +1,193 @@
+//! Follow keyboard layout changes
+//!
+//! A key sequence written as text, e.g., "idkfa", is resolved to the physical
+//! keys that type it. The plugin starts from a US layout and learns the actual
+//! layout from the characters keys produce. When a key produces a character
+//! other than expected, e.g., `KeyY` typing "z" after switching to a German
+//! layout, the text sequences are resolved again and [BindingsRemapped] lists
+//! the sequences whose keys changed so prompts can update.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, keyboard_layout::BindingsRemapped};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(KeySequence::from_text(|| info!("god mode"), "iddqd"));
+//! }
+//!
+//! fn refresh(mut remapped: EventReader<BindingsRemapped>) {
+//!     for event in remapped.read() {
+//!         info!("{} bindings moved", event.sequences.len());
+//!     }
+//! }
+//! ```
+use crate::{
+    cache::KeySequenceCache,
+    cond_system::IntoCondSystem,
+    input_sequence::{InputSequenceBuilder, KeySequence},
+    KeyChord, Modifiers, TimeLimit,
+};
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        system::{EntityCommand, Query, Res, ResMut, Resource, System},
+        world::{Command, World},
+    },
+    input::{
+        keyboard::{Key, KeyCode, KeyboardInput},
+        ButtonInput, ButtonState,
+    },
+    log::warn,
+};
+use std::collections::HashMap;
+
+/// The text a key sequence was written as.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct LogicalKeys(pub String);
+
+/// Sent when text sequences resolve to different keys.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct BindingsRemapped {
+    /// Entities of the sequences whose keys changed.
+    pub sequences: Vec<Entity>,
+}
+
+/// Which key chord types each character, learned from key presses on top of a
+/// US layout.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct KeyboardLayoutMap {
+    learned: HashMap<char, KeyChord>,
+}
+
+impl KeyboardLayoutMap {
+    /// Return the key chord that types `c`.
+    pub fn resolve(&self, c: char) -> Option<KeyChord> {
+        self.learned
+            .get(&c)
+            .cloned()
+            .or_else(|| KeyChord::from_web_key_value(c.encode_utf8(&mut [0; 4])))
+    }
+
+    /// Return the key chords that type `text`, skipping characters without a
+    /// key.
+    pub fn resolve_str(&self, text: &str) -> Vec<KeyChord> {
+        text.chars()
+            .filter_map(|c| {
+                let chord = self.resolve(c);
+                if chord.is_none() {
+                    warn!("No key types {c:?}.");
+                }
+                chord
+            })
+            .collect()
+    }
+
+    /// Record that `chord` types `c`. Returns true if that is news.
+    pub fn learn(&mut self, c: char, chord: KeyChord) -> bool {
+        if self.resolve(c).as_ref() == Some(&chord) {
+            return false;
+        }
+        self.learned.insert(c, chord);
+        true
+    }
+}
+
+/// Builds a key sequence from text.
+pub struct LogicalKeySequenceBuilder<S> {
+    /// Builder for everything but the acts.
+    pub builder: InputSequenceBuilder<KeyChord, S, ()>,
+    text: String,
+}
+
+impl KeySequence {
+    /// Create a key sequence that types `text`. Not operant until added to an
+    /// entity.
+    pub fn from_text<C, M>(system: C, text: impl Into<String>) -> LogicalKeySequenceBuilder<C::System>
+    where
+        C: IntoCondSystem<(), (), M> + 'static,
+    {
+        LogicalKeySequenceBuilder {
+            builder: InputSequenceBuilder::new(system),
+            text: text.into(),
+        }
+    }
+}
+
+impl<S> LogicalKeySequenceBuilder<S>
+where
+    S: System<In = (), Out = ()>,
+{
+    /// Specify a time limit from the start of the first matching input.
+    pub fn time_limit(mut self, time_limit: impl Into<TimeLimit>) -> Self {
+        self.builder = self.builder.time_limit(time_limit);
+        self
+    }
+}
+
+impl<S> EntityCommand for LogicalKeySequenceBuilder<S>
+where
+    S: System<In = (), Out = ()> + Send + Sync + 'static,
+{
+    fn apply(mut self, id: Entity, world: &mut World) {
+        self.builder.acts = world
+            .get_resource::<KeyboardLayoutMap>()
+            .cloned()
+            .unwrap_or_default()
+            .resolve_str(&self.text);
+        EntityCommand::apply(self.builder, id, world);
+        world.entity_mut(id).insert(LogicalKeys(self.text));
+    }
+}
+
+impl<S> Command for LogicalKeySequenceBuilder<S>
+where
+    S: System<In = (), Out = ()> + Send + Sync + 'static,
+{
+    fn apply(self, world: &mut World) {
+        let id = world.spawn_empty().id();
+        EntityCommand::apply(self, id, world);
+    }
+}
+
+/// Learn the layout from key presses and resolve text sequences again when it
+/// changes.
+pub(crate) fn detect_layout_changes(
+    mut inputs: EventReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut map: ResMut<KeyboardLayoutMap>,
+    mut sequences: Query<(Entity, &LogicalKeys, &mut KeySequence)>,
+    mut cache: ResMut<KeySequenceCache>,
+    mut remapped: EventWriter<BindingsRemapped>,
+) {
+    let shift = Modifiers::from(&*keys) & Modifiers::SHIFT;
+    let mut changed = false;
+    for input in inputs.read() {
+        if input.state != ButtonState::Pressed {
+            continue;
+        }
+        let Key::Character(text) = &input.logical_key else {
+            continue;
+        };
+        let mut chars = text.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            changed |= map.learn(c, KeyChord(shift, input.key_code));
+        }
+    }
+    if !changed {
+        return;
+    }
+    let mut moved = Vec::new();
+    for (id, text, mut sequence) in &mut sequences {
+        let acts = map.resolve_str(&text.0);
+        if acts != sequence.acts {
+            sequence.acts = acts;
+            moved.push(id);
+        }
+    }
+    if !moved.is_empty() {
+        cache.reset();
+        remapped.send(BindingsRemapped { sequences: moved });
+    }
+}
@@ -0,0 +1,273 @@
+//! Keymaps as data
+//!
+//! A [Keymap] lists named key bindings without the systems they run, so it can
+//! be shipped as an asset and bound to systems at startup. [Keymap::to_bytes]
+//! produces a compact binary encoding that loads much faster than parsing
+//! text. Keys are stored by name, so keymaps saved under one Bevy release
+//! still load under the next, even if it reorders [KeyCode]. With the "serde" feature, keymaps can also be stored in RON or JSON
+//! settings files, where key chords are written in `keyseq!` shorthand.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, keymap::{Binding, Keymap}};
+//!
+//! let keymap = Keymap {
+//!     bindings: vec![Binding {
+//!         name: "save".into(),
+//!         acts: keyseq! { Ctrl-X Ctrl-S }.into_iter().map(KeyChord::from).collect(),
+//!         time_limit: Some(Duration::from_secs(1).into()),
+//!     }],
+//! };
+//! let bytes = keymap.to_bytes();
+//! assert_eq!(Keymap::from_bytes(&bytes).unwrap(), keymap);
+//!
+//! fn save() {
+//!     info!("save");
+//! }
+//!
+//! let mut world = World::new();
+//! for binding in &keymap.bindings {
+//!     if binding.name == "save" {
+//!         let seq = binding.key_sequence(save).build(&mut world);
+//!         world.spawn(seq);
+//!     }
+//! }
+//! ```
+use crate::{
+    chord::{key_code_from_name, native_key_code_from_parts, native_key_code_parts},
+    cond_system::IntoCondSystem,
+    input_sequence::{InputSequenceBuilder, KeySequence},
+    KeyChord, Modifiers, TimeLimit,
+};
+use bevy::{input::keyboard::KeyCode, reflect::Enum};
+use std::{fmt, time::Duration};
+
+const MAGIC: &[u8; 4] = b"BISQ";
+const VERSION: u8 = 1;
+
+/// A named key binding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Binding {
+    /// Name of the action the binding triggers.
+    pub name: String,
+    /// Key chords of the sequence.
+    pub acts: Vec<KeyChord>,
+    /// Optional time limit.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_limit: Option<TimeLimit>,
+}
+
+impl Binding {
+    /// Create a key sequence builder that runs `system` for this binding.
+    pub fn key_sequence<C, M>(&self, system: C) -> InputSequenceBuilder<KeyChord, C::System, ()>
+    where
+        C: IntoCondSystem<(), (), M> + 'static,
+    {
+        let builder = KeySequence::new(system, self.acts.clone());
+        match &self.time_limit {
+            Some(limit) => builder.time_limit(limit.clone()),
+            None => builder,
+        }
+    }
+}
+
+/// A list of named key bindings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keymap {
+    /// Bindings in order.
+    pub bindings: Vec<Binding>,
+}
+
+/// Error decoding a binary keymap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeymapError {
+    /// Data does not start with the keymap header.
+    BadMagic,
+    /// Data was written by an unsupported format version.
+    UnsupportedVersion(u8),
+    /// Data ended in the middle of a value.
+    UnexpectedEnd,
+    /// A binding name is not valid UTF-8.
+    InvalidName,
+    /// A key name does not name a key.
+    UnknownKey(String),
+    /// A time limit has an unknown tag.
+    InvalidTimeLimit(u8),
+    /// A time limit is too long to represent.
+    TimeLimitOutOfRange,
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapError::BadMagic => write!(f, "not a binary keymap"),
+            KeymapError::UnsupportedVersion(v) => write!(f, "unsupported keymap version {v}"),
+            KeymapError::UnexpectedEnd => write!(f, "unexpected end of keymap"),
+            KeymapError::InvalidName => write!(f, "binding name is not valid UTF-8"),
+            KeymapError::UnknownKey(name) => write!(f, "unknown key code {name:?}"),
+            KeymapError::InvalidTimeLimit(t) => write!(f, "invalid time limit tag {t}"),
+            KeymapError::TimeLimitOutOfRange => write!(f, "time limit out of range"),
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+impl Keymap {
+    /// Encode the keymap in a compact binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        write_varint(&mut out, self.bindings.len() as u64);
+        for binding in &self.bindings {
+            write_varint(&mut out, binding.name.len() as u64);
+            out.extend_from_slice(binding.name.as_bytes());
+            write_varint(&mut out, binding.acts.len() as u64);
+            for KeyChord(mods, key) in &binding.acts {
+                out.push(mods.bits());
+                write_key(&mut out, key);
+            }
+            match &binding.time_limit {
+                None => out.push(0),
+                Some(TimeLimit::Frames(frames)) => {
+                    out.push(1);
+                    write_varint(&mut out, *frames as u64);
+                }
+                Some(TimeLimit::Duration(duration)) => {
+                    out.push(2);
+                    write_varint(&mut out, duration.as_secs());
+                    write_varint(&mut out, duration.subsec_nanos() as u64);
+                }
+                Some(TimeLimit::RealDuration(duration)) => {
+                    out.push(3);
+                    write_varint(&mut out, duration.as_secs());
+                    write_varint(&mut out, duration.subsec_nanos() as u64);
+                }
+            }
+        }
+        out
+    }
+
+    /// Decode a keymap written by [Keymap::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KeymapError> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(KeymapError::BadMagic);
+        }
+        let version = reader.byte()?;
+        if version != VERSION {
+            return Err(KeymapError::UnsupportedVersion(version));
+        }
+        let count = reader.varint()?;
+        let mut bindings = Vec::new();
+        for _ in 0..count {
+            let len = reader.varint()? as usize;
+            let name = std::str::from_utf8(reader.take(len)?)
+                .map_err(|_| KeymapError::InvalidName)?
+                .to_string();
+            let n = reader.varint()?;
+            let mut acts = Vec::new();
+            for _ in 0..n {
+                let mods = Modifiers(reader.byte()?);
+                acts.push(KeyChord(mods, reader.key()?));
+            }
+            let time_limit = match reader.byte()? {
+                0 => None,
+                1 => Some(TimeLimit::Frames(
+                    u32::try_from(reader.varint()?)
+                        .map_err(|_| KeymapError::TimeLimitOutOfRange)?,
+                )),
+                2 => Some(TimeLimit::Duration(reader.duration()?)),
+                3 => Some(TimeLimit::RealDuration(reader.duration()?)),
+                tag => return Err(KeymapError::InvalidTimeLimit(tag)),
+            };
+            bindings.push(Binding {
+                name,
+                acts,
+                time_limit,
+            });
+        }
+        Ok(Keymap { bindings })
+    }
+}
+
+/// Write a key by name, followed by the platform and scan code of an
+/// unidentified key.
+fn write_key(out: &mut Vec<u8>, key: &KeyCode) {
+    let name = key.variant_name();
+    write_varint(out, name.len() as u64);
+    out.extend_from_slice(name.as_bytes());
+    if let KeyCode::Unidentified(native) = key {
+        let (tag, code) = native_key_code_parts(native);
+        out.push(tag);
+        write_varint(out, code as u64);
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], KeymapError> {
+        if self.0.len() < n {
+            return Err(KeymapError::UnexpectedEnd);
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, KeymapError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn duration(&mut self) -> Result<Duration, KeymapError> {
+        let secs = self.varint()?;
+        let nanos = self.varint()?;
+        if nanos >= 1_000_000_000 {
+            return Err(KeymapError::TimeLimitOutOfRange);
+        }
+        Duration::from_secs(secs)
+            .checked_add(Duration::from_nanos(nanos))
+            .ok_or(KeymapError::TimeLimitOutOfRange)
+    }
+
+    fn key(&mut self) -> Result<KeyCode, KeymapError> {
+        let len = self.varint()? as usize;
+        let bytes = self.take(len)?;
+        let name = std::str::from_utf8(bytes)
+            .map_err(|_| KeymapError::UnknownKey(String::from_utf8_lossy(bytes).into_owned()))?;
+        if name == "Unidentified" {
+            let tag = self.byte()?;
+            let code = self.varint()?;
+            return u32::try_from(code)
+                .ok()
+                .and_then(|code| native_key_code_from_parts(tag, code))
+                .map(KeyCode::Unidentified)
+                .ok_or_else(|| KeymapError::UnknownKey(format!("Unidentified({tag}, {code})")));
+        }
+        key_code_from_name(name).ok_or_else(|| KeymapError::UnknownKey(name.to_string()))
+    }
+
+    fn varint(&mut self) -> Result<u64, KeymapError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(KeymapError::UnexpectedEnd)
+    }
+}
@@ -0,0 +1,175 @@
+//! Gamepad layout profiles for face-button labels
+//!
+//! [GamepadButton] names face buttons by position: `South` is the bottom
+//! button. Xbox and PlayStation pads label those positions alike (A and Cross
+//! are both at the bottom), but a Switch Pro pad swaps them: its A is on the
+//! right and its B at the bottom.
+//!
+//! Put a [GamepadProfile] on a gamepad entity to pick its layout and how its
+//! face buttons match. Button sequences are written in positions, which are
+//! also the Xbox labels. With [FaceSemantics::Position], a pad matches by
+//! position, so muscle memory carries over. With [FaceSemantics::Label], a pad
+//! matches by label, so "press A" means the button marked A on that pad.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, button::ButtonAct, layout::*};
+//!
+//! fn setup(mut commands: Commands, gamepads: Query<Entity, Added<Gamepad>>) {
+//!     for gamepad in &gamepads {
+//!         commands.entity(gamepad).insert(GamepadProfile {
+//!             layout: GamepadLayout::SwitchPro,
+//!             semantics: FaceSemantics::Label,
+//!         });
+//!     }
+//! }
+//!
+//! let act = ButtonAct::Press(GamepadButton::South);
+//! assert_eq!(act.display(GamepadLayout::Xbox).to_string(), "A");
+//! assert_eq!(act.display(GamepadLayout::PlayStation).to_string(), "Cross");
+//! assert_eq!(act.display(GamepadLayout::SwitchPro).to_string(), "B");
+//! ```
+use crate::button::{ButtonAct, ButtonGroup};
+use bevy::{ecs::component::Component, input::gamepad::GamepadButton, reflect::Reflect};
+use std::fmt;
+
+/// A family of gamepad button labels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum GamepadLayout {
+    /// Xbox controllers.
+    #[default]
+    Xbox,
+    /// DualShock and DualSense controllers.
+    PlayStation,
+    /// Switch Pro controllers.
+    SwitchPro,
+}
+
+/// How a gamepad's face buttons match button sequences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum FaceSemantics {
+    /// Match by position.
+    #[default]
+    Position,
+    /// Match by label, taking the sequence's buttons as Xbox labels.
+    Label,
+}
+
+/// The layout of a gamepad and how its face buttons match.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub struct GamepadProfile {
+    /// Labels on the gamepad.
+    pub layout: GamepadLayout,
+    /// How face buttons match.
+    pub semantics: FaceSemantics,
+}
+
+impl GamepadLayout {
+    /// Return the label of `button` on this layout.
+    pub fn label(&self, button: GamepadButton) -> &'static str {
+        use GamepadButton::*;
+        use GamepadLayout::*;
+        match (self, button) {
+            (Xbox, South) | (SwitchPro, East) => "A",
+            (Xbox, East) | (SwitchPro, South) => "B",
+            (Xbox, West) | (SwitchPro, North) => "X",
+            (Xbox, North) | (SwitchPro, West) => "Y",
+            (PlayStation, South) => "Cross",
+            (PlayStation, East) => "Circle",
+            (PlayStation, West) => "Square",
+            (PlayStation, North) => "Triangle",
+            (Xbox, LeftTrigger) => "LB",
+            (Xbox, RightTrigger) => "RB",
+            (Xbox, LeftTrigger2) => "LT",
+            (Xbox, RightTrigger2) => "RT",
+            (PlayStation, LeftTrigger) => "L1",
+            (PlayStation, RightTrigger) => "R1",
+            (PlayStation, LeftTrigger2) => "L2",
+            (PlayStation, RightTrigger2) => "R2",
+            (SwitchPro, LeftTrigger) => "L",
+            (SwitchPro, RightTrigger) => "R",
+            (SwitchPro, LeftTrigger2) => "ZL",
+            (SwitchPro, RightTrigger2) => "ZR",
+            (PlayStation, LeftThumb) => "L3",
+            (PlayStation, RightThumb) => "R3",
+            (_, LeftThumb) => "LS",
+            (_, RightThumb) => "RS",
+            (Xbox, Select) => "View",
+            (Xbox, Start) => "Menu",
+            (PlayStation, Select) => "Create",
+            (PlayStation, Start) => "Options",
+            (SwitchPro, Select) => "-",
+            (SwitchPro, Start) => "+",
+            (Xbox, Mode) => "Xbox",
+            (PlayStation, Mode) => "PS",
+            (SwitchPro, Mode) => "Home",
+            (_, DPadUp) => "Up",
+            (_, DPadDown) => "Down",
+            (_, DPadLeft) => "Left",
+            (_, DPadRight) => "Right",
+            (_, C) => "C",
+            (_, Z) => "Z",
+            (_, Other(_)) => "?",
+        }
+    }
+
+    /// Return the position that carries the Xbox label of `button` on this
+    /// layout, e.g., `East` for `South` on a Switch Pro pad.
+    pub fn by_label(&self, button: GamepadButton) -> GamepadButton {
+        use GamepadButton::*;
+        match (self, button) {
+            (GamepadLayout::SwitchPro, South) => East,
+            (GamepadLayout::SwitchPro, East) => South,
+            (GamepadLayout::SwitchPro, West) => North,
+            (GamepadLayout::SwitchPro, North) => West,
+            _ => button,
+        }
+    }
+}
+
+impl GamepadProfile {
+    /// Translate a button pressed on this gamepad into the button it matches
+    /// in sequences.
+    pub fn translate(&self, button: GamepadButton) -> GamepadButton {
+        match self.semantics {
+            FaceSemantics::Position => button,
+            // The swap is its own inverse.
+            FaceSemantics::Label => self.layout.by_label(button),
+        }
+    }
+}
+
+/// Formats a button act with the labels of a [GamepadLayout].
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutButtonAct {
+    act: ButtonAct,
+    layout: GamepadLayout,
+}
+
+impl fmt::Display for LayoutButtonAct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = self.layout.label(self.act.button());
+        match self.act {
+            ButtonAct::Press(_) => f.write_str(label),
+            ButtonAct::SoftPull(_) => write!(f, "Soft {label}"),
+            ButtonAct::HardPull(_) => write!(f, "Hard {label}"),
+            ButtonAct::Release(_) => write!(f, "Release {label}"),
+            ButtonAct::Tap(_) => write!(f, "Tap {label}"),
+            ButtonAct::Hold(_) => write!(f, "Hold {label}"),
+            ButtonAct::Stick(_, direction) => write!(f, "{label} {direction}"),
+            ButtonAct::Any(ButtonGroup::Face) => f.write_str("Any face button"),
+            ButtonAct::Any(ButtonGroup::Shoulder) => f.write_str("Any shoulder button"),
+            ButtonAct::Any(ButtonGroup::Dpad) => f.write_str("Any D-pad button"),
+        }
+    }
+}
+
+impl ButtonAct {
+    /// Format this act with the labels of `layout`.
+    pub fn display(&self, layout: GamepadLayout) -> LayoutButtonAct {
+        LayoutButtonAct {
+            act: *self,
+            layout,
+        }
+    }
+}
@@ -2,17 +2,80 @@
 #![doc = include_str!("../README.md")]
 #![forbid(missing_docs)]
 
+pub mod act_queue;
+pub mod adaptive;
 pub mod action;
+#[cfg(feature = "asset")]
+pub mod asset;
+pub mod button;
 pub mod cache;
+pub mod cheat;
 mod chord;
+pub mod combo_tree;
 pub mod cond_system;
+pub mod cooldown;
+pub mod dedupe;
+pub mod deferred;
+pub mod disabled;
+pub mod export;
+pub mod fingerprint;
+pub mod fixed;
+pub mod focus;
 mod frame_time;
+pub mod gesture;
+pub mod global;
+pub mod guard;
+pub mod hashed;
+pub mod health;
+pub mod hold;
+#[cfg(feature = "ui")]
+pub mod hint;
+pub mod import;
 pub mod input_sequence;
+pub mod keyboard_layout;
+pub mod keymap;
+pub mod layout;
+pub mod lint;
+#[cfg(feature = "macros")]
+pub mod macros;
+pub mod mash;
+#[cfg(feature = "state")]
+pub mod loading;
+pub mod matched;
+pub mod mixed;
+pub mod modifier;
+pub mod mouse;
+pub mod namespace;
+pub mod pattern;
 mod plugin;
+pub mod prerequisite;
+pub mod progress;
+pub mod qte;
+pub mod rebind;
+pub mod region;
+pub mod registry;
+#[cfg(feature = "replay")]
+pub mod replay;
+pub mod scene;
+pub mod schedule;
+pub mod score;
+pub mod shorthand;
+pub mod simon;
+#[cfg(feature = "state")]
+pub mod state_gate;
+pub mod state_machine;
+pub mod stick;
+pub mod stylus;
+pub mod tag;
+pub mod testing;
+pub mod text;
+pub mod throttle;
 mod time_limit;
+pub mod tolerance;
+pub mod tutorial;
 
 pub use chord::{KeyChord, KeyChordQueue};
-pub use plugin::InputSequencePlugin;
+pub use plugin::{EvictionPolicy, InputSequencePlugin, InputSequencePluginSettings};
 pub use time_limit::TimeLimit;
 
 pub use keyseq::{
@@ -23,7 +86,7 @@ pub use keyseq::{
 /// Convenient glob import
 pub mod prelude {
     pub use super::cond_system::IntoCondSystem;
-    pub use super::input_sequence::{ButtonSequence, InputSequence, KeySequence};
+    pub use super::input_sequence::{ButtonSequence, InputSequence, KeySequence, Press};
     pub use super::{action, keyseq, InputSequencePlugin, Modifiers, TimeLimit};
     pub use super::{KeyChord, KeyChordQueue};
     pub use std::time::Duration;
@@ -0,0 +1,187 @@
+//! Check keymaps for mistakes before shipping them
+//!
+//! [lint] reports bindings that conflict, shadow one another, or can never
+//! match. [lint_text] reads the plain text keymap format, one `name: chords`
+//! binding per line, and also reports chords that are not written in
+//! canonical `keyseq!` shorthand. The `keyseq-lint` binary, built with the
+//! "cli" feature, runs these checks on keymap files.
+//!
+//! ```rust
+//! use bevy_input_sequence::lint::{lint_text, Lint};
+//!
+//! let (keymap, lints) = lint_text("
+//!     god mode: I D D Q D
+//!     save: ctrl-x Ctrl-S
+//!     quit: Ctrl-X
+//! ").unwrap();
+//! assert_eq!(keymap.bindings.len(), 3);
+//! assert_eq!(lints, vec![
+//!     Lint::Formatting { binding: "save".into(), expected: "Ctrl-X Ctrl-S".into() },
+//!     Lint::Shadowed { prefix: "quit".into(), binding: "save".into() },
+//! ]);
+//! ```
+use crate::{
+    chord::is_modifier,
+    keymap::{Binding, Keymap},
+    shorthand::{self, ParseError},
+    TimeLimit,
+};
+use std::fmt;
+
+/// A problem with a keymap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Lint {
+    /// Two bindings have the same chords.
+    Conflict {
+        /// Name of the first binding.
+        first: String,
+        /// Name of the second binding.
+        second: String,
+    },
+    /// A binding's chords begin another's, so the shorter one fires partway
+    /// through the longer one.
+    Shadowed {
+        /// Name of the shorter binding.
+        prefix: String,
+        /// Name of the longer binding.
+        binding: String,
+    },
+    /// A binding can never match.
+    Unreachable {
+        /// Name of the binding.
+        binding: String,
+        /// Why it can't match.
+        reason: String,
+    },
+    /// A binding is not written in canonical shorthand.
+    Formatting {
+        /// Name of the binding.
+        binding: String,
+        /// Canonical shorthand.
+        expected: String,
+    },
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lint::Conflict { first, second } => {
+                write!(f, "conflict: {first:?} and {second:?} have the same chords")
+            }
+            Lint::Shadowed { prefix, binding } => {
+                write!(f, "shadowed: {prefix:?} fires partway through {binding:?}")
+            }
+            Lint::Unreachable { binding, reason } => {
+                write!(f, "unreachable: {binding:?} {reason}")
+            }
+            Lint::Formatting { binding, expected } => {
+                write!(f, "formatting: {binding:?} should be written {expected:?}")
+            }
+        }
+    }
+}
+
+fn unreachable(binding: &Binding) -> Option<String> {
+    if binding.acts.is_empty() {
+        return Some("has no chords".into());
+    }
+    if let Some(chord) = binding.acts.iter().find(|chord| is_modifier(chord.1)) {
+        return Some(format!("uses modifier key {:?} as a key", chord.1));
+    }
+    // Each chord after the first takes at least one more frame.
+    match binding.time_limit {
+        Some(TimeLimit::Frames(frames)) if (frames as usize) + 1 < binding.acts.len() => Some(
+            format!("has {} chords but only {frames} frames", binding.acts.len()),
+        ),
+        _ => None,
+    }
+}
+
+/// Report conflicting, shadowed, and unreachable bindings.
+pub fn lint(keymap: &Keymap) -> Vec<Lint> {
+    let bindings = &keymap.bindings;
+    let mut lints = Vec::new();
+    for (i, a) in bindings.iter().enumerate() {
+        for b in &bindings[i + 1..] {
+            let (short, long) = if a.acts.len() <= b.acts.len() { (a, b) } else { (b, a) };
+            if short.acts.is_empty() || !long.acts.starts_with(&short.acts) {
+                continue;
+            }
+            lints.push(if short.acts.len() == long.acts.len() {
+                Lint::Conflict {
+                    first: a.name.clone(),
+                    second: b.name.clone(),
+                }
+            } else {
+                Lint::Shadowed {
+                    prefix: short.name.clone(),
+                    binding: long.name.clone(),
+                }
+            });
+        }
+    }
+    lints.extend(bindings.iter().filter_map(|binding| {
+        unreachable(binding).map(|reason| Lint::Unreachable {
+            binding: binding.name.clone(),
+            reason,
+        })
+    }));
+    lints
+}
+
+/// Error reading a text keymap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextError {
+    /// Line number starting at 1.
+    pub line: usize,
+    /// What went wrong, if the chords did not parse.
+    pub error: Option<ParseError>,
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            Some(error) => write!(f, "line {}: {error}", self.line),
+            None => write!(f, "line {}: expected \"name: chords\"", self.line),
+        }
+    }
+}
+
+impl std::error::Error for TextError {}
+
+/// Read a text keymap and lint it. Each line is a binding `name: chords`;
+/// blank lines and lines starting with `#` are ignored.
+pub fn lint_text(text: &str) -> Result<(Keymap, Vec<Lint>), TextError> {
+    let mut keymap = Keymap::default();
+    let mut lints = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, chords) = line.split_once(':').ok_or(TextError {
+            line: i + 1,
+            error: None,
+        })?;
+        let (name, chords) = (name.trim(), chords.trim());
+        let acts = shorthand::parse_key_sequence(chords).map_err(|error| TextError {
+            line: i + 1,
+            error: Some(error),
+        })?;
+        if let Some(expected) = shorthand::key_sequence(&acts) {
+            if expected != chords.split_whitespace().collect::<Vec<_>>().join(" ") {
+                lints.push(Lint::Formatting {
+                    binding: name.to_string(),
+                    expected,
+                });
+            }
+        }
+        keymap.bindings.push(Binding {
+            name: name.to_string(),
+            acts,
+            time_limit: None,
+        });
+    }
+    lints.extend(lint(&keymap));
+    Ok((keymap, lints))
+}
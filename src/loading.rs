@@ -0,0 +1,174 @@
+//! Keep sequences inert until a state is reached
+//!
+//! Requires the "state" feature. Sequences registered while assets load, e.g.,
+//! from a keymap asset, should not fire on a loading screen. Wrap a builder
+//! with [active_after](crate::input_sequence::InputSequenceBuilder::active_after)
+//! and it is held back until `State<S>` equals the given state. Register the
+//! state type once with
+//! [add_sequence_state](AddSequenceState::add_sequence_state).
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, loading::AddSequenceState};
+//!
+//! #[derive(States, Clone, Debug, Default, PartialEq, Eq, Hash)]
+//! enum LoadingState {
+//!     #[default]
+//!     Loading,
+//!     Ready,
+//! }
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(
+//!         KeySequence::new(|| info!("quick save"), keyseq! { F5 })
+//!             .active_after(LoadingState::Ready));
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(bevy::state::app::StatesPlugin)
+//!    .add_plugins(InputSequencePlugin::default())
+//!    .init_state::<LoadingState>()
+//!    .add_sequence_state::<LoadingState>()
+//!    .add_systems(Startup, setup);
+//! ```
+use crate::input_sequence::InputSequenceBuilder;
+use bevy::{
+    app::App,
+    ecs::{
+        entity::Entity,
+        schedule::IntoSystemConfigs,
+        system::{EntityCommand, Resource},
+        world::{Command, World},
+    },
+    state::state::{State, StateTransition, StateTransitionSteps, States},
+};
+
+type Pending = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Sequences held back until `State<S>` reaches their state.
+#[derive(Resource)]
+pub struct PendingSequences<S: States> {
+    queued: Vec<(S, Pending)>,
+}
+
+impl<S: States> Default for PendingSequences<S> {
+    fn default() -> Self {
+        Self { queued: Vec::new() }
+    }
+}
+
+impl<S: States> PendingSequences<S> {
+    /// Number of sequences held back.
+    pub fn len(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Return true if no sequences are held back.
+    pub fn is_empty(&self) -> bool {
+        self.queued.is_empty()
+    }
+}
+
+/// A sequence builder that registers once `State<S>` equals `state`.
+pub struct ActiveAfter<S, B> {
+    /// State to wait for
+    pub state: S,
+    /// Builder to apply
+    pub builder: B,
+}
+
+impl<Act, Sys, I> InputSequenceBuilder<Act, Sys, I> {
+    /// Hold the sequence back until `State<S>` equals `state`. Requires
+    /// [add_sequence_state](AddSequenceState::add_sequence_state) for `S`.
+    pub fn active_after<S: States>(self, state: S) -> ActiveAfter<S, Self> {
+        ActiveAfter {
+            state,
+            builder: self,
+        }
+    }
+}
+
+fn in_state<S: States>(world: &World, state: &S) -> bool {
+    world
+        .get_resource::<State<S>>()
+        .is_some_and(|current| current.get() == state)
+}
+
+fn park<S: States>(world: &mut World, state: S, apply: Pending) {
+    world
+        .get_resource_or_insert_with(PendingSequences::<S>::default)
+        .queued
+        .push((state, apply));
+}
+
+impl<S, B> Command for ActiveAfter<S, B>
+where
+    S: States,
+    B: Command + Sync,
+{
+    fn apply(self, world: &mut World) {
+        if in_state(world, &self.state) {
+            self.builder.apply(world);
+        } else {
+            let builder = self.builder;
+            park(world, self.state, Box::new(move |world| builder.apply(world)));
+        }
+    }
+}
+
+impl<S, B> EntityCommand for ActiveAfter<S, B>
+where
+    S: States,
+    B: EntityCommand + Sync,
+{
+    fn apply(self, id: Entity, world: &mut World) {
+        if in_state(world, &self.state) {
+            self.builder.apply(id, world);
+        } else {
+            let builder = self.builder;
+            park(
+                world,
+                self.state,
+                Box::new(move |world| {
+                    // The entity may have been despawned meanwhile.
+                    if world.get_entity(id).is_ok() {
+                        builder.apply(id, world);
+                    }
+                }),
+            );
+        }
+    }
+}
+
+/// Register a state type that sequences can wait for.
+pub trait AddSequenceState {
+    /// Register the sequences waiting for a state of `S` once it is entered.
+    fn add_sequence_state<S: States>(&mut self) -> &mut Self;
+}
+
+impl AddSequenceState for App {
+    fn add_sequence_state<S: States>(&mut self) -> &mut Self {
+        self.init_resource::<PendingSequences<S>>().add_systems(
+            StateTransition,
+            release_pending_sequences::<S>.after(StateTransitionSteps::EnterSchedules),
+        )
+    }
+}
+
+fn release_pending_sequences<S: States>(world: &mut World) {
+    let Some(state) = world.get_resource::<State<S>>().map(|s| s.get().clone()) else {
+        return;
+    };
+    let Some(mut pending) = world.get_resource_mut::<PendingSequences<S>>() else {
+        return;
+    };
+    if !pending.queued.iter().any(|(s, _)| *s == state) {
+        return;
+    }
+    let (ready, waiting) = pending.queued.drain(..).partition(|(s, _)| *s == state);
+    pending.queued = waiting;
+    for (_, apply) in ready {
+        apply(world);
+    }
+}
@@ -0,0 +1,192 @@
+//! Key and gamepad macros that produce this crate's act types
+//!
+//! The [key](crate::key) and [keyseq](crate::keyseq) macros at the crate root
+//! come from the keyseq crate and produce bare `(Modifiers, KeyCode)` tuples.
+//! The versions here accept the same syntax but produce a [KeyChord] and an
+//! array of them, so their output is checked against the runtime's types and
+//! no direct dependency on keyseq is needed.
+//!
+//! Keys are written as their `KeyCode` names, e.g., `F5`, `Tab`, `Home`, or
+//! `Numpad1`, and these versions also take shorter spellings:
+//!
+//! | Spelling | Key |
+//! |---|---|
+//! | `Up`, `Down`, `Left`, `Right` | `ArrowUp`, `ArrowDown`, ... |
+//! | `PgUp`, `PgDn` | `PageUp`, `PageDown` |
+//! | `Esc`, `Return`, `Del`, `Ins` | `Escape`, `Enter`, `Delete`, `Insert` |
+//! | `Num0` to `Num9`, `NumEnter` | `Numpad0` to `Numpad9`, `NumpadEnter` |
+//! | `ctrl`, `alt`, `shift`, `super` | `Ctrl`, `Alt`, `Shift`, `Super` |
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{macros::{key, keyseq}, KeyChord, Modifiers};
+//!
+//! let save: KeyChord = key! { Ctrl-S };
+//! assert_eq!(save, KeyChord(Modifiers::CONTROL, KeyCode::KeyS));
+//! let chords: [KeyChord; 2] = keyseq! { Ctrl-X Ctrl-S };
+//! assert_eq!(chords[1], save);
+//! assert_eq!(
+//!     keyseq! { ctrl-F5 Up Up Down Down },
+//!     [
+//!         KeyChord(Modifiers::CONTROL, KeyCode::F5),
+//!         KeyChord::from(KeyCode::ArrowUp),
+//!         KeyChord::from(KeyCode::ArrowUp),
+//!         KeyChord::from(KeyCode::ArrowDown),
+//!         KeyChord::from(KeyCode::ArrowDown),
+//!     ]
+//! );
+//! ```
+//!
+//! [padseq] does the same for gamepads and produces an array of
+//! [ButtonAct](crate::button::ButtonAct)s. Buttons are written by position,
+//! `south`, `east`, `north`, and `west`, or by their Xbox labels, `A`, `B`,
+//! `X`, and `Y`; the rest are `lb`, `rb`, `lt`, `rt`, `ls`, `rs`, `start`,
+//! `select`, and the D-pad's `up`, `down`, `left`, and `right`. Fighting game
+//! notation writes left stick directions for a character facing right: `u`,
+//! `d`, `f` (forward), `b` (back), and the diagonals `uf`, `ub`, `df`, and
+//! `db`. A `+` may separate a motion from its button, as in `d df f + X`; it
+//! adds no step, so the button is the step after the motion.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{button::ButtonAct, macros::padseq, stick::{Direction, Stick}};
+//!
+//! assert_eq!(
+//!     padseq! { south east north },
+//!     [
+//!         ButtonAct::Press(GamepadButton::South),
+//!         ButtonAct::Press(GamepadButton::East),
+//!         ButtonAct::Press(GamepadButton::North),
+//!     ]
+//! );
+//! assert_eq!(
+//!     padseq! { d df f + X },
+//!     [
+//!         ButtonAct::Stick(Stick::Left, Direction::Down),
+//!         ButtonAct::Stick(Stick::Left, Direction::DownRight),
+//!         ButtonAct::Stick(Stick::Left, Direction::Right),
+//!         ButtonAct::Press(GamepadButton::West),
+//!     ]
+//! );
+//! ```
+//!
+//! [KeyChord]: crate::KeyChord
+
+// Rewrite the short spellings one token at a time, then hand the result to
+// the keyseq macro named by the leading `@key` or `@keyseq`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __key_spellings {
+    (@$m:ident [$($out:tt)*]) => { $crate::$m!($($out)*) };
+    (@$m:ident [$($out:tt)*] Up $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* ArrowUp] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Down $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* ArrowDown] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Left $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* ArrowLeft] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Right $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* ArrowRight] $($rest)*) };
+    (@$m:ident [$($out:tt)*] PgUp $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* PageUp] $($rest)*) };
+    (@$m:ident [$($out:tt)*] PgDn $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* PageDown] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Esc $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Escape] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Return $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Enter] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Del $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Delete] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Ins $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Insert] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num0 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad0] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num1 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad1] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num2 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad2] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num3 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad3] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num4 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad4] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num5 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad5] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num6 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad6] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num7 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad7] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num8 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad8] $($rest)*) };
+    (@$m:ident [$($out:tt)*] Num9 $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Numpad9] $($rest)*) };
+    (@$m:ident [$($out:tt)*] NumEnter $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* NumpadEnter] $($rest)*) };
+    (@$m:ident [$($out:tt)*] ctrl $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Ctrl] $($rest)*) };
+    (@$m:ident [$($out:tt)*] alt $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Alt] $($rest)*) };
+    (@$m:ident [$($out:tt)*] shift $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Shift] $($rest)*) };
+    (@$m:ident [$($out:tt)*] super $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* Super] $($rest)*) };
+    (@$m:ident [$($out:tt)*] $t:tt $($rest:tt)*) => { $crate::__key_spellings!(@$m [$($out)* $t] $($rest)*) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __key_chord {
+    ($($t:tt)*) => {
+        $crate::KeyChord::from($crate::__key_spellings!(@key [] $($t)*))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __key_chords {
+    ($($t:tt)*) => {
+        $crate::__key_spellings!(@keyseq [] $($t)*).map($crate::KeyChord::from)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pad_act {
+    (@press $button:ident) => {
+        $crate::button::ButtonAct::Press(::bevy::input::gamepad::GamepadButton::$button)
+    };
+    (@stick $direction:ident) => {
+        $crate::button::ButtonAct::Stick(
+            $crate::stick::Stick::Left,
+            $crate::stick::Direction::$direction,
+        )
+    };
+    (south) => { $crate::__pad_act!(@press South) };
+    (east) => { $crate::__pad_act!(@press East) };
+    (north) => { $crate::__pad_act!(@press North) };
+    (west) => { $crate::__pad_act!(@press West) };
+    (A) => { $crate::__pad_act!(@press South) };
+    (B) => { $crate::__pad_act!(@press East) };
+    (X) => { $crate::__pad_act!(@press West) };
+    (Y) => { $crate::__pad_act!(@press North) };
+    (lb) => { $crate::__pad_act!(@press LeftTrigger) };
+    (rb) => { $crate::__pad_act!(@press RightTrigger) };
+    (lt) => { $crate::__pad_act!(@press LeftTrigger2) };
+    (rt) => { $crate::__pad_act!(@press RightTrigger2) };
+    (ls) => { $crate::__pad_act!(@press LeftThumb) };
+    (rs) => { $crate::__pad_act!(@press RightThumb) };
+    (start) => { $crate::__pad_act!(@press Start) };
+    (select) => { $crate::__pad_act!(@press Select) };
+    (up) => { $crate::__pad_act!(@press DPadUp) };
+    (down) => { $crate::__pad_act!(@press DPadDown) };
+    (left) => { $crate::__pad_act!(@press DPadLeft) };
+    (right) => { $crate::__pad_act!(@press DPadRight) };
+    (u) => { $crate::__pad_act!(@stick Up) };
+    (d) => { $crate::__pad_act!(@stick Down) };
+    (f) => { $crate::__pad_act!(@stick Right) };
+    (b) => { $crate::__pad_act!(@stick Left) };
+    (uf) => { $crate::__pad_act!(@stick UpRight) };
+    (ub) => { $crate::__pad_act!(@stick UpLeft) };
+    (df) => { $crate::__pad_act!(@stick DownRight) };
+    (db) => { $crate::__pad_act!(@stick DownLeft) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pad_acts {
+    ([$($out:expr),*]) => { [$($out),*] };
+    ([$($out:expr),*] + $($rest:tt)*) => { $crate::__pad_acts!([$($out),*] $($rest)*) };
+    ([$($out:expr),*] $name:ident $($rest:tt)*) => {
+        $crate::__pad_acts!([$($out,)* $crate::__pad_act!($name)] $($rest)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pad_seq {
+    ($($t:tt)*) => {
+        $crate::__pad_acts!([] $($t)*)
+    };
+}
+
+/// Produce a [KeyChord](crate::KeyChord), e.g., `key! { Ctrl-A }`.
+pub use crate::__key_chord as key;
+/// Produce an array of [KeyChord](crate::KeyChord)s, e.g., `keyseq! { Ctrl-X
+/// Ctrl-S }`.
+pub use crate::__key_chords as keyseq;
+/// Produce an array of [ButtonAct](crate::button::ButtonAct)s, e.g., `padseq!
+/// { d df f + X }`.
+pub use crate::__pad_seq as padseq;
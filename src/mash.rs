@@ -0,0 +1,189 @@
+//! Button-mash steps
+//!
+//! A [MashSequence] fires when its act is pressed `count` times within
+//! `window`, e.g., "mash A to break free". Only the presses in the last
+//! `window` count, so mashing too slowly never fires. Each press sends a
+//! [MashProgress] event with the presses so far and their rate, e.g., to fill
+//! a meter. Enable with [match_mash](crate::InputSequencePlugin::match_mash).
+//!
+//! The act is a [MixedAct], so keys, mouse buttons, and gamepad buttons can
+//! all be mashed.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, mash::{MashProgress, MashSequence}};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(MashSequence::new(
+//!         || info!("broke free"),
+//!         GamepadButton::South,
+//!         10,
+//!         Duration::from_secs(2),
+//!     ));
+//! }
+//!
+//! fn meter(mut reader: EventReader<MashProgress>) {
+//!     for progress in reader.read() {
+//!         info!("{}/{} at {:.1}/s", progress.presses, progress.count, progress.rate);
+//!     }
+//! }
+//! ```
+use crate::{
+    cond_system::IntoCondSystem,
+    guard::InputSequenceGuard,
+    input_sequence::sequence_builder_commands,
+    matched::run_matched,
+    mixed::{just_pressed, MixedAct},
+    schedule::{CurrentSchedule, MatchSchedule},
+};
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        system::{Commands, IntoSystem, Local, Query, Res, System, SystemId},
+        world::World,
+    },
+    input::{gamepad::Gamepad, keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    time::Time,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+/// A step of `count` presses of `act` within `window`.
+#[derive(Component, Clone, Debug)]
+pub struct MashSequence {
+    /// System to run when the mash completes.
+    pub system_id: SystemId,
+    /// Act to mash.
+    pub act: MixedAct,
+    /// Presses needed; zero counts as one.
+    pub count: usize,
+    /// Time the presses must fall within.
+    pub window: Duration,
+}
+
+impl MashSequence {
+    /// Create a mash of `count` presses of `act` within `window`. Not operant
+    /// until added to an entity. A `count` of zero counts as one.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<C, M>(
+        system: C,
+        act: impl Into<MixedAct>,
+        count: usize,
+        window: Duration,
+    ) -> MashSequenceBuilder<C::System>
+    where
+        C: IntoCondSystem<(), (), M> + 'static,
+    {
+        MashSequenceBuilder {
+            system: IntoSystem::into_system(system),
+            act: act.into(),
+            count,
+            window,
+        }
+    }
+}
+
+/// Builder for a [MashSequence].
+///
+/// It has no options of its own. Of the
+/// [InputSequenceBuilder](crate::input_sequence::InputSequenceBuilder)
+/// options, [Once](crate::disabled::Once),
+/// [Cooldown](crate::cooldown::Cooldown), [Tags](crate::tag::Tags), and
+/// [MatchSchedule](crate::schedule::MatchSchedule) are honored when inserted
+/// on the mash's entity; the rest, including
+/// [Cheat](crate::cheat::Cheat) and [Namespace](crate::namespace::Namespace),
+/// are ignored.
+pub struct MashSequenceBuilder<S> {
+    system: S,
+    act: MixedAct,
+    count: usize,
+    window: Duration,
+}
+
+impl<S> MashSequenceBuilder<S>
+where
+    S: System<In = (), Out = ()>,
+{
+    /// Build the sequence. Requires world to register the system.
+    pub fn build(self, world: &mut World) -> MashSequence {
+        MashSequence {
+            system_id: world.register_system(self.system),
+            act: self.act,
+            count: self.count,
+            window: self.window,
+        }
+    }
+}
+
+sequence_builder_commands!(MashSequenceBuilder);
+
+/// Sent on each press of a [MashSequence]'s act.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct MashProgress {
+    /// Entity the mash is on.
+    pub entity: Entity,
+    /// Presses within the window so far.
+    pub presses: usize,
+    /// Presses needed.
+    pub count: usize,
+    /// Presses per second over the window.
+    pub rate: f32,
+}
+
+/// Count the presses of each mash and run its system once there are enough
+/// within its window.
+#[allow(clippy::type_complexity)]
+pub(crate) fn mash_matcher(
+    sequences: Query<(Entity, &MashSequence, Option<&MatchSchedule>)>,
+    (keys, mouse, gamepads): (
+        Option<Res<ButtonInput<KeyCode>>>,
+        Option<Res<ButtonInput<MouseButton>>>,
+        Query<&Gamepad>,
+    ),
+    (time, current): (Res<Time>, Res<CurrentSchedule>),
+    guard: Res<InputSequenceGuard>,
+    mut presses: Local<HashMap<Entity, VecDeque<Duration>>>,
+    mut writer: EventWriter<MashProgress>,
+    mut commands: Commands,
+) {
+    if guard.is_suppressed() {
+        presses.clear();
+        return;
+    }
+    presses.retain(|id, _| sequences.contains(*id));
+    let input = just_pressed(keys.as_deref(), mouse.as_deref(), &gamepads);
+    if input.is_empty() {
+        return;
+    }
+    let now = time.elapsed();
+    for (id, seq, schedule) in &sequences {
+        if !current.accepts(schedule) {
+            continue;
+        }
+        let count = seq.count.max(1);
+        let n = input.iter().filter(|act| **act == seq.act).count();
+        if n == 0 {
+            continue;
+        }
+        let times = presses.entry(id).or_default();
+        times.extend(std::iter::repeat_n(now, n));
+        while times.front().is_some_and(|t| now - *t > seq.window) {
+            times.pop_front();
+        }
+        writer.send(MashProgress {
+            entity: id,
+            presses: times.len().min(count),
+            count,
+            rate: times.len() as f32 / seq.window.as_secs_f32().max(f32::EPSILON),
+        });
+        if times.len() >= count {
+            let start = times[times.len() - count];
+            times.clear();
+            run_matched(&mut commands, seq.system_id, (), None, now - start, count);
+        }
+    }
+}
@@ -0,0 +1,454 @@
+//! Events that carry the entity of the matched sequence
+//!
+//! A sequence spawned on an entity, e.g., a character's special move, often
+//! needs to act on that entity. While a sequence's system runs, [MatchSource]
+//! holds the entity the sequence is on. [send_matched](crate::action::send_matched)
+//! wraps an event in [Matched] with that entity and details of the match:
+//! the gamepad that entered it, how long it took, and how many inputs it took.
+//!
+//! To have [send_event](crate::action::send_event) send [Matched] alongside or
+//! instead of the plain event, set the builder's
+//! [emit](crate::input_sequence::InputSequenceBuilder::emit) option.
+//!
+//! A key or gamepad sequence that had started matching and is dropped sends
+//! [SequenceAborted].
+//!
+//! After its system runs, a sequence on an entity also triggers
+//! [SequenceComplete] on that entity, so an observer can react to it.
+//!
+//! Some matchers can complete several sequences in one frame, e.g., rolling
+//! sequences `A B C` and `B C`. By default all of them fire; a
+//! [MatchPriority] set on the plugin or on a sequence's entity lets the
+//! longest or first registered one win instead.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, matched::SequenceComplete};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands
+//!         .spawn_empty()
+//!         .queue(KeySequence::new(|| {}, keyseq! { Ctrl-S }))
+//!         .observe(|trigger: Trigger<SequenceComplete>| {
+//!             info!("{} completed", trigger.entity());
+//!         });
+//! }
+//! ```
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, matched::Matched};
+//!
+//! #[derive(Event, Clone)]
+//! struct Dash;
+//!
+//! #[derive(Component)]
+//! struct Speed(f32);
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn(Speed(1.0)).queue(
+//!         KeySequence::new(action::send_matched(Dash), keyseq! { D D }));
+//! }
+//!
+//! fn dash(mut reader: EventReader<Matched<Dash>>, mut speeds: Query<&mut Speed>) {
+//!     for matched in reader.read() {
+//!         if let Ok(mut speed) = speeds.get_mut(matched.entity) {
+//!             speed.0 *= 2.0;
+//!         }
+//!     }
+//! }
+//! ```
+use crate::{
+    cooldown::start_cooldown,
+    registry::SequenceId,
+    score::{MatchScore, Scoring},
+    InputSequencePluginSettings,
+};
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::Event,
+        system::{Commands, Resource, SystemId, SystemInput},
+        world::World,
+    },
+    hierarchy::Parent,
+    reflect::Reflect,
+};
+use std::time::Duration;
+
+/// An event fired by the sequence on `entity`.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct Matched<E> {
+    /// Entity the sequence is on, which identifies the sequence.
+    pub entity: SequenceId,
+    /// Gamepad that entered the sequence; `None` for the keyboard.
+    pub device: Option<Entity>,
+    /// Virtual time from the first act to the last.
+    pub duration: Duration,
+    /// Number of inputs entered, i.e., the sequence's acts plus any forgiven
+    /// mistakes.
+    pub inputs: usize,
+    /// True if the sequence matched despite inputs that were forgiven.
+    pub lenient: bool,
+    /// Number of wrong inputs forgiven by the sequence's
+    /// [Tolerance](crate::tolerance::Tolerance).
+    pub mistakes: u32,
+    /// Quality of the match if the sequence has [Scoring].
+    pub score: Option<MatchScore>,
+    /// How long the last key was held for a
+    /// [HoldSequence](crate::hold::HoldSequence); `None` for other sequences.
+    pub held: Option<Duration>,
+    /// The event.
+    pub event: E,
+}
+
+/// Triggered on the entity of a sequence after its system runs.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct SequenceComplete {
+    /// Gamepad that entered the sequence; `None` for the keyboard.
+    pub device: Option<Entity>,
+    /// Virtual time from the first act to the last.
+    pub duration: Duration,
+    /// Number of inputs entered, i.e., the sequence's acts plus any forgiven
+    /// mistakes.
+    pub inputs: usize,
+    /// True if the sequence matched despite inputs that were forgiven.
+    pub lenient: bool,
+    /// Number of wrong inputs forgiven by the sequence's
+    /// [Tolerance](crate::tolerance::Tolerance).
+    pub mistakes: u32,
+    /// Quality of the match if the sequence has [Scoring].
+    pub score: Option<MatchScore>,
+    /// How long the last key was held for a
+    /// [HoldSequence](crate::hold::HoldSequence); `None` for other sequences.
+    pub held: Option<Duration>,
+}
+
+/// Why a partial match was dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AbortReason {
+    /// The last act arrived after the sequence's time limit.
+    TimedOut,
+    /// An act that doesn't continue the sequence was entered.
+    WrongInput,
+}
+
+/// Sent when a sequence that had started matching is dropped before it
+/// completes, e.g., to play a "combo dropped" sound.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SequenceAborted {
+    /// Entity the sequence is on.
+    pub entity: Entity,
+    /// Acts that had matched.
+    pub matched_len: usize,
+    /// Why it was dropped.
+    pub reason: AbortReason,
+}
+
+/// Which events [send_event](crate::action::send_event) sends for a sequence.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum Emit {
+    /// Send the plain event.
+    #[default]
+    Event,
+    /// Send the event wrapped in [Matched].
+    Matched,
+    /// Send both.
+    Both,
+}
+
+/// Details of the match whose system is running.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MatchInfo {
+    /// Entity the sequence is on.
+    pub entity: SequenceId,
+    /// Gamepad that entered the sequence; `None` for the keyboard.
+    pub device: Option<Entity>,
+    /// Virtual time from the first act to the last.
+    pub duration: Duration,
+    /// Number of inputs entered, i.e., the sequence's acts plus any forgiven
+    /// mistakes.
+    pub inputs: usize,
+    /// True if the sequence matched despite inputs that were forgiven.
+    pub lenient: bool,
+    /// Number of wrong inputs forgiven by the sequence's
+    /// [Tolerance](crate::tolerance::Tolerance).
+    pub mistakes: u32,
+    /// Quality of the match if the sequence has [Scoring].
+    pub score: Option<MatchScore>,
+    /// How long the last key was held for a
+    /// [HoldSequence](crate::hold::HoldSequence); `None` for other sequences.
+    pub held: Option<Duration>,
+    /// Which events to send.
+    pub emit: Emit,
+}
+
+impl MatchInfo {
+    /// Wrap `event` with the details of this match.
+    pub fn wrap<E>(&self, event: E) -> Matched<E> {
+        Matched {
+            entity: self.entity,
+            device: self.device,
+            duration: self.duration,
+            inputs: self.inputs,
+            lenient: self.lenient,
+            mistakes: self.mistakes,
+            score: self.score,
+            held: self.held,
+            event,
+        }
+    }
+}
+
+/// Which of the sequences that complete in the same frame fire.
+///
+/// Set it for all sequences with
+/// [InputSequencePlugin::priority](crate::InputSequencePlugin::priority) or
+/// for one with the builder's
+/// [priority](crate::input_sequence::InputSequenceBuilder::priority) option,
+/// which takes precedence. A sequence's policy decides whether it yields to
+/// the others. Sequences that may yield fire in `PostUpdate`, once every
+/// matcher has run.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum MatchPriority {
+    /// Fire every sequence that completes.
+    #[default]
+    AllFire,
+    /// Fire unless a longer sequence completed in the same frame.
+    LongestWins,
+    /// Fire unless a sequence registered earlier completed in the same frame.
+    FirstRegistered,
+}
+
+/// A sequence that completed this frame.
+#[derive(Clone, Copy, Debug)]
+struct Completion {
+    /// Acts in the sequence.
+    len: usize,
+    /// When the sequence was registered.
+    order: (u32, Entity),
+}
+
+type RunMatch = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Matches of this frame, and those waiting for [resolve_matches] to pick
+/// among them.
+#[derive(Resource, Default)]
+pub(crate) struct PendingMatches {
+    completed: Vec<Completion>,
+    waiting: Vec<(MatchPriority, Completion, RunMatch)>,
+}
+
+/// Run the waiting matches that their [MatchPriority] lets fire.
+pub(crate) fn resolve_matches(world: &mut World) {
+    let Some(mut pending) = world.get_resource_mut::<PendingMatches>() else {
+        return;
+    };
+    let completed = std::mem::take(&mut pending.completed);
+    let waiting = std::mem::take(&mut pending.waiting);
+    for (priority, completion, run) in waiting {
+        let yields = completed.iter().any(|other| match priority {
+            MatchPriority::AllFire => false,
+            MatchPriority::LongestWins => other.len > completion.len,
+            MatchPriority::FirstRegistered => other.order < completion.order,
+        });
+        if !yields {
+            run(world);
+        }
+    }
+}
+
+/// The match whose system is running, if its sequence is on an entity.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct MatchSource(pub Option<MatchInfo>);
+
+/// How a sequence matched.
+#[derive(Clone, Copy, Default)]
+struct Details {
+    device: Option<Entity>,
+    duration: Duration,
+    len: usize,
+    mistakes: u32,
+    held: Option<Duration>,
+}
+
+/// Run the system of a sequence of `len` acts with [MatchSource] describing
+/// the match.
+pub(crate) fn run_matched<I>(
+    commands: &mut Commands,
+    system_id: SystemId<I>,
+    input: I::Inner<'static>,
+    device: Option<Entity>,
+    duration: Duration,
+    len: usize,
+) where
+    I: SystemInput + 'static,
+    I::Inner<'static>: Send + Sync,
+{
+    queue_match(
+        commands,
+        system_id,
+        input,
+        Details {
+            device,
+            duration,
+            len,
+            ..Details::default()
+        },
+    );
+}
+
+/// Run a sequence's system for a match that forgave `mistakes` wrong inputs,
+/// or leave it to [resolve_matches] if the sequence has a [MatchPriority].
+pub(crate) fn run_matched_with_mistakes<I>(
+    commands: &mut Commands,
+    system_id: SystemId<I>,
+    input: I::Inner<'static>,
+    device: Option<Entity>,
+    duration: Duration,
+    len: usize,
+    mistakes: u32,
+) where
+    I: SystemInput + 'static,
+    I::Inner<'static>: Send + Sync,
+{
+    queue_match(
+        commands,
+        system_id,
+        input,
+        Details {
+            device,
+            duration,
+            len,
+            mistakes,
+            held: None,
+        },
+    );
+}
+
+/// Run the system of a sequence of `len` acts whose last one was held for
+/// `held`, if it was held.
+pub(crate) fn run_matched_held(
+    commands: &mut Commands,
+    system_id: SystemId,
+    duration: Duration,
+    len: usize,
+    held: Option<Duration>,
+) {
+    queue_match(
+        commands,
+        system_id,
+        (),
+        Details {
+            duration,
+            len,
+            held,
+            ..Details::default()
+        },
+    );
+}
+
+/// Queue running a sequence's system with `details` of its match.
+fn queue_match<I>(
+    commands: &mut Commands,
+    system_id: SystemId<I>,
+    input: I::Inner<'static>,
+    details: Details,
+) where
+    I: SystemInput + 'static,
+    I::Inner<'static>: Send + Sync,
+{
+    let len = details.len;
+    commands.queue(move |world: &mut World| {
+        let system_entity = system_id.entity();
+        let completion = world.get_entity(system_entity).ok().map(|entity| Completion {
+            len,
+            order: (
+                entity
+                    .get_change_ticks::<Parent>()
+                    .map(|ticks| ticks.added.get())
+                    .unwrap_or_default(),
+                system_entity,
+            ),
+        });
+        let priority = world
+            .get::<Parent>(system_entity)
+            .and_then(|parent| world.get::<MatchPriority>(parent.get()))
+            .copied()
+            .or_else(|| {
+                world
+                    .get_resource::<InputSequencePluginSettings>()
+                    .map(|settings| settings.priority)
+            })
+            .unwrap_or_default();
+        let run = move |world: &mut World| run_now(world, system_id, input, details);
+        let Some(mut pending) = world.get_resource_mut::<PendingMatches>() else {
+            run(world);
+            return;
+        };
+        if let Some(completion) = completion {
+            pending.completed.push(completion);
+            if priority != MatchPriority::AllFire {
+                pending.waiting.push((priority, completion, Box::new(run)));
+                return;
+            }
+        }
+        run(world);
+    });
+}
+
+/// Run a sequence's system with [MatchSource] describing the match.
+fn run_now<I>(
+    world: &mut World,
+    system_id: SystemId<I>,
+    input: I::Inner<'static>,
+    details: Details,
+) where
+    I: SystemInput + 'static,
+{
+    let Details {
+        device,
+        duration,
+        len,
+        mistakes,
+        held,
+    } = details;
+    // The system is a child of the entity the sequence is on.
+    let parent = world.get::<Parent>(system_id.entity()).map(|parent| parent.get());
+    if parent.is_some_and(|entity| !start_cooldown(world, entity)) {
+        return;
+    }
+    let info = parent.map(|entity| {
+        MatchInfo {
+            entity,
+            device,
+            duration,
+            inputs: len + mistakes as usize,
+            lenient: mistakes > 0,
+            mistakes,
+            score: world
+                .get::<Scoring>(entity)
+                .map(|scoring| scoring.score(duration, mistakes)),
+            held,
+            emit: world.get::<Emit>(entity).copied().unwrap_or_default(),
+        }
+    });
+    world.insert_resource(MatchSource(info));
+    let _ = world.run_system_with_input(system_id, input);
+    world.insert_resource(MatchSource(None));
+    if let Some(info) = info {
+        world.trigger_targets(
+            SequenceComplete {
+                device: info.device,
+                duration: info.duration,
+                inputs: info.inputs,
+                lenient: info.lenient,
+                mistakes: info.mistakes,
+                score: info.score,
+                held: info.held,
+            },
+            info.entity,
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Sequences that mix keys, mouse buttons, and gamepad buttons
+//!
+//! A [MixedAct] is a press from any of these devices, so one sequence can span
+//! them, e.g., `Ctrl` then left-click then `South`. Presses from all devices
+//! share one timeline in the order they arrive. Enable with
+//! [match_mixed](crate::InputSequencePlugin::match_mixed).
+//!
+//! A modifier key pressed on its own is a chord without that modifier, e.g.,
+//! pressing left `Ctrl` is `KeyChord(Modifiers::empty(), KeyCode::ControlLeft)`.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, mixed::{MixedAct, MixedSequence}};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(MixedSequence::new(
+//!         || info!("mixed"),
+//!         [
+//!             MixedAct::from(KeyCode::ControlLeft),
+//!             MixedAct::from(MouseButton::Left),
+//!             MixedAct::from(GamepadButton::South),
+//!         ],
+//!     ));
+//! }
+//! ```
+use crate::{
+    act_queue::ActQueue, input_sequence::InputSequence, mouse::MouseAct, KeyChord, Modifiers,
+};
+use bevy::{
+    ecs::system::{Query, Res, ResMut},
+    input::{
+        gamepad::{Gamepad, GamepadButton},
+        keyboard::KeyCode,
+        mouse::MouseButton,
+        ButtonInput,
+    },
+};
+
+/// A press from a keyboard, mouse, or gamepad.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MixedAct {
+    /// A key chord.
+    Key(KeyChord),
+    /// A mouse button press.
+    Mouse(MouseAct),
+    /// A gamepad button press on any gamepad.
+    Button(GamepadButton),
+}
+
+impl From<KeyChord> for MixedAct {
+    fn from(chord: KeyChord) -> Self {
+        MixedAct::Key(chord)
+    }
+}
+
+impl From<KeyCode> for MixedAct {
+    fn from(key: KeyCode) -> Self {
+        MixedAct::Key(KeyChord::from(key))
+    }
+}
+
+impl From<MouseButton> for MixedAct {
+    fn from(button: MouseButton) -> Self {
+        MixedAct::Mouse(MouseAct(button))
+    }
+}
+
+impl From<GamepadButton> for MixedAct {
+    fn from(button: GamepadButton) -> Self {
+        MixedAct::Button(button)
+    }
+}
+
+/// Represents a sequence of presses from several devices
+pub type MixedSequence = InputSequence<MixedAct, ()>;
+
+/// Queue the presses of all devices this update.
+pub(crate) fn queue_mixed_acts(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mouse: Option<Res<ButtonInput<MouseButton>>>,
+    gamepads: Query<&Gamepad>,
+    mut queue: ResMut<ActQueue<MixedAct>>,
+) {
+    queue.extend(just_pressed(keys.as_deref(), mouse.as_deref(), &gamepads));
+}
+
+/// Return the presses of all devices this update, keys first.
+pub(crate) fn just_pressed(
+    keys: Option<&ButtonInput<KeyCode>>,
+    mouse: Option<&ButtonInput<MouseButton>>,
+    gamepads: &Query<&Gamepad>,
+) -> Vec<MixedAct> {
+    let mut acts = Vec::new();
+    if let Some(keys) = keys {
+        let mods = Modifiers::from(keys);
+        acts.extend(keys.get_just_pressed().map(|key| {
+            MixedAct::Key(KeyChord(mods.difference(Modifiers::from(*key)), *key))
+        }));
+    }
+    if let Some(mouse) = mouse {
+        acts.extend(mouse.get_just_pressed().map(|b| MixedAct::from(*b)));
+    }
+    for gamepad in gamepads {
+        acts.extend(gamepad.get_just_pressed().map(|b| MixedAct::Button(*b)));
+    }
+    acts
+}
@@ -0,0 +1,119 @@
+//! Game-defined modifiers
+//!
+//! [Modifiers] stores Ctrl, Alt, Shift, and Super in its low nybble. The high
+//! nybble is free for game-defined modifiers, e.g., holding the aim button.
+//! Register a held-state predicate for a bit and key chords using that bit
+//! will only match while the predicate returns true.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, modifier::{self, AddCustomModifier}};
+//!
+//! fn aiming(mouse: Res<ButtonInput<MouseButton>>) -> bool {
+//!     mouse.pressed(MouseButton::Right)
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(InputSequencePlugin::default())
+//!    .init_resource::<ButtonInput<MouseButton>>()
+//!    .add_custom_modifier(modifier::CUSTOM_1, aiming);
+//!
+//! let _aimed_shot = KeyChord(modifier::CUSTOM_1, KeyCode::KeyF);
+//! ```
+use bevy::{
+    app::App,
+    ecs::{
+        system::{IntoSystem, Resource, SystemId},
+        world::World,
+    },
+};
+
+use crate::Modifiers;
+
+/// First game-defined modifier bit.
+pub const CUSTOM_1: Modifiers = Modifiers(0b0001_0000);
+/// Second game-defined modifier bit.
+pub const CUSTOM_2: Modifiers = Modifiers(0b0010_0000);
+/// Third game-defined modifier bit.
+pub const CUSTOM_3: Modifiers = Modifiers(0b0100_0000);
+/// Fourth game-defined modifier bit.
+pub const CUSTOM_4: Modifiers = Modifiers(0b1000_0000);
+
+/// All game-defined modifier bits.
+pub const CUSTOM: Modifiers = Modifiers(0b1111_0000);
+
+/// Holds the predicates for game-defined modifiers and which are currently
+/// held.
+#[derive(Resource, Debug)]
+pub struct CustomModifiers {
+    predicates: Vec<(Modifiers, SystemId<(), bool>)>,
+    held: Modifiers,
+}
+
+impl Default for CustomModifiers {
+    fn default() -> Self {
+        Self {
+            predicates: Vec::new(),
+            held: Modifiers::empty(),
+        }
+    }
+}
+
+impl CustomModifiers {
+    /// Return the game-defined modifiers held as of the last update.
+    pub fn held(&self) -> Modifiers {
+        self.held
+    }
+}
+
+/// Register game-defined modifiers.
+pub trait AddCustomModifier {
+    /// Treat `modifier` as held whenever the system `is_held` returns true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modifier` uses any of the standard modifier bits.
+    fn add_custom_modifier<M>(
+        &mut self,
+        modifier: Modifiers,
+        is_held: impl IntoSystem<(), bool, M> + 'static,
+    ) -> &mut Self;
+}
+
+impl AddCustomModifier for App {
+    fn add_custom_modifier<M>(
+        &mut self,
+        modifier: Modifiers,
+        is_held: impl IntoSystem<(), bool, M> + 'static,
+    ) -> &mut Self {
+        assert!(
+            CUSTOM.contains(modifier),
+            "Custom modifier {modifier:?} must only use the high nybble"
+        );
+        let world = self.world_mut();
+        let id = world.register_system(is_held);
+        world
+            .get_resource_or_insert_with(CustomModifiers::default)
+            .predicates
+            .push((modifier, id));
+        self
+    }
+}
+
+/// Run the held-state predicates and record which modifiers are held.
+pub(crate) fn update_custom_modifiers(world: &mut World) {
+    let Some(predicates) = world
+        .get_resource::<CustomModifiers>()
+        .map(|custom| custom.predicates.clone())
+    else {
+        return;
+    };
+    let mut held = Modifiers::empty();
+    for (modifier, id) in predicates {
+        if world.run_system(id).unwrap_or(false) {
+            held |= modifier;
+        }
+    }
+    world.resource_mut::<CustomModifiers>().held = held;
+}
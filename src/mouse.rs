@@ -0,0 +1,72 @@
+//! Mouse button sequences
+//!
+//! Each mouse button press is a [MouseAct]. Enable with
+//! [match_mouse](crate::InputSequencePlugin::match_mouse); it is on by default
+//! when `ButtonInput<MouseButton>` exists.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, mouse::MouseSequence};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(MouseSequence::new(
+//!         || info!("ping"),
+//!         [MouseButton::Right, MouseButton::Right, MouseButton::Middle],
+//!     ));
+//! }
+//! ```
+use crate::{act_queue::ActQueue, input_sequence::InputSequence};
+use bevy::{
+    ecs::system::{Res, ResMut},
+    input::{mouse::MouseButton, ButtonInput},
+    reflect::Reflect,
+};
+use std::cmp::Ordering;
+
+/// A press of a mouse button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub struct MouseAct(pub MouseButton);
+
+impl From<MouseButton> for MouseAct {
+    #[inline(always)]
+    fn from(button: MouseButton) -> Self {
+        MouseAct(button)
+    }
+}
+
+impl MouseAct {
+    fn rank(&self) -> u32 {
+        match self.0 {
+            MouseButton::Left => 0,
+            MouseButton::Right => 1,
+            MouseButton::Middle => 2,
+            MouseButton::Back => 3,
+            MouseButton::Forward => 4,
+            MouseButton::Other(n) => 5 + n as u32,
+        }
+    }
+}
+
+// MouseButton is not ordered, which the trie requires.
+impl Ord for MouseAct {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for MouseAct {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Represents a mouse button sequence
+pub type MouseSequence = InputSequence<MouseAct, ()>;
+
+/// Queue the mouse buttons pressed this update.
+pub(crate) fn queue_mouse_buttons(
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut queue: ResMut<ActQueue<MouseAct>>,
+) {
+    queue.extend(buttons.get_just_pressed().map(|b| MouseAct(*b)));
+}
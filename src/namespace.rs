@@ -0,0 +1,139 @@
+//! Group sequences by the crate that registers them
+//!
+//! When several crates register sequences into the same app, give each
+//! crate's sequences a [Namespace]. A namespace can be switched off as a whole
+//! with [Namespaces], and [conflicts] reports sequences from different
+//! namespaces that would shadow each other. Sequences without a namespace
+//! belong to the host game.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, namespace::{Namespace, Namespaces}};
+//!
+//! fn setup(mut commands: Commands, mut namespaces: ResMut<Namespaces>) {
+//!     commands.queue(
+//!         KeySequence::new(|| info!("toggle inspector"), keyseq! { Ctrl-Shift-I })
+//!             .namespace("inspector"),
+//!     );
+//!     namespaces.disable("inspector");
+//! }
+//! ```
+use crate::input_sequence::InputSequence;
+use bevy::ecs::{component::Component, entity::Entity, system::Resource, system::SystemInput};
+use std::{borrow::Cow, collections::HashSet, fmt};
+
+/// The origin of a sequence, typically the name of the crate that registers
+/// it.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Namespace(pub Cow<'static, str>);
+
+impl Namespace {
+    /// Create a namespace.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+
+    /// Qualify `label` with this namespace, e.g., "inspector::toggle".
+    pub fn qualify(&self, label: &str) -> String {
+        format!("{}::{label}", self.0)
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Namespaces that are switched off. All namespaces are enabled by default.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Namespaces {
+    disabled: HashSet<Namespace>,
+}
+
+impl Namespaces {
+    /// Stop matching the sequences in namespace `name`.
+    pub fn disable(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.disabled.insert(Namespace::new(name));
+    }
+
+    /// Resume matching the sequences in namespace `name`.
+    pub fn enable(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.disabled.remove(&Namespace::new(name));
+    }
+
+    /// Return true if sequences in namespace `name` match.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        !self.disabled.iter().any(|ns| ns.0 == name)
+    }
+
+    /// Return true if a sequence in `namespace` may match. Sequences without a
+    /// namespace always may.
+    pub fn allows(&self, namespace: Option<&Namespace>) -> bool {
+        !namespace.is_some_and(|ns| self.disabled.contains(ns))
+    }
+}
+
+/// How two sequences conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Both sequences have the same acts.
+    Same,
+    /// The first sequence's acts begin the second's.
+    Prefix,
+}
+
+/// Two sequences from different namespaces that shadow each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The shorter sequence.
+    pub first: Entity,
+    /// Namespace of the first sequence.
+    pub first_namespace: Option<Namespace>,
+    /// The longer sequence.
+    pub second: Entity,
+    /// Namespace of the second sequence.
+    pub second_namespace: Option<Namespace>,
+    /// How they conflict.
+    pub kind: ConflictKind,
+}
+
+/// Report the pairs of sequences from different namespaces where one's acts
+/// equal or begin the other's.
+pub fn conflicts<'a, Act, I>(
+    sequences: impl IntoIterator<Item = (Entity, &'a InputSequence<Act, I>, Option<&'a Namespace>)>,
+) -> Vec<Conflict>
+where
+    Act: PartialEq + 'a,
+    I: SystemInput + 'static,
+{
+    let sequences: Vec<_> = sequences.into_iter().collect();
+    let mut conflicts = Vec::new();
+    for (i, (a, seq_a, ns_a)) in sequences.iter().enumerate() {
+        for (b, seq_b, ns_b) in &sequences[i + 1..] {
+            if ns_a == ns_b {
+                continue;
+            }
+            let (first, second) = if seq_a.acts.len() <= seq_b.acts.len() {
+                ((a, seq_a, ns_a), (b, seq_b, ns_b))
+            } else {
+                ((b, seq_b, ns_b), (a, seq_a, ns_a))
+            };
+            if !second.1.acts.starts_with(&first.1.acts) {
+                continue;
+            }
+            conflicts.push(Conflict {
+                first: *first.0,
+                first_namespace: first.2.cloned(),
+                second: *second.0,
+                second_namespace: second.2.cloned(),
+                kind: if first.1.acts.len() == second.1.acts.len() {
+                    ConflictKind::Same
+                } else {
+                    ConflictKind::Prefix
+                },
+            });
+        }
+    }
+    conflicts
+}
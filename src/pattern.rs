@@ -0,0 +1,177 @@
+//! Match key sequences with wildcard and alternative steps
+//!
+//! A [PatternSequence] is a key sequence whose steps are [KeyPattern]s: a
+//! specific chord, any chord, any of a few chords, or any digit or letter key
+//! pressed without modifiers. The trie can't hold
+//! such steps, so pattern sequences are checked against a window of the most
+//! recent chords instead.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, pattern::{KeyPattern, PatternSequence}};
+//!
+//! fn setup(mut commands: Commands) {
+//!     // Escape, any key, Escape.
+//!     commands.queue(PatternSequence::new(
+//!         || info!("menu"),
+//!         [KeyCode::Escape.into(), KeyPattern::Any, KeyCode::Escape.into()],
+//!     ));
+//!     // A or B, then C.
+//!     commands.queue(PatternSequence::new(
+//!         || info!("branch"),
+//!         [KeyPattern::any_of(keyseq! { A B }), KeyCode::KeyC.into()],
+//!     ));
+//!     // Any three digits, then Enter.
+//!     commands.queue(PatternSequence::new(
+//!         || info!("code entered"),
+//!         [KeyPattern::AnyDigit, KeyPattern::AnyDigit, KeyPattern::AnyDigit,
+//!          KeyCode::Enter.into()],
+//!     ));
+//! }
+//! ```
+use crate::{
+    chord::key_label, cond_system::IntoCondSystem, input_sequence::sequence_builder_commands,
+    time_limit::TimeLimit, KeyChord,
+};
+use bevy::{
+    ecs::{
+        component::Component,
+        system::{IntoSystem, System, SystemId},
+        world::World,
+    },
+    input::keyboard::KeyCode,
+};
+
+/// One step of a [PatternSequence].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeyPattern {
+    /// This chord.
+    Key(KeyChord),
+    /// Any chord.
+    Any,
+    /// Any of these chords.
+    AnyOf(Vec<KeyChord>),
+    /// Any of the digit keys above the letters, without modifiers.
+    AnyDigit,
+    /// Any letter key, without modifiers.
+    AnyLetter,
+}
+
+impl KeyPattern {
+    /// Match any of `chords`.
+    pub fn any_of<T: Into<KeyChord>>(chords: impl IntoIterator<Item = T>) -> Self {
+        KeyPattern::AnyOf(chords.into_iter().map(Into::into).collect())
+    }
+
+    /// Return true if `chord` satisfies this step.
+    pub fn matches(&self, chord: &KeyChord) -> bool {
+        match self {
+            KeyPattern::Key(key) => key == chord,
+            KeyPattern::Any => true,
+            KeyPattern::AnyOf(keys) => keys.contains(chord),
+            KeyPattern::AnyDigit => {
+                chord.0.is_empty() && is_single(chord.1, char::is_ascii_digit)
+            }
+            KeyPattern::AnyLetter => {
+                chord.0.is_empty() && is_single(chord.1, char::is_ascii_alphabetic)
+            }
+        }
+    }
+}
+
+/// Return true if the label of `key` is a single character satisfying `f`,
+/// e.g., "7" for `Digit7` but not "F7".
+fn is_single(key: KeyCode, f: impl Fn(&char) -> bool) -> bool {
+    let mut chars = key_label(key).chars();
+    chars.next().is_some_and(|c| f(&c)) && chars.next().is_none()
+}
+
+impl From<KeyChord> for KeyPattern {
+    fn from(chord: KeyChord) -> Self {
+        KeyPattern::Key(chord)
+    }
+}
+
+impl From<KeyCode> for KeyPattern {
+    fn from(key: KeyCode) -> Self {
+        KeyPattern::Key(key.into())
+    }
+}
+
+/// A key sequence with wildcard and alternative steps.
+#[derive(Component, Clone, Debug)]
+pub struct PatternSequence {
+    /// System to run when the sequence matches.
+    pub system_id: SystemId,
+    /// Steps to match.
+    pub patterns: Vec<KeyPattern>,
+    /// Optional time limit from the first chord.
+    pub time_limit: Option<TimeLimit>,
+}
+
+impl PatternSequence {
+    /// Create a pattern sequence. Not operant until added to an entity.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<C, M>(
+        system: C,
+        patterns: impl IntoIterator<Item = KeyPattern>,
+    ) -> PatternSequenceBuilder<C::System>
+    where
+        C: IntoCondSystem<(), (), M> + 'static,
+    {
+        PatternSequenceBuilder {
+            system: IntoSystem::into_system(system),
+            patterns: patterns.into_iter().collect(),
+            time_limit: None,
+        }
+    }
+
+    /// Return true if the last chords of `recent` match the steps.
+    pub fn matches(&self, recent: &[KeyChord]) -> bool {
+        let n = self.patterns.len();
+        n > 0
+            && recent.len() >= n
+            && self
+                .patterns
+                .iter()
+                .zip(&recent[recent.len() - n..])
+                .all(|(pattern, chord)| pattern.matches(chord))
+    }
+}
+
+/// Builder for a [PatternSequence].
+///
+/// Besides a time limit it has no options of its own. Of the
+/// [InputSequenceBuilder](crate::input_sequence::InputSequenceBuilder)
+/// options, [Cheat](crate::cheat::Cheat),
+/// [Namespace](crate::namespace::Namespace), [Once](crate::disabled::Once),
+/// [Cooldown](crate::cooldown::Cooldown), [Tags](crate::tag::Tags), and
+/// [MatchSchedule](crate::schedule::MatchSchedule) are honored when inserted
+/// on the sequence's entity; the rest are ignored.
+pub struct PatternSequenceBuilder<S> {
+    system: S,
+    patterns: Vec<KeyPattern>,
+    time_limit: Option<TimeLimit>,
+}
+
+impl<S> PatternSequenceBuilder<S>
+where
+    S: System<In = (), Out = ()>,
+{
+    /// Specify a time limit from the start of the first matching input.
+    pub fn time_limit(mut self, time_limit: impl Into<TimeLimit>) -> Self {
+        self.time_limit = Some(time_limit.into());
+        self
+    }
+
+    /// Build the sequence. Requires world to register the system.
+    pub fn build(self, world: &mut World) -> PatternSequence {
+        PatternSequence {
+            system_id: world.register_system(self.system),
+            patterns: self.patterns,
+            time_limit: self.time_limit,
+        }
+    }
+}
+
+sequence_builder_commands!(PatternSequenceBuilder);
@@ -2,54 +2,197 @@ use bevy::{
     app::{App, Plugin, Update},
     core::FrameCount,
     ecs::{
+        change_detection::{DetectChanges, Ref},
         entity::Entity,
+        event::EventWriter,
         prelude::In,
         intern::Interned,
-        query::Added,
+        query::{Added, Changed, Has, Or, With, Without},
+        system::SystemInput,
         removal_detection::RemovedComponents,
         schedule::{IntoSystemConfigs, ScheduleLabel, SystemSet},
-        system::{Commands, Local, Query, Res, ResMut},
+        system::{Commands, Local, Query, Res, ResMut, Resource, SystemId, SystemParam},
     },
     input::{
         gamepad::{Gamepad, GamepadButton},
-        keyboard::KeyCode,
+        gestures::{DoubleTapGesture, PanGesture, PinchGesture},
+        touch::TouchInput,
+        keyboard::{KeyCode, KeyboardInput},
+        mouse::MouseButton,
         ButtonInput,
     },
     log::warn,
-    time::Time,
+    time::{Real, Time},
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Duration,
 };
-use std::collections::{HashMap, VecDeque};
 
 use crate::{
+    adaptive::{narrow_window, widen_windows, WindowAdjusted},
+    prerequisite::{close_prerequisites, open_prerequisites},
+    cheat::{Cheat, CheatsEnabled},
+    button::{ButtonAct, ButtonGroup, PullLevel, TapHoldCalibration, TriggerCalibration},
+    act_queue::{add_act_matcher, ActHeld},
     cache::{ButtonSequenceCache, KeySequenceCache},
     chord::{is_modifier, KeyChordQueue},
-    frame_time::FrameTime,
-    input_sequence::{ButtonSequence, InputSequence, KeySequence},
-    KeyChord, Modifiers,
+    dedupe::{detect_duplicate_presses, DuplicatePresses},
+    deferred::{drain_deferred_sequences, DeferredSequences},
+    disabled::{disable_once, register_sequence},
+    focus::{FocusOwner, SequenceFocus},
+    frame_time::{track_paused_time, FrameTime, PausedTime},
+    gesture::{queue_gesture_acts, GestureAct, GestureCalibration},
+    guard::InputSequenceGuard,
+    keyboard_layout::{detect_layout_changes, BindingsRemapped, KeyboardLayoutMap},
+    layout::GamepadProfile,
+    matched::{
+        resolve_matches, run_matched, run_matched_with_mistakes, AbortReason, MatchPriority,
+        MatchSource, PendingMatches, SequenceAborted,
+    },
+    mash::{mash_matcher, MashProgress, MashSequence},
+    mixed::{queue_mixed_acts, MixedAct},
+    mouse::{queue_mouse_buttons, MouseAct},
+    namespace::{Namespace, Namespaces},
+    hashed::{HashedKeySequence, Rolling, RollingEntry, RollingWindow, SequenceDigest},
+    pattern::PatternSequence,
+    input_sequence::{ButtonSequence, InputSequence, KeySequence, Press},
+    modifier::{update_custom_modifiers, CustomModifiers},
+    schedule::{
+        enter_schedule, leave_schedule, set_schedule, CurrentSchedule, MatchSchedule,
+        ScheduleCaches,
+    },
+    simon::{fail_simon_rounds, SimonOutcome},
+    stick::{Direction, Stick, StickAsDpad, StickCalibration},
+    stylus::{queue_stylus_acts, StylusAct, StylusCalibration},
+    throttle::{throttle_presses, ThrottleHistory},
+    tag::{count_tagged, TagStats},
+    text::queue_char_acts,
+    hold::{queue_hold_acts, HoldAct},
+    health::{update_gamepad_gates, GamepadGateChanged, GatedGamepads},
+    tolerance::{Progress, Tolerance},
+    tutorial::update_tutorial_hints,
+    progress::{report_key_progress, SequenceProgress},
+    qte::expire_qtes,
+    KeyChord, Modifiers, TimeLimit,
+};
+use trie_rs::{
+    inc_search::{Answer, IncSearch},
+    map::Trie,
 };
-use trie_rs::inc_search::{Answer, IncSearch};
 
-/// ButtonInput sequence plugin.
-pub struct InputSequencePlugin {
+/// App-wide settings for [InputSequencePlugin].
+///
+/// Available to systems as a resource once the plugin is added.
+///
+/// What a wrong input does is decided per sequence, by its
+/// [match_mode](crate::input_sequence::InputSequenceBuilder::match_mode) or
+/// [tolerate](crate::input_sequence::InputSequenceBuilder::tolerate) option,
+/// not here. Input is neither buffered nor consumed: every matcher sees each
+/// input in the frame it arrives, so overlapping sequences can all match it.
+#[derive(Resource, Clone, Debug)]
+pub struct InputSequencePluginSettings {
+    /// Schedules and optional system sets the matchers run in.
     #[allow(clippy::type_complexity)]
-    schedules: Vec<(Interned<dyn ScheduleLabel>, Option<Interned<dyn SystemSet>>)>,
-    match_key: Option<bool>,
-    match_button: Option<bool>,
+    pub schedules: Vec<(Interned<dyn ScheduleLabel>, Option<Interned<dyn SystemSet>>)>,
+    /// Match key sequences. By default will match keys if resource
+    /// `ButtonInput<KeyCode>` exists.
+    pub match_key: Option<bool>,
+    /// Match button sequences. Off by default.
+    pub match_button: Option<bool>,
+    /// Match mouse button sequences. By default will match mouse buttons if
+    /// resource `ButtonInput<MouseButton>` exists.
+    pub match_mouse: Option<bool>,
+    /// Match sequences that mix keys, mouse buttons, and gamepad buttons. Off
+    /// by default.
+    pub match_mixed: Option<bool>,
+    /// Match touchpad gesture sequences. Off by default.
+    pub match_gesture: Option<bool>,
+    /// Match stylus sequences. Off by default.
+    pub match_stylus: Option<bool>,
+    /// Match timed hold sequences. Off by default.
+    pub match_hold: Option<bool>,
+    /// Match typed character sequences. Off by default.
+    pub match_text: Option<bool>,
+    /// Match button-mash sequences. Off by default.
+    pub match_mash: Option<bool>,
+    /// Time limit for sequences that do not specify their own.
+    pub default_time_limit: Option<TimeLimit>,
+    /// Most partial button matches to track at once. Unbounded by default.
+    pub max_partial_matches: Option<usize>,
+    /// Which partial matches to drop when over `max_partial_matches`.
+    pub eviction: EvictionPolicy,
+    /// Which sequences fire when several complete in the same frame.
+    pub priority: MatchPriority,
+    /// How long to hold back a key sequence that begins a longer one before
+    /// running it. Off by default, so it runs at once.
+    pub prefix_timeout: Option<TimeLimit>,
+}
+
+/// Which partial matches to drop when there are too many.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// Drop the partial match that started first.
+    #[default]
+    Oldest,
+    /// Drop the partial match with the fewest matched acts, oldest first.
+    LeastProgress,
 }
 
-impl Default for InputSequencePlugin {
+impl Default for InputSequencePluginSettings {
     fn default() -> Self {
-        InputSequencePlugin {
+        Self {
             schedules: vec![(Interned(Box::leak(Box::new(Update))), None)],
             match_key: None,
             match_button: None,
+            match_mouse: None,
+            match_mixed: None,
+            match_gesture: None,
+            match_stylus: None,
+            match_hold: None,
+            match_text: None,
+            match_mash: None,
+            default_time_limit: None,
+            max_partial_matches: None,
+            eviction: EvictionPolicy::default(),
+            priority: MatchPriority::default(),
+            prefix_timeout: None,
         }
     }
 }
 
+/// ButtonInput sequence plugin.
+#[derive(Default)]
+pub struct InputSequencePlugin {
+    settings: InputSequencePluginSettings,
+}
+
+impl From<InputSequencePluginSettings> for InputSequencePlugin {
+    fn from(settings: InputSequencePluginSettings) -> Self {
+        Self { settings }
+    }
+}
+
 impl Plugin for InputSequencePlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<InputSequenceGuard>();
+        app.init_resource::<DeferredSequences>();
+        app.init_resource::<CheatsEnabled>();
+        app.init_resource::<Namespaces>();
+        app.init_resource::<MatchSource>();
+        app.init_resource::<DuplicatePresses>();
+        app.init_resource::<ThrottleHistory>();
+        app.insert_resource(self.settings.clone());
+        app.register_type::<TimeLimit>().register_type::<Press>();
+        app.insert_resource(CurrentSchedule::new(
+            self.settings
+                .schedules
+                .first()
+                .map(|(schedule, _)| *schedule)
+                .unwrap_or_else(|| Update.intern()),
+        ));
         if self
+            .settings
             .match_key
             .unwrap_or(app.world().get_resource::<ButtonInput<KeyCode>>().is_some())
         {
@@ -58,38 +201,52 @@ impl Plugin for InputSequencePlugin {
                 // .register_type::<InputSequenceCache<KeyChord, ()>>()
                 ;
             // Add key sequence.
-            app.init_resource::<KeySequenceCache>();
+            app.init_resource::<KeySequenceCache>()
+                .init_resource::<ScheduleCaches<KeySequenceCache>>();
             app.init_resource::<KeyChordQueue>();
+            app.init_resource::<KeyboardLayoutMap>()
+                .add_event::<KeyboardInput>()
+                .add_event::<BindingsRemapped>()
+                .add_event::<SequenceAborted>();
 
-            for (schedule, set) in &self.schedules {
-                if let Some(set) = set {
-                    app.add_systems(
-                        *schedule,
-                        (
-                            detect_key_removals,
-                            detect_key_additions,
-                            key_sequence_matcher,
-                        )
-                            .chain()
-                            .in_set(*set),
-                    );
-                } else {
-                    app.add_systems(
-                        *schedule,
-                        (
-                            detect_key_removals,
-                            detect_key_additions,
-                            key_sequence_matcher,
-                        )
-                            .chain(),
-                    );
-                }
-            }
+            register_sequence::<KeySequence>(app);
+            register_sequence::<HashedKeySequence>(app);
+            register_sequence::<PatternSequence>(app);
+            add_matcher(app, &self.settings, |schedule| {
+                (
+                    enter_schedule::<KeySequenceCache>(schedule),
+                    drain_deferred_sequences,
+                    detect_layout_changes,
+                    detect_key_removals,
+                    detect_key_additions,
+                    detect_focus_changes,
+                    detect_key_cheat_changes,
+                    detect_key_namespace_changes,
+                    update_custom_modifiers,
+                    detect_duplicate_presses,
+                    throttle_presses,
+                    hashed_key_sequence_matcher,
+                    pattern_sequence_matcher,
+                    tolerant_key_sequence_matcher,
+                    key_sequence_matcher,
+                    leave_schedule::<KeySequenceCache>(schedule),
+                )
+                    .chain()
+            });
+            app.add_event::<SequenceProgress>().add_systems(
+                bevy::app::PostUpdate,
+                (update_tutorial_hints, report_key_progress),
+            );
+            #[cfg(feature = "ui")]
+            app.add_systems(
+                bevy::app::PostUpdate,
+                (crate::hint::build_hints, crate::hint::update_hints).chain(),
+            );
         } else {
             warn!("No key sequence matcher added; consider adding DefaultPlugins.");
         }
 
-        if self.match_button.unwrap_or(
+        if self.settings.match_button.unwrap_or(
             false
             // NOTE: Is there a way to detect whether gamepad input is available post 0.14?
             // app.world()
@@ -97,39 +254,113 @@ impl Plugin for InputSequencePlugin {
             //     .is_some(),
         ) {
             // app
-            //     .register_type::<InputSequence<GamepadButton, In<Entity>>>()
+            app.register_type::<InputSequence<ButtonAct, In<Entity>>>();
             //     // .register_type::<InputSequenceCache<GamepadButton, Gamepad>>()
             //     ;
             // Add button sequences.
-            app.init_resource::<ButtonSequenceCache>();
-
-            for (schedule, set) in &self.schedules {
-                if let Some(set) = set {
-                    app.add_systems(
-                        *schedule,
-                        (
-                            detect_button_removals,
-                            detect_button_additions,
-                            button_sequence_matcher,
-                        )
-                            .chain()
-                            .in_set(*set),
-                    );
-                } else {
-                    app.add_systems(
-                        *schedule,
-                        (
-                            detect_button_removals,
-                            detect_button_additions,
-                            button_sequence_matcher,
-                        )
-                            .chain(),
-                    );
-                }
-            }
+            app.init_resource::<ButtonSequenceCache>()
+                .init_resource::<ScheduleCaches<ButtonSequenceCache>>();
+            app.init_resource::<GatedGamepads>()
+                .add_event::<GamepadGateChanged>()
+                .add_event::<SequenceAborted>();
+            app.register_type::<StickCalibration>()
+                .init_resource::<StickCalibration>()
+                .register_type::<TriggerCalibration>()
+                .init_resource::<TriggerCalibration>()
+                .register_type::<TapHoldCalibration>()
+                .init_resource::<TapHoldCalibration>();
+
+            register_sequence::<ButtonSequence>(app);
+            add_matcher(app, &self.settings, |schedule| {
+                (
+                    enter_schedule::<ButtonSequenceCache>(schedule),
+                    drain_deferred_sequences,
+                    detect_button_removals,
+                    detect_button_additions,
+                    detect_button_cheat_changes,
+                    detect_button_namespace_changes,
+                    detect_duplicate_presses,
+                    throttle_presses,
+                    update_gamepad_gates,
+                    button_sequence_matcher,
+                    leave_schedule::<ButtonSequenceCache>(schedule),
+                )
+                    .chain()
+            });
         } else {
             warn!("No button sequence matcher added; consider adding DefaultPlugins.");
         }
+
+        if self.settings.match_mouse.unwrap_or(
+            app.world()
+                .get_resource::<ButtonInput<MouseButton>>()
+                .is_some(),
+        ) {
+            add_act_matcher::<MouseAct, _>(app, &self.settings, queue_mouse_buttons);
+        }
+
+        if self.settings.match_mixed.unwrap_or(false) {
+            add_act_matcher::<MixedAct, _>(app, &self.settings, queue_mixed_acts);
+        }
+
+        if self.settings.match_gesture.unwrap_or(false) {
+            app.add_event::<PanGesture>()
+                .add_event::<PinchGesture>()
+                .add_event::<DoubleTapGesture>()
+                .register_type::<GestureCalibration>()
+                .init_resource::<GestureCalibration>();
+            add_act_matcher::<GestureAct, _>(app, &self.settings, queue_gesture_acts);
+        }
+
+        if self.settings.match_stylus.unwrap_or(false) {
+            app.add_event::<TouchInput>()
+                .register_type::<StylusCalibration>()
+                .init_resource::<StylusCalibration>();
+            add_act_matcher::<StylusAct, _>(app, &self.settings, queue_stylus_acts);
+        }
+
+        if self.settings.match_hold.unwrap_or(false) {
+            app.init_resource::<ActHeld<HoldAct>>();
+            add_act_matcher::<HoldAct, _>(app, &self.settings, queue_hold_acts);
+        }
+
+        if self.settings.match_text.unwrap_or(false) {
+            app.add_event::<KeyboardInput>();
+            add_act_matcher::<char, _>(app, &self.settings, queue_char_acts);
+        }
+
+        if self.settings.match_mash.unwrap_or(false) {
+            app.add_event::<MashProgress>();
+            register_sequence::<MashSequence>(app);
+            add_matcher(app, &self.settings, |schedule| {
+                (set_schedule(schedule), mash_matcher).chain()
+            });
+        }
+
+        app.init_resource::<PendingMatches>()
+            .add_systems(bevy::app::PostUpdate, resolve_matches);
+        app.init_resource::<PausedTime>().add_systems(
+            bevy::app::First,
+            track_paused_time.after(bevy::time::TimeSystem),
+        );
+        app.init_resource::<TagStats>();
+        app.add_event::<SequenceAborted>()
+            .add_event::<WindowAdjusted>()
+            .add_event::<SimonOutcome>()
+            .add_observer(narrow_window)
+            .add_observer(open_prerequisites)
+            .add_observer(disable_once)
+            .add_observer(count_tagged)
+            .add_systems(bevy::app::PreUpdate, close_prerequisites)
+            .add_systems(
+                bevy::app::PostUpdate,
+                (
+                    widen_windows,
+                    fail_simon_rounds,
+                    // A hit held back for its priority resolves first.
+                    expire_qtes.after(resolve_matches),
+                ),
+            );
     }
 }
 
@@ -137,21 +368,24 @@ impl InputSequencePlugin {
     /// Constructs an empty input sequence plugin with no default schedules.
     pub fn empty() -> Self {
         Self {
-            schedules: vec![],
-            match_key: None,
-            match_button: None,
+            settings: InputSequencePluginSettings {
+                schedules: vec![],
+                ..Default::default()
+            },
         }
     }
+
     /// Run the executor in a specific `Schedule`.
     pub fn run_in(mut self, schedule: impl ScheduleLabel) -> Self {
-        self.schedules
+        self.settings
+            .schedules
             .push((Interned(Box::leak(Box::new(schedule))), None));
         self
     }
 
     /// Run the executor in a specific `Schedule` and `SystemSet`.
     pub fn run_in_set(mut self, schedule: impl ScheduleLabel, set: impl SystemSet) -> Self {
-        self.schedules.push((
+        self.settings.schedules.push((
             Interned(Box::leak(Box::new(schedule))),
             Some(Interned(Box::leak(Box::new(set)))),
         ));
@@ -161,20 +395,114 @@ impl InputSequencePlugin {
     /// Run systems to match keys. By default will match keys if resource
     /// `ButtonInput<KeyCode>` exists.
     pub fn match_key(mut self, yes: bool) -> Self {
-        self.match_key = Some(yes);
+        self.settings.match_key = Some(yes);
         self
     }
 
     /// Run systems to match button. By default will match keys if resource
     /// `ButtonInput<GamepadButton>` exists.
     pub fn match_button(mut self, yes: bool) -> Self {
-        self.match_button = Some(yes);
+        self.settings.match_button = Some(yes);
+        self
+    }
+
+    /// Run systems to match mouse buttons. By default will match mouse buttons
+    /// if resource `ButtonInput<MouseButton>` exists.
+    pub fn match_mouse(mut self, yes: bool) -> Self {
+        self.settings.match_mouse = Some(yes);
+        self
+    }
+
+    /// Run systems to match sequences that mix keys, mouse buttons, and gamepad
+    /// buttons. Off by default.
+    pub fn match_mixed(mut self, yes: bool) -> Self {
+        self.settings.match_mixed = Some(yes);
+        self
+    }
+
+    /// Run systems to match touchpad gestures. Off by default.
+    pub fn match_gesture(mut self, yes: bool) -> Self {
+        self.settings.match_gesture = Some(yes);
+        self
+    }
+
+    /// Run systems to match stylus pressure. Off by default.
+    pub fn match_stylus(mut self, yes: bool) -> Self {
+        self.settings.match_stylus = Some(yes);
+        self
+    }
+
+    /// Run systems to match timed holds. Off by default.
+    pub fn match_hold(mut self, yes: bool) -> Self {
+        self.settings.match_hold = Some(yes);
+        self
+    }
+
+    /// Run systems to match typed characters. Off by default.
+    pub fn match_text(mut self, yes: bool) -> Self {
+        self.settings.match_text = Some(yes);
+        self
+    }
+
+    /// Run systems to match button mashes. Off by default.
+    pub fn match_mash(mut self, yes: bool) -> Self {
+        self.settings.match_mash = Some(yes);
+        self
+    }
+
+    /// Apply `time_limit` to sequences that do not specify their own.
+    pub fn default_time_limit(mut self, time_limit: impl Into<TimeLimit>) -> Self {
+        self.settings.default_time_limit = Some(time_limit.into());
+        self
+    }
+
+    /// Track at most `max` partial button matches, one per gamepad, dropping
+    /// the ones chosen by `policy` when over. Key sequences track a single
+    /// partial match and are unaffected.
+    pub fn max_partial_matches(mut self, max: usize, policy: EvictionPolicy) -> Self {
+        self.settings.max_partial_matches = Some(max);
+        self.settings.eviction = policy;
+        self
+    }
+
+    /// Choose which sequences fire when several complete in the same frame.
+    /// All of them fire by default.
+    pub fn priority(mut self, priority: MatchPriority) -> Self {
+        self.settings.priority = priority;
+        self
+    }
+
+    /// Hold back a key sequence that begins a longer one, e.g., `Z` while
+    /// `Z X C` may still be typed. It runs once `timeout` passes or the input
+    /// diverges from the longer sequence, and not at all if the longer one
+    /// completes.
+    pub fn suppress_prefixes(mut self, timeout: impl Into<TimeLimit>) -> Self {
+        self.settings.prefix_timeout = Some(timeout.into());
         self
     }
 }
 
+/// Add the systems `matcher` returns for each configured schedule to that
+/// schedule, in its set if it has one.
+pub(crate) fn add_matcher<M, S>(
+    app: &mut App,
+    settings: &InputSequencePluginSettings,
+    matcher: impl Fn(Interned<dyn ScheduleLabel>) -> S,
+) where
+    S: IntoSystemConfigs<M>,
+{
+    for (schedule, set) in &settings.schedules {
+        let systems = matcher(*schedule);
+        if let Some(set) = set {
+            app.add_systems(*schedule, systems.in_set(*set));
+        } else {
+            app.add_systems(*schedule, systems);
+        }
+    }
+}
+
 fn detect_key_additions(
-    sequences: Query<&InputSequence<KeyChord, ()>, Added<InputSequence<KeyChord, ()>>>,
+    sequences: Query<&InputSequence<KeyChord, ()>, Changed<InputSequence<KeyChord, ()>>>,
     mut cache: ResMut<KeySequenceCache>,
 )
 {
@@ -185,7 +513,7 @@ fn detect_key_additions(
 
 #[allow(clippy::type_complexity)]
 fn detect_button_additions(
-    sequences: Query<&InputSequence<GamepadButton, In<Entity>>, Added<InputSequence<GamepadButton, In<Entity>>>>,
+    sequences: Query<&InputSequence<ButtonAct, In<Entity>>, Changed<InputSequence<ButtonAct, In<Entity>>>>,
     mut cache: ResMut<ButtonSequenceCache>,
 )
 {
@@ -194,40 +522,246 @@ fn detect_button_additions(
     }
 }
 
+#[allow(clippy::type_complexity)]
+fn detect_focus_changes(
+    focus: Option<Res<SequenceFocus>>,
+    owners: Query<(), (Changed<FocusOwner>, With<KeySequence>)>,
+    mut removals: RemovedComponents<FocusOwner>,
+    mut cache: ResMut<KeySequenceCache>,
+) {
+    let focus_changed = focus.is_some_and(|focus| focus.is_changed());
+    if focus_changed || !owners.is_empty() || removals.read().next().is_some() {
+        cache.rebuild();
+    }
+}
+
+fn detect_key_cheat_changes(
+    cheats: Res<CheatsEnabled>,
+    added: Query<(), (Added<Cheat>, With<KeySequence>)>,
+    mut removals: RemovedComponents<Cheat>,
+    mut cache: ResMut<KeySequenceCache>,
+) {
+    if cheats.is_changed() || !added.is_empty() || removals.read().next().is_some() {
+        cache.rebuild();
+    }
+}
+
+fn detect_button_cheat_changes(
+    cheats: Res<CheatsEnabled>,
+    added: Query<(), (Added<Cheat>, With<ButtonSequence>)>,
+    mut removals: RemovedComponents<Cheat>,
+    mut cache: ResMut<ButtonSequenceCache>,
+) {
+    if cheats.is_changed() || !added.is_empty() || removals.read().next().is_some() {
+        cache.reset();
+    }
+}
+
+fn detect_key_namespace_changes(
+    namespaces: Res<Namespaces>,
+    added: Query<(), (Added<Namespace>, With<KeySequence>)>,
+    mut removals: RemovedComponents<Namespace>,
+    mut cache: ResMut<KeySequenceCache>,
+) {
+    if namespaces.is_changed() || !added.is_empty() || removals.read().next().is_some() {
+        cache.rebuild();
+    }
+}
+
+fn detect_button_namespace_changes(
+    namespaces: Res<Namespaces>,
+    added: Query<(), (Added<Namespace>, With<ButtonSequence>)>,
+    mut removals: RemovedComponents<Namespace>,
+    mut cache: ResMut<ButtonSequenceCache>,
+) {
+    if namespaces.is_changed() || !added.is_empty() || removals.read().next().is_some() {
+        cache.reset();
+    }
+}
+
 fn detect_key_removals(
     mut cache: ResMut<KeySequenceCache>,
     mut removals: RemovedComponents<InputSequence<KeyChord, ()>>,
+    mut rolling_removals: RemovedComponents<Rolling>,
+    mut tolerance_removals: RemovedComponents<Tolerance>,
 ) {
-    if removals.read().next().is_some() {
+    if removals.read().count()
+        + rolling_removals.read().count()
+        + tolerance_removals.read().count()
+        > 0
+    {
         cache.reset();
     }
 }
 
 fn detect_button_removals(
     mut cache: ResMut<ButtonSequenceCache>,
-    mut removals: RemovedComponents<InputSequence<GamepadButton, In<Entity>>>,
+    mut removals: RemovedComponents<InputSequence<ButtonAct, In<Entity>>>,
 ) {
     if removals.read().next().is_some() {
         cache.reset();
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn button_sequence_matcher(
-    sequences: Query<&ButtonSequence>,
+    sequences: Query<(
+        Entity,
+        &ButtonSequence,
+        Has<Cheat>,
+        Option<&Namespace>,
+        Option<&MatchSchedule>,
+    )>,
+    cheats: Res<CheatsEnabled>,
+    namespaces: Res<Namespaces>,
     time: Res<Time>,
+    real_time: Res<Time<Real>>,
     mut last_times: Local<HashMap<Entity, VecDeque<FrameTime>>>,
     mut cache: ResMut<ButtonSequenceCache>,
-    frame_count: Res<FrameCount>,
+    (frame_count, paused): (Res<FrameCount>, Res<PausedTime>),
     mut commands: Commands,
-    gamepads: Query<(Entity, &Gamepad)>,
+    gamepads: Query<(Entity, &Gamepad, Option<&GamepadProfile>)>,
+    guard: Res<InputSequenceGuard>,
+    settings: Res<InputSequencePluginSettings>,
+    (calibration, tap_hold, stick_calibration): (
+        Res<TriggerCalibration>,
+        Res<TapHoldCalibration>,
+        Res<StickCalibration>,
+    ),
+    (mut pulls, mut held_since, mut directions): (
+        Local<HashMap<(Entity, GamepadButton), PullLevel>>,
+        Local<HashMap<(Entity, GamepadButton), (Duration, bool)>>,
+        Local<HashMap<(Entity, Stick), Direction>>,
+    ),
+    (duplicates, gated, mut aborted, stick_as_dpad, current): (
+        Res<DuplicatePresses>,
+        Res<GatedGamepads>,
+        EventWriter<SequenceAborted>,
+        Query<(), With<StickAsDpad>>,
+        Res<CurrentSchedule>,
+    ),
 ) {
-    let now = FrameTime {
-        frame: frame_count.0,
-        time: time.elapsed_secs(),
-    };
-    for (id, gamepad) in &gamepads {
+    if guard.is_suppressed() {
+        last_times.clear();
+        cache.forget();
+        return;
+    }
+    let now = FrameTime::now(&frame_count, &time, &real_time, &paused);
+    let cheats = cheats.0;
+    cache.trie_with_stick_as_dpad(
+        sequences
+            .iter()
+            .filter(|(_, _, cheat, ns, schedule)| {
+                (!cheat || cheats) && namespaces.allows(*ns) && current.accepts(*schedule)
+            })
+            .map(|(id, seq, ..)| (seq, stick_as_dpad.contains(id))),
+    );
+    let pulled: HashSet<GamepadButton> = cache
+        .acts()
+        .filter_map(|act| match act {
+            ButtonAct::SoftPull(button) | ButtonAct::HardPull(button) => Some(*button),
+            _ => None,
+        })
+        .collect();
+    let timed: HashSet<GamepadButton> = cache
+        .acts()
+        .filter_map(|act| match act {
+            ButtonAct::Tap(button) | ButtonAct::Hold(button) => Some(*button),
+            _ => None,
+        })
+        .collect();
+    let sticks: HashSet<Stick> = cache
+        .acts()
+        .filter_map(|act| match act {
+            ButtonAct::Stick(stick, _) => Some(*stick),
+            _ => None,
+        })
+        .collect();
+    for (id, gamepad, profile) in &gamepads {
+        if gated.contains(&id) {
+            last_times.remove(&id);
+            cache.forget_key(id);
+            held_since.retain(|(gamepad, _), _| *gamepad != id);
+            directions.retain(|(gamepad, _), _| *gamepad != id);
+            continue;
+        }
+        let translate = |button: &GamepadButton| {
+            profile.map(|p| p.translate(*button)).unwrap_or(*button)
+        };
+        let mut input: Vec<ButtonAct> = gamepad
+            .get_just_pressed()
+            .filter(|button| !duplicates.buttons.contains(&(id, **button)))
+            .map(|button| ButtonAct::Press(translate(button)))
+            .collect();
+        for button in &pulled {
+            let value = gamepad.get(*button).unwrap_or(0.0);
+            let level = pulls.entry((id, *button)).or_default();
+            let new = calibration.level(value, *level);
+            if *level < PullLevel::Soft && new >= PullLevel::Soft {
+                input.push(ButtonAct::SoftPull(*button));
+            }
+            if *level < PullLevel::Hard && new == PullLevel::Hard {
+                input.push(ButtonAct::HardPull(*button));
+            }
+            *level = new;
+        }
+        for stick in &sticks {
+            let value = match stick {
+                Stick::Left => gamepad.left_stick(),
+                Stick::Right => gamepad.right_stick(),
+            };
+            let previous = directions.get(&(id, *stick)).copied();
+            match stick_calibration.direction(value, previous) {
+                Some(direction) => {
+                    if previous != Some(direction) {
+                        input.push(ButtonAct::Stick(*stick, direction));
+                    }
+                    directions.insert((id, *stick), direction);
+                }
+                None => {
+                    directions.remove(&(id, *stick));
+                }
+            }
+        }
         for button in gamepad.get_just_pressed() {
+            held_since.insert((id, *button), (time.elapsed(), false));
+        }
+        for button in gamepad.get_pressed() {
+            if let Some((since, held)) = held_since.get_mut(&(id, *button)) {
+                if !*held && time.elapsed() - *since >= tap_hold.hold {
+                    *held = true;
+                    input.push(ButtonAct::Hold(translate(button)));
+                }
+            }
+        }
+        for button in gamepad.get_just_released() {
+            input.push(ButtonAct::Release(translate(button)));
+            if let Some((since, _)) = held_since.remove(&(id, *button)) {
+                if time.elapsed() - since <= tap_hold.tap {
+                    input.push(ButtonAct::Tap(translate(button)));
+                }
+            }
+        }
+        // A press counts as its group's act if some sequence uses the group
+        // and none uses the press itself.
+        for act in &mut input {
+            if let ButtonAct::Press(button) = *act {
+                if let Some(group) = ButtonGroup::of(button) {
+                    if !cache.uses(act) && cache.uses(&ButtonAct::Any(group)) {
+                        *act = ButtonAct::Any(group);
+                    }
+                }
+            }
+        }
+        // Presses always count, except of buttons being pulled, tapped, or
+        // held. Other acts only count if some sequence uses them, so they
+        // don't interrupt sequences that don't mention them.
+        input.retain(|act| {
+            cache.uses(act)
+                || matches!(act, ButtonAct::Press(button)
+                    if !pulled.contains(button) && !timed.contains(button))
+        });
+        for act in input {
             let last_times = match last_times.get_mut(&id) {
                 Some(x) => x,
                 None => {
@@ -238,87 +772,637 @@ fn button_sequence_matcher(
 
             last_times.push_back(now.clone());
             let start = &last_times[0];
-            let mut search = cache.recall(id, sequences.iter().by_ref());
-            for seq in inc_consume_input(&mut search, std::iter::once(*button)) {
+            let mut search = cache.recall(
+                id,
+                sequences
+                    .iter()
+                    .filter(|(_, _, cheat, ns, schedule)| {
+                        (!cheat || cheats) && namespaces.allows(*ns) && current.accepts(*schedule)
+                    })
+                    .map(|(_, seq, ..)| seq),
+            );
+            let before: Vec<ButtonAct> = search.prefix();
+            let mut ended = false;
+            for seq in inc_consume_input(&mut search, std::iter::once(act)) {
+                ended = true;
                 if seq
                     .time_limit
                     .as_ref()
+                    .or(settings.default_time_limit.as_ref())
                     .map(|limit| (&now - start).has_timedout(limit))
                     .unwrap_or(false)
                 {
                     // Sequence timed out.
+                    send_aborts(
+                        &mut aborted,
+                        sequences
+                            .iter()
+                            .filter(|(_, s, ..)| s.acts == seq.acts)
+                            .map(|(entity, ..)| entity),
+                        seq.acts.len() - 1,
+                        AbortReason::TimedOut,
+                    );
                 } else {
-                    commands.run_system_with_input(seq.system_id, id);
+                    run_matched(&mut commands, seq.system_id, id, Some(id), (&now - start).duration(), seq.acts.len());
                 }
             }
+            let after: Vec<ButtonAct> = search.prefix();
+            if !ended && !before.is_empty() && !after.starts_with(&before) {
+                send_aborts(
+                    &mut aborted,
+                    sequences
+                        .iter()
+                        .filter(|(_, s, cheat, ns, schedule)| {
+                            (!cheat || cheats)
+                                && namespaces.allows(*ns)
+                                && current.accepts(*schedule)
+                                && s.acts.len() > before.len()
+                                && s.acts.starts_with(&before)
+                        })
+                        .map(|(entity, ..)| entity),
+                    before.len(),
+                    AbortReason::WrongInput,
+                );
+            }
             let prefix_len = search.prefix_len();
             let l = last_times.len();
             let _ = last_times.drain(0..l - prefix_len);
             let position = search.into();
-            cache.store(id, position);
+            cache.store_progress(id, position, prefix_len);
+        }
+    }
+    if let Some(max) = settings.max_partial_matches {
+        let evicted = cache.evict(max, settings.eviction);
+        if !evicted.is_empty() {
+            warn!(
+                "Evicted {} partial button matches over the limit of {max}.",
+                evicted.len()
+            );
+            for id in evicted {
+                last_times.remove(&id);
+            }
         }
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn key_sequence_matcher(
-    sequences: Query<&KeySequence>,
+/// A consumed input and the inputs held when it was pressed.
+#[derive(Clone, Debug)]
+struct InputRecord<K> {
+    time: FrameTime,
+    held: Vec<K>,
+}
+
+/// Check that a matched sequence satisfies its time limit and press
+/// requirements. The last `seq.acts.len()` records are the inputs that matched
+/// it.
+fn check_satisfied<Act, I, K>(
+    seq: &InputSequence<Act, I>,
+    records: &VecDeque<InputRecord<K>>,
+    now: &FrameTime,
+    default_time_limit: Option<&TimeLimit>,
+    key: impl Fn(&Act) -> K,
+) -> Result<(), AbortReason>
+where
+    I: SystemInput,
+    K: PartialEq,
+{
+    let n = seq.acts.len();
+    let Some(records) = records.len().checked_sub(n).map(|i| records.range(i..)) else {
+        return Err(AbortReason::WrongInput);
+    };
+    let records: Vec<_> = records.collect();
+    if let Some(start) = records.first() {
+        if seq
+            .time_limit
+            .as_ref()
+            .or(default_time_limit)
+            .map(|limit| (now - &start.time).has_timedout(limit))
+            .unwrap_or(false)
+        {
+            // Sequence timed out.
+            return Err(AbortReason::TimedOut);
+        }
+    }
+    if (1..n).any(|i| {
+        seq.interval(i)
+            .is_some_and(|max| (&records[i].time - &records[i - 1].time).duration() > max)
+    }) {
+        return Err(AbortReason::TimedOut);
+    }
+    if (0..n.saturating_sub(1)).all(|i| match seq.press(i) {
+        Press::HeldUntilNext => records[i + 1].held.contains(&key(&seq.acts[i])),
+        Press::HeldFor(duration) => {
+            records[i + 1].held.contains(&key(&seq.acts[i]))
+                && (&records[i + 1].time - &records[i].time).duration() >= duration
+        }
+        Press::JustPressed | Press::Pressed => true,
+    }) {
+        Ok(())
+    } else {
+        Err(AbortReason::WrongInput)
+    }
+}
+
+/// Return true if a sequence under `prefix` accepts an already held input for
+/// its last step.
+fn accepts_held<Act, I>(trie: &Trie<Act, InputSequence<Act, I>>, prefix: &[Act]) -> bool
+where
+    Act: Ord + Clone,
+    I: SystemInput,
+{
+    let step = prefix.len() - 1;
+    trie.predictive_search::<Vec<Act>, _>(prefix)
+        .any(|(_, seq)| seq.press(step) == Press::Pressed)
+}
+
+/// Key input shared by the key matchers: the chords queued in
+/// [KeyChordQueue] followed by the keys just pressed with the modifiers held.
+#[derive(SystemParam)]
+struct KeyInput<'w> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    duplicates: Res<'w, DuplicatePresses>,
+    keychord_queue: ResMut<'w, KeyChordQueue>,
+    custom_modifiers: Option<Res<'w, CustomModifiers>>,
+    guard: Res<'w, InputSequenceGuard>,
+}
+
+impl KeyInput<'_> {
+    fn is_suppressed(&self) -> bool {
+        self.guard.is_suppressed()
+    }
+
+    /// Return the modifiers held, including custom ones.
+    fn mods(&self) -> Modifiers {
+        let mut mods = Modifiers::from(&self.keys);
+        if let Some(custom_modifiers) = &self.custom_modifiers {
+            mods |= custom_modifiers.held();
+        }
+        mods
+    }
+
+    /// Return the chords entered this update, each with its key if it was
+    /// pressed rather than queued.
+    fn chords(&self) -> impl Iterator<Item = (Option<KeyCode>, KeyChord)> + '_ {
+        let mods = self.mods();
+        self.keychord_queue
+            .iter()
+            .map(|chord| (None, chord.clone()))
+            .chain(
+                self.keys
+                    .get_just_pressed()
+                    .filter(|k| !is_modifier(**k) && !self.duplicates.keys.contains(*k))
+                    .map(move |k| (Some(*k), KeyChord(mods, *k))),
+            )
+    }
+
+    /// Return the chords entered this update, leaving the queue for the key
+    /// sequence matcher.
+    fn peek(&self) -> Vec<KeyChord> {
+        self.chords().map(|(_, chord)| chord).collect()
+    }
+
+    /// Return the chords entered this update and empty the queue.
+    fn drain(&mut self) -> Vec<(Option<KeyCode>, KeyChord)> {
+        let chords = self.chords().collect();
+        self.keychord_queue.clear();
+        chords
+    }
+}
+
+/// Removals that invalidate the [RollingWindow] of hashed and rolling
+/// sequences.
+#[derive(SystemParam)]
+struct RollingRemovals<'w, 's> {
+    hashed: RemovedComponents<'w, 's, HashedKeySequence>,
+    keys: RemovedComponents<'w, 's, KeySequence>,
+    rolling: RemovedComponents<'w, 's, Rolling>,
+    cheats: RemovedComponents<'w, 's, Cheat>,
+    namespaces: RemovedComponents<'w, 's, Namespace>,
+}
+
+impl RollingRemovals<'_, '_> {
+    /// Return true if anything was removed since the last call.
+    fn any(&mut self) -> bool {
+        self.hashed.read().count()
+            + self.keys.read().count()
+            + self.rolling.read().count()
+            + self.cheats.read().count()
+            + self.namespaces.read().count()
+            > 0
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn hashed_key_sequence_matcher(
+    sequences: Query<(Ref<HashedKeySequence>, Has<Cheat>, Option<&Namespace>, Option<&MatchSchedule>)>,
+    rolling: Query<
+        (Ref<KeySequence>, Has<Cheat>, Option<&Namespace>, Option<&MatchSchedule>),
+        With<Rolling>,
+    >,
+    (cheats, namespaces, current): (Res<CheatsEnabled>, Res<Namespaces>, Res<CurrentSchedule>),
     time: Res<Time>,
-    keys: Res<ButtonInput<KeyCode>>,
-    mut last_times: Local<VecDeque<FrameTime>>,
-    mut cache: ResMut<KeySequenceCache>,
-    frame_count: Res<FrameCount>,
+    real_time: Res<Time<Real>>,
+    input: KeyInput,
+    mut window: Local<RollingWindow>,
+    (frame_count, paused): (Res<FrameCount>, Res<PausedTime>),
     mut commands: Commands,
-    mut keychord_queue: ResMut<KeyChordQueue>,
+    settings: Res<InputSequencePluginSettings>,
+    added_cheats: Query<(), (Added<Cheat>, Or<(With<HashedKeySequence>, With<Rolling>)>)>,
+    mut removals: RollingRemovals,
 ) {
-    let mods = Modifiers::from(&keys);
-    let now = FrameTime {
-        frame: frame_count.0,
-        time: time.elapsed_secs(),
-    };
-    let maybe_start = last_times.front().cloned();
-    let mut input = keychord_queue
-        .drain(..)
-        .chain(
-            keys.get_just_pressed()
-                .filter(|k| !is_modifier(**k))
-                .map(|k| {
-                    let chord = KeyChord(mods, *k);
-                    last_times.push_back(now.clone());
-                    chord
-                }),
-        )
-        .peekable();
-    if input.peek().is_none() {
+    if input.is_suppressed() {
+        window.clear();
         return;
     }
+    if removals.any()
+        || cheats.is_changed()
+        || namespaces.is_changed()
+        || !added_cheats.is_empty()
+        || sequences.iter().any(|(seq, ..)| seq.is_added())
+        || rolling.iter().any(|(seq, ..)| seq.is_added())
+    {
+        let default_time_limit = &settings.default_time_limit;
+        let hashed = sequences
+            .iter()
+            .filter(|(_, cheat, ns, schedule)| {
+                (!cheat || cheats.0) && namespaces.allows(*ns) && current.accepts(*schedule)
+            })
+            .map(|(seq, ..)| RollingEntry {
+                system_id: seq.system_id,
+                digest: seq.digest,
+                time_limit: seq.time_limit.clone().or(default_time_limit.clone()),
+            });
+        let plain = rolling
+            .iter()
+            .filter(|(_, cheat, ns, schedule)| {
+                (!cheat || cheats.0) && namespaces.allows(*ns) && current.accepts(*schedule)
+            })
+            .map(|(seq, ..)| RollingEntry {
+                system_id: seq.system_id,
+                digest: SequenceDigest::new(&seq.acts, 0),
+                time_limit: seq.time_limit.clone().or(default_time_limit.clone()),
+            });
+        window.set_entries(hashed.chain(plain));
+    }
+    if window.is_empty() {
+        return;
+    }
+    let now = FrameTime::now(&frame_count, &time, &real_time, &paused);
+    let input = input.peek();
+    for chord in input {
+        for (entry, duration) in window.push(&chord, &now) {
+            run_matched(&mut commands, entry.system_id, (), None, duration, entry.digest.len);
+        }
+    }
+}
 
-    let mut search = cache.recall(sequences.iter());
-
-    // eprintln!("maybe_start {maybe_start:?} now {now:?}");
-    for seq in inc_consume_input(&mut search, input) {
-        if let Some(ref start) = maybe_start {
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn pattern_sequence_matcher(
+    sequences: Query<(&PatternSequence, Has<Cheat>, Option<&Namespace>, Option<&MatchSchedule>)>,
+    (cheats, namespaces, current): (Res<CheatsEnabled>, Res<Namespaces>, Res<CurrentSchedule>),
+    (time, real_time, frame_count, paused): (
+        Res<Time>,
+        Res<Time<Real>>,
+        Res<FrameCount>,
+        Res<PausedTime>,
+    ),
+    input: KeyInput,
+    mut recent: Local<VecDeque<(KeyChord, FrameTime)>>,
+    mut commands: Commands,
+    settings: Res<InputSequencePluginSettings>,
+) {
+    if input.is_suppressed() || sequences.is_empty() {
+        recent.clear();
+        return;
+    }
+    let now = FrameTime::now(&frame_count, &time, &real_time, &paused);
+    let input = input.peek();
+    let max_len = sequences
+        .iter()
+        .map(|(seq, ..)| seq.patterns.len())
+        .max()
+        .unwrap_or(0);
+    for chord in input {
+        recent.push_back((chord, now.clone()));
+        while recent.len() > max_len {
+            recent.pop_front();
+        }
+        let chords: Vec<KeyChord> = recent.iter().map(|(chord, _)| chord.clone()).collect();
+        let mut matched = false;
+        for (seq, ..) in sequences.iter().filter(|(_, cheat, ns, schedule)| {
+            (!cheat || cheats.0) && namespaces.allows(*ns) && current.accepts(*schedule)
+        }) {
+            if !seq.matches(&chords) {
+                continue;
+            }
+            let (_, start) = &recent[recent.len() - seq.patterns.len()];
+            let elapsed = &now - start;
             if seq
                 .time_limit
                 .as_ref()
-                .map(|limit| (&now - start).has_timedout(limit))
-                .unwrap_or(false)
+                .or(settings.default_time_limit.as_ref())
+                .is_some_and(|limit| elapsed.has_timedout(limit))
             {
-                // Sequence timed out.
                 continue;
             }
+            run_matched(&mut commands, seq.system_id, (), None, elapsed.duration(), seq.patterns.len());
+            matched = true;
+        }
+        if matched {
+            recent.clear();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn tolerant_key_sequence_matcher(
+    sequences: Query<(
+        Entity,
+        &KeySequence,
+        &Tolerance,
+        Has<Cheat>,
+        Option<&Namespace>,
+        Option<&MatchSchedule>,
+    )>,
+    (cheats, namespaces, current_schedule): (
+        Res<CheatsEnabled>,
+        Res<Namespaces>,
+        Res<CurrentSchedule>,
+    ),
+    (time, real_time, frame_count, paused): (
+        Res<Time>,
+        Res<Time<Real>>,
+        Res<FrameCount>,
+        Res<PausedTime>,
+    ),
+    input: KeyInput,
+    mut progress: Local<HashMap<Entity, Progress>>,
+    mut commands: Commands,
+    settings: Res<InputSequencePluginSettings>,
+) {
+    if input.is_suppressed() || sequences.is_empty() {
+        progress.clear();
+        return;
+    }
+    let now = FrameTime::now(&frame_count, &time, &real_time, &paused);
+    let input = input.peek();
+    progress.retain(|id, _| sequences.contains(*id));
+    if input.is_empty() {
+        return;
+    }
+    let default_time_limit = settings.default_time_limit.as_ref();
+    for (id, seq, tolerance, cheat, ns, schedule) in &sequences {
+        if !((!cheat || cheats.0) && namespaces.allows(ns) && current_schedule.accepts(schedule)) {
+            progress.remove(&id);
+            continue;
+        }
+        let mut current = progress.remove(&id);
+        for chord in &input {
+            if let Some(time_limit) = seq.time_limit.as_ref().or(default_time_limit) {
+                if current
+                    .as_ref()
+                    .is_some_and(|p| (&now - &p.start).has_timedout(time_limit))
+                {
+                    current = None;
+                }
+            }
+            if Progress::advance(&mut current, &seq.acts, *tolerance, chord, &now) {
+                let p = current.take().unwrap();
+                run_matched_with_mistakes(
+                    &mut commands,
+                    seq.system_id,
+                    (),
+                    None,
+                    (&now - &p.start).duration(),
+                    seq.acts.len(),
+                    p.mistakes,
+                );
+            }
+        }
+        if let Some(current) = current {
+            progress.insert(id, current);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn key_sequence_matcher(
+    sequences: Query<
+        (
+            Entity,
+            &KeySequence,
+            Option<&FocusOwner>,
+            Has<Cheat>,
+            Option<&Namespace>,
+            Option<&MatchSchedule>,
+        ),
+        (Without<Rolling>, Without<Tolerance>),
+    >,
+    cheats: Res<CheatsEnabled>,
+    namespaces: Res<Namespaces>,
+    focus: Option<Res<SequenceFocus>>,
+    (time, real_time, frame_count, paused): (
+        Res<Time>,
+        Res<Time<Real>>,
+        Res<FrameCount>,
+        Res<PausedTime>,
+    ),
+    mut input: KeyInput,
+    mut records: Local<VecDeque<InputRecord<KeyCode>>>,
+    mut used: Local<HashSet<KeyCode>>,
+    mut cache: ResMut<KeySequenceCache>,
+    mut commands: Commands,
+    (settings, mut aborted, mut deferred, current): (
+        Res<InputSequencePluginSettings>,
+        EventWriter<SequenceAborted>,
+        Local<Option<DeferredMatch>>,
+        Res<CurrentSchedule>,
+    ),
+) {
+    if input.is_suppressed() {
+        input.drain();
+        records.clear();
+        cache.forget();
+        *deferred = None;
+        return;
+    }
+    let now = FrameTime::now(&frame_count, &time, &real_time, &paused);
+    if let Some(timeout) = &settings.prefix_timeout {
+        if deferred
+            .as_ref()
+            .is_some_and(|d| (&now - &d.matched_at).has_timedout(timeout))
+        {
+            deferred.take().unwrap().run(&mut commands);
+        }
+    }
+    // Keys that advanced the current match may not satisfy another step while
+    // held.
+    used.retain(|key| input.keys.pressed(*key));
+    let held: Vec<KeyCode> = input
+        .keys
+        .get_pressed()
+        .filter(|k| !is_modifier(**k))
+        .copied()
+        .collect();
+    let mods = input.mods();
+    let input = input.drain();
+    if input.is_empty() && held.iter().all(|k| used.contains(k)) {
+        return;
+    }
+
+    let focus = focus.and_then(|focus| focus.0);
+    // Insert focused sequences last so they take precedence over global ones.
+    let eligible: Vec<(Entity, &KeySequence)> = sequences
+        .iter()
+        .filter(|(_, _, owner, _, _, _)| owner.is_none())
+        .chain(
+            sequences
+                .iter()
+                .filter(|(_, _, owner, _, _, _)| owner.is_some_and(|owner| Some(owner.0) == focus)),
+        )
+        .filter(|(_, _, _, cheat, ns, schedule)| {
+            (!cheat || cheats.0) && namespaces.allows(*ns) && current.accepts(*schedule)
+        })
+        .map(|(id, seq, ..)| (id, seq))
+        .collect();
+    let (trie, position) = cache.trie_and_position(eligible.iter().map(|(_, seq)| *seq));
+    let mut search = position
+        .map(|p| IncSearch::resume(trie, p))
+        .unwrap_or_else(|| trie.inc_search());
+
+    let default_time_limit = settings.default_time_limit.as_ref();
+    let mut consume = |search: &mut IncSearch<'_, KeyChord, KeySequence>,
+                       used: &mut HashSet<KeyCode>,
+                       key: Option<KeyCode>,
+                       chord: KeyChord| {
+        records.push_back(InputRecord {
+            time: now.clone(),
+            held: held.clone(),
+        });
+        let before: Vec<KeyChord> = search.prefix();
+        let mut ended = false;
+        let mut completed = Vec::new();
+        for seq in inc_consume_input(search, std::iter::once(chord)) {
+            ended = true;
+            match check_satisfied(seq, &records, &now, default_time_limit, |chord| chord.1) {
+                Ok(()) => {
+                    let start = &records[records.len() - seq.acts.len()].time;
+                    completed.push(DeferredMatch {
+                        system_id: seq.system_id,
+                        acts: seq.acts.clone(),
+                        duration: (&now - start).duration(),
+                        matched_at: now.clone(),
+                    });
+                }
+                Err(reason) => send_aborts(
+                    &mut aborted,
+                    eligible
+                        .iter()
+                        .filter(|(_, s)| s.acts == seq.acts)
+                        .map(|(id, _)| *id),
+                    seq.acts.len() - 1,
+                    reason,
+                ),
+            }
+        }
+        let after: Vec<KeyChord> = search.prefix();
+        // Settle a match held back for a longer sequence: drop it if the
+        // longer one completed, keep holding it while the longer one may
+        // still complete, and run it otherwise.
+        if let Some(held_back) = deferred.take() {
+            let begins = |acts: &[KeyChord]| {
+                acts.len() > held_back.acts.len() && acts.starts_with(&held_back.acts)
+            };
+            if begins(&after) {
+                *deferred = Some(held_back);
+            } else if !completed.iter().any(|m| begins(&m.acts)) {
+                held_back.run(&mut commands);
+            }
+        }
+        for matched in completed {
+            // A match the search is still at begins a longer sequence.
+            if settings.prefix_timeout.is_some() && after == matched.acts {
+                *deferred = Some(matched);
+            } else {
+                matched.run(&mut commands);
+            }
+        }
+        if !ended && !before.is_empty() && !after.starts_with(&before) {
+            send_aborts(
+                &mut aborted,
+                eligible
+                    .iter()
+                    .filter(|(_, s)| s.acts.len() > before.len() && s.acts.starts_with(&before))
+                    .map(|(id, _)| *id),
+                before.len(),
+                AbortReason::WrongInput,
+            );
+        }
+        if search.prefix_len() == 0 {
+            used.clear();
+        } else if let Some(key) = key {
+            used.insert(key);
+        }
+    };
+    for (key, chord) in input {
+        consume(&mut search, &mut used, key, chord);
+    }
+    // Let held keys satisfy steps that accept an already pressed key.
+    for &key in &held {
+        if used.contains(&key) {
+            continue;
+        }
+        let chord = KeyChord(mods, key);
+        if search.peek(&chord).is_none() {
+            continue;
+        }
+        let mut prefix: Vec<KeyChord> = search.prefix();
+        prefix.push(chord.clone());
+        if accepts_held(trie, &prefix) {
+            consume(&mut search, &mut used, Some(key), chord);
         }
-        commands.run_system(seq.system_id);
     }
     let prefix_len = search.prefix_len();
-    let l = last_times.len();
-    let _ = last_times.drain(0..l.saturating_sub(prefix_len));
+    let l = records.len();
+    let _ = records.drain(0..l.saturating_sub(prefix_len));
     let position = search.into();
     cache.store(position);
 }
 
+/// A key sequence match held back while a longer sequence it begins may
+/// still match.
+struct DeferredMatch {
+    system_id: SystemId,
+    acts: Vec<KeyChord>,
+    duration: Duration,
+    matched_at: FrameTime,
+}
+
+impl DeferredMatch {
+    fn run(self, commands: &mut Commands) {
+        run_matched(commands, self.system_id, (), None, self.duration, self.acts.len());
+    }
+}
+
+/// Send [SequenceAborted] for the entity of each of `sequences` after `matched_len` of their
+/// acts had matched.
+fn send_aborts(
+    writer: &mut EventWriter<SequenceAborted>,
+    sequences: impl Iterator<Item = Entity>,
+    matched_len: usize,
+    reason: AbortReason,
+) {
+    for entity in sequences {
+        writer.send(SequenceAborted {
+            entity,
+            matched_len,
+            reason,
+        });
+    }
+}
+
 /// Incrementally consume the input.
-fn inc_consume_input<'a, 'b, K, V>(
+pub(crate) fn inc_consume_input<'a, 'b, K, V>(
     search: &'b mut IncSearch<'a, K, V>,
     input: impl Iterator<Item = K> + 'b,
 ) -> impl Iterator<Item = &'a V> + 'b
@@ -0,0 +1,94 @@
+//! Only match a sequence for a while after another one matched
+//!
+//! Give sequence B a [Prerequisite] on sequence A with the builder's
+//! [requires](crate::input_sequence::InputSequenceBuilder::requires) option
+//! and B only matches within the given time after A matched, e.g., for
+//! multi-stage secret codes or combo branches. The plugin keeps B
+//! [Disabled] the rest of the time, so don't insert or remove [Disabled] on B
+//! yourself.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::prelude::*;
+//! use std::time::Duration;
+//!
+//! fn setup(mut commands: Commands) {
+//!     let stage1 = commands.spawn_empty().id();
+//!     commands.entity(stage1).queue(
+//!         KeySequence::new(|| info!("stage 1"), keyseq! { I D }));
+//!     commands.spawn_empty().queue(
+//!         KeySequence::new(|| info!("stage 2"), keyseq! { K F A })
+//!             .requires(stage1, Duration::from_secs(5)));
+//! }
+//! ```
+use crate::{disabled::Disabled, matched::SequenceComplete};
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        observer::Trigger,
+        query::Has,
+        system::{Commands, Query, Res},
+    },
+    time::Time,
+};
+use std::time::Duration;
+
+/// Only match the sequence on this entity within `within` after the sequence
+/// on `after` matched.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+#[require(Disabled)]
+pub struct Prerequisite {
+    /// Entity of the sequence that must match first.
+    pub after: Entity,
+    /// How long after that match this sequence may match.
+    pub within: Duration,
+    open_until: Option<Duration>,
+}
+
+impl Prerequisite {
+    /// Require the sequence on `after` to have matched no longer than
+    /// `within` ago.
+    pub fn new(after: Entity, within: Duration) -> Self {
+        Self {
+            after,
+            within,
+            open_until: None,
+        }
+    }
+}
+
+/// Enable the sequences whose prerequisite just matched.
+pub(crate) fn open_prerequisites(
+    trigger: Trigger<SequenceComplete>,
+    mut sequences: Query<(Entity, &mut Prerequisite, Has<Disabled>)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (id, mut prerequisite, disabled) in &mut sequences {
+        if prerequisite.after != trigger.entity() {
+            continue;
+        }
+        prerequisite.open_until = Some(time.elapsed() + prerequisite.within);
+        if disabled {
+            commands.entity(id).remove::<Disabled>();
+        }
+    }
+}
+
+/// Disable the sequences whose prerequisite matched too long ago.
+pub(crate) fn close_prerequisites(
+    mut sequences: Query<(Entity, &mut Prerequisite)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (id, mut prerequisite) in &mut sequences {
+        if prerequisite
+            .open_until
+            .is_some_and(|until| time.elapsed() > until)
+        {
+            prerequisite.open_until = None;
+            commands.entity(id).insert(Disabled);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Report each step of a key sequence as it matches
+//!
+//! Mark a key sequence with the builder's
+//! [report_progress](crate::input_sequence::InputSequenceBuilder::report_progress)
+//! option and a [SequenceProgress] event is sent whenever the partial match
+//! grows along it, e.g., to fill a combo bar. Completion is reported by the
+//! sequence's own system, not by a progress event.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, progress::SequenceProgress};
+//! use std::time::Duration;
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn_empty().queue(
+//!         KeySequence::new(|| info!("hadouken"), keyseq! { S D F })
+//!             .time_limit(Duration::from_secs(1))
+//!             .report_progress());
+//! }
+//!
+//! fn combo_bar(mut reader: EventReader<SequenceProgress>) {
+//!     for progress in reader.read() {
+//!         info!("{}/{} with {:?} left", progress.matched, progress.total, progress.remaining);
+//!     }
+//! }
+//! ```
+use crate::{
+    cache::KeySequenceCache, frame_time::PausedTime, input_sequence::KeySequence,
+    InputSequencePluginSettings, KeyChord, TimeLimit,
+};
+use bevy::{
+    core::FrameCount,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        query::With,
+        system::{Local, Query, Res},
+    },
+    time::{Real, Time},
+};
+use std::time::Duration;
+
+/// Mark a key sequence whose steps send [SequenceProgress].
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReportProgress;
+
+/// Sent when a step of a [ReportProgress] sequence matches.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct SequenceProgress {
+    /// Entity the sequence is on.
+    pub entity: Entity,
+    /// Acts matched so far.
+    pub matched: usize,
+    /// Acts in the sequence.
+    pub total: usize,
+    /// Time left before the partial match times out, if the sequence has a
+    /// time limit.
+    pub remaining: Option<TimeLimit>,
+}
+
+/// Send progress events when the partial match changes.
+#[allow(clippy::type_complexity)]
+pub(crate) fn report_key_progress(
+    sequences: Query<(Entity, &KeySequence), With<ReportProgress>>,
+    cache: Res<KeySequenceCache>,
+    settings: Res<InputSequencePluginSettings>,
+    (time, real_time, frame_count, paused): (
+        Res<Time>,
+        Res<Time<Real>>,
+        Res<FrameCount>,
+        Res<PausedTime>,
+    ),
+    mut last: Local<(Vec<KeyChord>, Option<(u32, Duration, Duration)>)>,
+    mut writer: EventWriter<SequenceProgress>,
+) {
+    let prefix = cache.prefix();
+    let (last_prefix, start) = &mut *last;
+    if prefix == *last_prefix {
+        return;
+    }
+    let now = (
+        frame_count.0.wrapping_sub(paused.frames),
+        time.elapsed(),
+        real_time.elapsed().saturating_sub(paused.real),
+    );
+    if prefix.len() <= 1 || !prefix.starts_with(last_prefix) {
+        *start = Some(now);
+    }
+    *last_prefix = prefix;
+    if last_prefix.is_empty() {
+        return;
+    }
+    let (frame, elapsed, real) = start.unwrap_or(now);
+    let remaining = |limit: &TimeLimit| match limit {
+        TimeLimit::Frames(f) => TimeLimit::Frames(f.saturating_sub(now.0 - frame)),
+        TimeLimit::Duration(d) => TimeLimit::Duration(d.saturating_sub(now.1 - elapsed)),
+        TimeLimit::RealDuration(d) => TimeLimit::RealDuration(d.saturating_sub(now.2 - real)),
+    };
+    for (entity, sequence) in &sequences {
+        if sequence.acts.len() > last_prefix.len() && sequence.acts.starts_with(last_prefix) {
+            writer.send(SequenceProgress {
+                entity,
+                matched: last_prefix.len(),
+                total: sequence.acts.len(),
+                remaining: sequence
+                    .time_limit
+                    .as_ref()
+                    .or(settings.default_time_limit.as_ref())
+                    .map(remaining),
+            });
+        }
+    }
+}
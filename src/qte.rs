@@ -0,0 +1,124 @@
+//! One-shot quick time events
+//!
+//! A [Qte] prompts for a single chord that must be pressed before a deadline.
+//! Exactly one of its two events is sent: the hit event if the chord is
+//! pressed in time, or else the miss event once the deadline passes. Either
+//! way the QTE's entity is then despawned. Both events must be added to the
+//! app.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, key, qte::Qte};
+//!
+//! #[derive(Event, Clone)]
+//! struct Dodged;
+//!
+//! #[derive(Event, Clone)]
+//! struct Hit;
+//!
+//! fn ambush(mut commands: Commands) {
+//!     commands.queue(Qte::new(key! { Space }, Duration::from_millis(800), Dodged, Hit));
+//! }
+//! ```
+use crate::{input_sequence::KeySequence, KeyChord};
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        system::{Commands, EntityCommand, Query, Res},
+        world::{Command, World},
+    },
+    hierarchy::DespawnRecursiveExt,
+    time::Time,
+};
+use std::time::Duration;
+
+/// A prompt to press `act` within `deadline`, sending `on_hit` if it is
+/// pressed in time and `on_miss` otherwise. Queue it as a command to start
+/// it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Qte<H, M> {
+    /// Chord to press
+    pub act: KeyChord,
+    /// Time to press it
+    pub deadline: Duration,
+    /// Event sent if it is pressed in time
+    pub on_hit: H,
+    /// Event sent if it is not
+    pub on_miss: M,
+}
+
+impl<H: Event + Clone, M: Event> Qte<H, M> {
+    /// Prompt for `act` within `deadline`.
+    pub fn new(act: impl Into<KeyChord>, deadline: Duration, on_hit: H, on_miss: M) -> Self {
+        Self {
+            act: act.into(),
+            deadline,
+            on_hit,
+            on_miss,
+        }
+    }
+}
+
+type Miss = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Time left on a QTE in progress.
+#[derive(Component)]
+pub struct QteTimer {
+    /// Game time at which the QTE is missed
+    pub expires: Duration,
+    on_miss: Option<Miss>,
+}
+
+impl<H: Event + Clone, M: Event> Command for Qte<H, M> {
+    fn apply(self, world: &mut World) {
+        let id = world.spawn_empty().id();
+        EntityCommand::apply(self, id, world);
+    }
+}
+
+impl<H: Event + Clone, M: Event> EntityCommand for Qte<H, M> {
+    fn apply(self, id: Entity, world: &mut World) {
+        let now = world
+            .get_resource::<Time>()
+            .map(|time| time.elapsed())
+            .unwrap_or_default();
+        let on_hit = self.on_hit;
+        EntityCommand::apply(
+            KeySequence::new(
+                move |mut writer: EventWriter<H>, mut commands: Commands| {
+                    writer.send(on_hit.clone());
+                    commands.entity(id).despawn_recursive();
+                },
+                [self.act],
+            ),
+            id,
+            world,
+        );
+        let on_miss = self.on_miss;
+        world.entity_mut(id).insert(QteTimer {
+            expires: now + self.deadline,
+            on_miss: Some(Box::new(move |world: &mut World| {
+                world.send_event(on_miss);
+            })),
+        });
+    }
+}
+
+/// Send the miss event of the QTEs whose deadline has passed.
+pub(crate) fn expire_qtes(
+    mut timers: Query<(Entity, &mut QteTimer)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (id, mut timer) in &mut timers {
+        if time.elapsed() < timer.expires {
+            continue;
+        }
+        if let Some(on_miss) = timer.on_miss.take() {
+            commands.queue(on_miss);
+        }
+        commands.entity(id).despawn_recursive();
+    }
+}
@@ -0,0 +1,193 @@
+//! Suggest ways out of rebinding conflicts
+//!
+//! When the user presses a new binding that conflicts with another one in the
+//! [Keymap], [suggest] reports each conflict along with the [Resolution]s a
+//! settings screen can offer: swap the two bindings, unbind the other one, or
+//! add a modifier to the new chords. Apply the one the user picks with
+//! [Resolution::apply].
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{
+//!     prelude::*,
+//!     keymap::{Binding, Keymap},
+//!     rebind::{suggest, Resolution},
+//! };
+//!
+//! let chords = |acts: &[(Modifiers, KeyCode)]| acts.iter().cloned().map(KeyChord::from).collect::<Vec<_>>();
+//! let mut keymap = Keymap {
+//!     bindings: vec![
+//!         Binding { name: "jump".into(), acts: chords(&keyseq! { Space }), time_limit: None },
+//!         Binding { name: "dash".into(), acts: chords(&keyseq! { D }), time_limit: None },
+//!     ],
+//! };
+//! let conflicts = suggest(&keymap, "jump", &chords(&keyseq! { D }));
+//! assert_eq!(conflicts[0].other, "dash");
+//! let swap = &conflicts[0].resolutions[0];
+//! assert!(matches!(swap, Resolution::Swap { .. }));
+//! swap.apply(&mut keymap, "jump", &chords(&keyseq! { D }));
+//! assert_eq!(keymap.bindings[0].acts, chords(&keyseq! { D }));
+//! assert_eq!(keymap.bindings[1].acts, chords(&keyseq! { Space }));
+//! ```
+use crate::{
+    keymap::{Binding, Keymap},
+    namespace::ConflictKind,
+    KeyChord, Modifiers,
+};
+
+/// Modifiers tried in order when suggesting [Resolution::AppendModifier].
+const MODIFIERS: [Modifiers; 4] = [
+    Modifiers::CONTROL,
+    Modifiers::SHIFT,
+    Modifiers::ALT,
+    Modifiers::SUPER,
+];
+
+/// A way to resolve a rebinding conflict.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    /// Give the other binding the rebound binding's previous chords.
+    Swap {
+        /// Name of the other binding.
+        other: String,
+        /// Chords the other binding gets.
+        acts: Vec<KeyChord>,
+    },
+    /// Remove the other binding from the keymap.
+    UnbindOther {
+        /// Name of the other binding.
+        other: String,
+    },
+    /// Bind these chords instead, i.e., the new chords with `modifiers` added
+    /// to the last one.
+    AppendModifier {
+        /// Modifiers added to the last chord.
+        modifiers: Modifiers,
+        /// Chords to bind instead.
+        acts: Vec<KeyChord>,
+    },
+}
+
+impl Resolution {
+    /// Rebind `name` to `acts` in `keymap`, resolving the conflict this way.
+    /// A binding named `name` is added if there is none.
+    pub fn apply(&self, keymap: &mut Keymap, name: &str, acts: &[KeyChord]) {
+        let acts = match self {
+            Resolution::Swap { other, acts: swapped } => {
+                if let Some(binding) = find_mut(keymap, other) {
+                    binding.acts = swapped.clone();
+                }
+                acts.to_vec()
+            }
+            Resolution::UnbindOther { other } => {
+                keymap.bindings.retain(|binding| binding.name != *other);
+                acts.to_vec()
+            }
+            Resolution::AppendModifier { acts, .. } => acts.clone(),
+        };
+        match find_mut(keymap, name) {
+            Some(binding) => binding.acts = acts,
+            None => keymap.bindings.push(Binding {
+                name: name.into(),
+                acts,
+                time_limit: None,
+            }),
+        }
+    }
+}
+
+/// A binding the new chords conflict with and how to resolve it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebindConflict {
+    /// Name of the conflicting binding.
+    pub other: String,
+    /// How they conflict; for [ConflictKind::Prefix], either one's chords may
+    /// begin the other's.
+    pub kind: ConflictKind,
+    /// Suggested resolutions, most preferred first.
+    pub resolutions: Vec<Resolution>,
+}
+
+fn find_mut<'a>(keymap: &'a mut Keymap, name: &str) -> Option<&'a mut Binding> {
+    keymap
+        .bindings
+        .iter_mut()
+        .find(|binding| binding.name == name)
+}
+
+fn conflict_kind(a: &[KeyChord], b: &[KeyChord]) -> Option<ConflictKind> {
+    if a.is_empty() || b.is_empty() {
+        None
+    } else if a == b {
+        Some(ConflictKind::Same)
+    } else if a.starts_with(b) || b.starts_with(a) {
+        Some(ConflictKind::Prefix)
+    } else {
+        None
+    }
+}
+
+/// Report the bindings other than `name` that rebinding it to `acts` would
+/// conflict with, each with suggested resolutions. Returns nothing if the
+/// rebind is free of conflicts.
+pub fn suggest(keymap: &Keymap, name: &str, acts: &[KeyChord]) -> Vec<RebindConflict> {
+    let others = || {
+        keymap
+            .bindings
+            .iter()
+            .filter(move |binding| binding.name != name)
+    };
+    let previous = keymap
+        .bindings
+        .iter()
+        .find(|binding| binding.name == name)
+        .map(|binding| &binding.acts);
+    // The same modifier works for every conflict, so look for it once.
+    let modified = acts.last().and_then(|KeyChord(mods, key)| {
+        MODIFIERS
+            .into_iter()
+            .filter(|modifier| !mods.contains(*modifier))
+            .map(|modifier| {
+                let mut modified = acts.to_vec();
+                *modified.last_mut().unwrap() = KeyChord(*mods | modifier, *key);
+                (modifier, modified)
+            })
+            .find(|(_, modified)| {
+                others().all(|binding| conflict_kind(modified, &binding.acts).is_none())
+            })
+    });
+    others()
+        .filter_map(|binding| {
+            let kind = conflict_kind(acts, &binding.acts)?;
+            let mut resolutions = Vec::new();
+            // Swapping only helps if the previous chords don't conflict with
+            // anything besides the rebound binding.
+            if let Some(previous) = previous.filter(|previous| {
+                conflict_kind(previous, acts).is_none()
+                    && others().all(|other| {
+                        other.name == binding.name
+                            || conflict_kind(previous, &other.acts).is_none()
+                    })
+            }) {
+                resolutions.push(Resolution::Swap {
+                    other: binding.name.clone(),
+                    acts: previous.clone(),
+                });
+            }
+            resolutions.push(Resolution::UnbindOther {
+                other: binding.name.clone(),
+            });
+            if let Some((modifiers, acts)) = &modified {
+                resolutions.push(Resolution::AppendModifier {
+                    modifiers: *modifiers,
+                    acts: acts.clone(),
+                });
+            }
+            Some(RebindConflict {
+                other: binding.name.clone(),
+                kind,
+                resolutions,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,83 @@
+//! Show physical keys as labeled on the player's keyboard
+//!
+//! A [KeyCode] names a key by its position on a US ANSI keyboard. Keyboards
+//! sold elsewhere put different legends on some of those positions, e.g., the
+//! key left of Enter reads `#` on a UK ISO keyboard and `]` on a Japanese JIS
+//! keyboard. Pick a [KeyboardRegion] to format chords with the legends the
+//! player actually sees.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, region::KeyboardRegion};
+//!
+//! let chord = KeyChord::from(KeyCode::Backslash);
+//! assert_eq!(chord.display(KeyboardRegion::Ansi).to_string(), "\\");
+//! assert_eq!(chord.display(KeyboardRegion::Iso).to_string(), "#");
+//! assert_eq!(chord.display(KeyboardRegion::Jis).to_string(), "]");
+//! ```
+//!
+//! Insert a [KeyboardRegion] resource to have
+//! [KeySequenceHint](crate::hint::KeySequenceHint)s use it.
+use crate::{chord::key_label, KeyChord};
+use bevy::{ecs::system::Resource, input::keyboard::KeyCode, reflect::Reflect};
+use std::fmt;
+
+/// A physical keyboard layout family.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum KeyboardRegion {
+    /// American layout with 104 keys; matches [KeyCode] names.
+    #[default]
+    Ansi,
+    /// European layout with 105 keys, labeled as on a UK keyboard.
+    Iso,
+    /// Japanese layout with 109 keys.
+    Jis,
+}
+
+impl KeyboardRegion {
+    /// Return the legend of `key` in this region.
+    pub fn label(&self, key: KeyCode) -> &'static str {
+        use KeyCode::*;
+        match (self, key) {
+            (KeyboardRegion::Ansi, Backslash) => "\\",
+            (KeyboardRegion::Iso, Backslash) => "#",
+            (KeyboardRegion::Jis, Backslash) => "]",
+            (KeyboardRegion::Jis, BracketLeft) => "@",
+            (KeyboardRegion::Jis, BracketRight) => "[",
+            (KeyboardRegion::Jis, Quote) => ":",
+            (KeyboardRegion::Jis, Equal) => "^",
+            (KeyboardRegion::Jis, Backquote) => "Hankaku",
+            (_, IntlBackslash) | (_, IntlRo) => "\\",
+            (_, IntlYen) => "¥",
+            _ => key_label(key),
+        }
+    }
+}
+
+/// Formats a key chord with the legends of a [KeyboardRegion].
+#[derive(Debug, Clone, Copy)]
+pub struct RegionalKeyChord<'a> {
+    chord: &'a KeyChord,
+    region: KeyboardRegion,
+}
+
+impl fmt::Display for RegionalKeyChord<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let KeyChord(mods, key) = self.chord;
+        mods.fmt(f)?;
+        if !mods.is_empty() {
+            f.write_str("-")?;
+        }
+        f.write_str(self.region.label(*key))
+    }
+}
+
+impl KeyChord {
+    /// Format this chord with the legends of `region`.
+    pub fn display(&self, region: KeyboardRegion) -> RegionalKeyChord<'_> {
+        RegionalKeyChord {
+            chord: self,
+            region,
+        }
+    }
+}
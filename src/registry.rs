@@ -0,0 +1,359 @@
+//! Look up registered sequences
+//!
+//! Sequences live on entities, so a sequence is identified by its entity.
+//! [SequenceRegistry] is a system parameter that answers questions about the
+//! registered sequences without querying their components directly, and
+//! registers batches of sequences with [extend](SequenceRegistry::extend):
+//! either every sequence in the batch is added or, if any conflicts, none is.
+//! [iter](SequenceRegistry::iter) lists the sequences along with their
+//! [Namespace], [FocusOwner], and whether they are a [Cheat], narrowed down by
+//! filters.
+//!
+//! ```rust
+//! use bevy::{ecs::system::SystemState, prelude::*};
+//! use bevy_input_sequence::{
+//!     prelude::*, key,
+//!     registry::{ConflictPolicy, KeySequenceRegistry},
+//! };
+//!
+//! /// Show the bindings that start with what has been typed so far.
+//! fn palette(registry: KeySequenceRegistry) {
+//!     for (id, rest) in registry.complete(&[KeyChord::from(key! { Ctrl-X })]) {
+//!         info!("{id}: {} more", rest.len());
+//!     }
+//! }
+//!
+//! /// List the inspector's cheats.
+//! fn cheats(registry: KeySequenceRegistry) {
+//!     for view in registry.iter().namespace("inspector").cheat(true) {
+//!         info!("{}: {:?}", view.id, view.sequence.acts);
+//!     }
+//! }
+//!
+//! /// Load a user's keymap entirely or not at all.
+//! fn load(world: &mut World) {
+//!     let save = KeySequence::new(|| info!("save"), keyseq! { Ctrl-X Ctrl-S }).build(world);
+//!     let mut state = SystemState::<KeySequenceRegistry>::new(world);
+//!     if let Err(e) = state.get_mut(world).extend([save], ConflictPolicy::RejectPrefix) {
+//!         warn!("keymap not loaded: {e}");
+//!     }
+//!     state.apply(world);
+//! }
+//! ```
+use crate::{
+    button::ButtonAct,
+    cheat::Cheat,
+    focus::FocusOwner,
+    input_sequence::InputSequence,
+    namespace::{ConflictKind, Namespace},
+    KeyChord,
+};
+use bevy::{
+    ecs::{
+        entity::Entity,
+        query::Has,
+        system::{Commands, Query, SystemInput, SystemParam},
+    },
+    hierarchy::BuildChildren,
+    prelude::In,
+};
+use std::{borrow::Cow, fmt};
+
+/// Identifies a registered sequence by the entity it is on.
+pub type SequenceId = Entity;
+
+/// Access to registered sequences.
+#[derive(SystemParam)]
+pub struct SequenceRegistry<'w, 's, Act, I>
+where
+    Act: Send + Sync + 'static,
+    I: SystemInput + Send + Sync + 'static,
+{
+    #[allow(clippy::type_complexity)]
+    sequences: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static InputSequence<Act, I>,
+            Option<&'static Namespace>,
+            Option<&'static FocusOwner>,
+            Has<Cheat>,
+        ),
+    >,
+    commands: Commands<'w, 's>,
+}
+
+/// A registered sequence and what it is marked with.
+pub struct SequenceView<'a, Act, I: SystemInput + 'static> {
+    /// Id of the sequence
+    pub id: SequenceId,
+    /// The sequence
+    pub sequence: &'a InputSequence<Act, I>,
+    /// Its namespace, if any
+    pub namespace: Option<&'a Namespace>,
+    /// The entity that must hold focus for it to match, if any
+    pub focus_owner: Option<Entity>,
+    /// True if it is marked [Cheat]
+    pub cheat: bool,
+}
+
+impl<Act: fmt::Debug, I: SystemInput + Clone + 'static> fmt::Debug for SequenceView<'_, Act, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SequenceView")
+            .field("id", &self.id)
+            .field("sequence", self.sequence)
+            .field("namespace", &self.namespace)
+            .field("focus_owner", &self.focus_owner)
+            .field("cheat", &self.cheat)
+            .finish()
+    }
+}
+
+/// Iterator over registered sequences returned by
+/// [iter](SequenceRegistry::iter). Filters narrow down the sequences it
+/// yields; all of them must hold.
+pub struct Sequences<'a, Act, I: SystemInput + 'static> {
+    views: Box<dyn Iterator<Item = SequenceView<'a, Act, I>> + 'a>,
+    namespace: Option<Option<Cow<'static, str>>>,
+    focus_owner: Option<Option<Entity>>,
+    cheat: Option<bool>,
+}
+
+impl<'a, Act, I: SystemInput + 'static> Sequences<'a, Act, I> {
+    /// Only yield sequences in namespace `name`.
+    pub fn namespace(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.namespace = Some(Some(name.into()));
+        self
+    }
+
+    /// Only yield sequences without a namespace, i.e., the host game's.
+    pub fn without_namespace(mut self) -> Self {
+        self.namespace = Some(None);
+        self
+    }
+
+    /// Only yield sequences whose [FocusOwner] is `owner`, or that have none
+    /// if `owner` is `None`.
+    pub fn focus_owner(mut self, owner: Option<Entity>) -> Self {
+        self.focus_owner = Some(owner);
+        self
+    }
+
+    /// Only yield cheats if `cheat` is true, or only other sequences if
+    /// false.
+    pub fn cheat(mut self, cheat: bool) -> Self {
+        self.cheat = Some(cheat);
+        self
+    }
+
+    fn accepts(&self, view: &SequenceView<'a, Act, I>) -> bool {
+        self.namespace
+            .as_ref()
+            .is_none_or(|name| name.as_deref() == view.namespace.map(|n| n.0.as_ref()))
+            && self
+                .focus_owner
+                .is_none_or(|owner| owner == view.focus_owner)
+            && self.cheat.is_none_or(|cheat| cheat == view.cheat)
+    }
+}
+
+impl<'a, Act, I: SystemInput + 'static> Iterator for Sequences<'a, Act, I> {
+    type Item = SequenceView<'a, Act, I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let view = self.views.next()?;
+            if self.accepts(&view) {
+                return Some(view);
+            }
+        }
+    }
+}
+
+/// Which conflicts make [extend](SequenceRegistry::extend) reject a batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Accept any batch.
+    Allow,
+    /// Reject sequences with the same acts.
+    #[default]
+    RejectSame,
+    /// Also reject sequences whose acts begin another's.
+    RejectPrefix,
+}
+
+impl ConflictPolicy {
+    fn rejects(&self, kind: ConflictKind) -> bool {
+        match self {
+            ConflictPolicy::Allow => false,
+            ConflictPolicy::RejectSame => kind == ConflictKind::Same,
+            ConflictPolicy::RejectPrefix => true,
+        }
+    }
+}
+
+/// What a sequence in a batch conflicts with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictWith {
+    /// An already registered sequence.
+    Registered(SequenceId),
+    /// The sequence at this index of the batch.
+    Batch(usize),
+}
+
+/// A sequence in a batch that conflicts with another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchConflict {
+    /// Index of the sequence in the batch.
+    pub index: usize,
+    /// The sequence it conflicts with.
+    pub with: ConflictWith,
+    /// How they conflict; for [ConflictKind::Prefix], either one's acts may
+    /// begin the other's.
+    pub kind: ConflictKind,
+}
+
+/// Error registering a batch of sequences; none of them were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchError {
+    /// Every conflict the policy rejects.
+    pub conflicts: Vec<BatchConflict>,
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} conflicting sequences:", self.conflicts.len())?;
+        for conflict in &self.conflicts {
+            let kind = match conflict.kind {
+                ConflictKind::Same => "same acts as",
+                ConflictKind::Prefix => "prefix conflict with",
+            };
+            match conflict.with {
+                ConflictWith::Registered(id) => {
+                    write!(f, " #{} has {kind} {id};", conflict.index)?
+                }
+                ConflictWith::Batch(other) => {
+                    write!(f, " #{} has {kind} #{other};", conflict.index)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+/// Return how two sequences of acts conflict, if they do.
+fn conflict_kind<Act: PartialEq>(a: &[Act], b: &[Act]) -> Option<ConflictKind> {
+    if a == b {
+        Some(ConflictKind::Same)
+    } else if a.starts_with(b) || b.starts_with(a) {
+        Some(ConflictKind::Prefix)
+    } else {
+        None
+    }
+}
+
+/// Registry of key sequences.
+pub type KeySequenceRegistry<'w, 's> = SequenceRegistry<'w, 's, KeyChord, ()>;
+/// Registry of gamepad button sequences.
+pub type ButtonSequenceRegistry<'w, 's> = SequenceRegistry<'w, 's, ButtonAct, In<Entity>>;
+
+impl<Act, I> SequenceRegistry<'_, '_, Act, I>
+where
+    Act: Clone + PartialEq + Send + Sync + 'static,
+    I: SystemInput + Send + Sync + 'static,
+{
+    /// Return the sequence with the given id.
+    pub fn get(&self, id: SequenceId) -> Option<&InputSequence<Act, I>> {
+        self.sequences.get(id).ok().map(|(_, seq, ..)| seq)
+    }
+
+    /// Iterate over the registered sequences; chain filters such as
+    /// [namespace](Sequences::namespace) to narrow them down.
+    pub fn iter(&self) -> Sequences<'_, Act, I> {
+        Sequences {
+            views: Box::new(self.sequences.iter().map(
+                |(id, sequence, namespace, focus_owner, cheat)| SequenceView {
+                    id,
+                    sequence,
+                    namespace,
+                    focus_owner: focus_owner.map(|owner| owner.0),
+                    cheat,
+                },
+            )),
+            namespace: None,
+            focus_owner: None,
+            cheat: None,
+        }
+    }
+
+    /// Return the sequences that begin with `prefix` along with their
+    /// remaining acts, shortest remainder first. A sequence equal to `prefix`
+    /// is included with no remaining acts.
+    pub fn complete(&self, prefix: &[Act]) -> Vec<(SequenceId, Vec<Act>)> {
+        let mut completions: Vec<_> = self
+            .sequences
+            .iter()
+            .filter_map(|(id, seq, ..)| {
+                seq.acts
+                    .strip_prefix(prefix)
+                    .map(|rest| (id, rest.to_vec()))
+            })
+            .collect();
+        completions.sort_by_key(|(id, rest)| (rest.len(), *id));
+        completions
+    }
+
+    /// Register all of `sequences`, or none of them if any conflicts with a
+    /// registered sequence or another one in the batch under `policy`.
+    /// Returns the ids of the new sequences in order.
+    ///
+    /// Each sequence's system becomes a child of its entity, as with the
+    /// builders. A rejected batch unregisters its sequences' systems.
+    pub fn extend(
+        &mut self,
+        sequences: impl IntoIterator<Item = InputSequence<Act, I>>,
+        policy: ConflictPolicy,
+    ) -> Result<Vec<SequenceId>, BatchError> {
+        let batch: Vec<_> = sequences.into_iter().collect();
+        let mut conflicts = Vec::new();
+        for (index, seq) in batch.iter().enumerate() {
+            for (id, registered, ..) in &self.sequences {
+                if let Some(kind) = conflict_kind(&seq.acts, &registered.acts) {
+                    conflicts.push(BatchConflict {
+                        index,
+                        with: ConflictWith::Registered(id),
+                        kind,
+                    });
+                }
+            }
+            for (other, earlier) in batch[..index].iter().enumerate() {
+                if let Some(kind) = conflict_kind(&seq.acts, &earlier.acts) {
+                    conflicts.push(BatchConflict {
+                        index,
+                        with: ConflictWith::Batch(other),
+                        kind,
+                    });
+                }
+            }
+        }
+        conflicts.retain(|conflict| policy.rejects(conflict.kind));
+        if !conflicts.is_empty() {
+            for seq in &batch {
+                self.commands.unregister_system(seq.system_id);
+            }
+            return Err(BatchError { conflicts });
+        }
+        Ok(batch
+            .into_iter()
+            .map(|seq| {
+                let system_entity = seq.system_id.entity();
+                let id = self.commands.spawn(seq).id();
+                self.commands.entity(system_entity).set_parent(id);
+                id
+            })
+            .collect())
+    }
+}
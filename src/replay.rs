@@ -0,0 +1,156 @@
+//! Save and load recorded act streams
+//!
+//! Requires the "replay" feature. A [Replay] is a list of acts with the time
+//! and device of each, stored as RON with a format version so libraries of
+//! regression replays survive crate upgrades.
+//!
+//! Compatibility policy: [import](Replay::import) reads any replay whose
+//! version is at most [Replay::VERSION], upgrading older ones as it goes, and
+//! rejects newer ones with [ReplayError::Unsupported]. The version only
+//! changes when a replay's meaning would; new optional fields don't bump it.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, button::ButtonAct, replay::Replay};
+//! use std::time::Duration;
+//!
+//! let mut replay = Replay::default();
+//! replay.push(Duration::ZERO, None, KeyChord(Modifiers::CONTROL, KeyCode::KeyS));
+//! replay.push(Duration::from_millis(250), Some(0), ButtonAct::Press(GamepadButton::South));
+//! let text = replay.export().unwrap();
+//! assert_eq!(Replay::import(&text).unwrap(), replay);
+//! ```
+use crate::{button::ButtonAct, KeyChord};
+use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+
+/// One recorded act.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayAct {
+    /// A key chord.
+    Key(KeyChord),
+    /// A gamepad act.
+    Button(ButtonAct),
+}
+
+impl From<KeyChord> for ReplayAct {
+    fn from(chord: KeyChord) -> Self {
+        ReplayAct::Key(chord)
+    }
+}
+
+impl From<ButtonAct> for ReplayAct {
+    fn from(act: ButtonAct) -> Self {
+        ReplayAct::Button(act)
+    }
+}
+
+/// An act and when and where it happened.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    /// Time since the start of the recording.
+    pub time: Duration,
+    /// Index of the gamepad in the recording; `None` for the keyboard.
+    #[serde(default)]
+    pub device: Option<u32>,
+    /// The act.
+    pub act: ReplayAct,
+}
+
+/// A recorded act stream.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replay {
+    /// Format version the replay was written with.
+    pub version: u32,
+    /// Acts in the order they happened.
+    pub entries: Vec<ReplayEntry>,
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// Only the version, read first to decide how to read the rest.
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+impl Replay {
+    /// Format version written by this crate.
+    pub const VERSION: u32 = 1;
+
+    /// Record `act` at `time` from `device`.
+    pub fn push(&mut self, time: Duration, device: Option<u32>, act: impl Into<ReplayAct>) {
+        self.entries.push(ReplayEntry {
+            time,
+            device,
+            act: act.into(),
+        });
+    }
+
+    /// Write the replay as RON.
+    pub fn export(&self) -> Result<String, ReplayError> {
+        Ok(ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+
+    /// Read a replay written by [export](Self::export) from this or an
+    /// earlier version of the format.
+    pub fn import(text: &str) -> Result<Self, ReplayError> {
+        let header: Header = ron::from_str(text)?;
+        if header.version > Self::VERSION {
+            return Err(ReplayError::Unsupported(header.version));
+        }
+        let mut replay: Replay = ron::from_str(text)?;
+        // Version 1 is the first; later versions upgrade older replays here.
+        replay.version = Self::VERSION;
+        Ok(replay)
+    }
+}
+
+/// Error reading or writing a [Replay].
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The replay is from a newer version of the format.
+    Unsupported(u32),
+    /// The replay is not valid RON.
+    Parse(ron::error::SpannedError),
+    /// The replay could not be written.
+    Write(ron::Error),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Unsupported(version) => write!(
+                f,
+                "replay format version {version} is newer than {}",
+                Replay::VERSION
+            ),
+            ReplayError::Parse(e) => write!(f, "invalid replay: {e}"),
+            ReplayError::Write(e) => write!(f, "could not write replay: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<ron::error::SpannedError> for ReplayError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        ReplayError::Parse(e)
+    }
+}
+
+impl From<ron::Error> for ReplayError {
+    fn from(e: ron::Error) -> Self {
+        ReplayError::Write(e)
+    }
+}
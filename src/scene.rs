@@ -0,0 +1,136 @@
+//! Author sequences in scene files
+//!
+//! [InputSequence] is reflected and registered by the plugin, so sequences can
+//! be written in `.scn.ron` files and spawned with
+//! [DynamicSceneRoot](https://docs.rs/bevy/0.15/bevy/scene/struct.DynamicSceneRoot.html).
+//! Systems can't be stored in a scene, so put the event to send on the same
+//! entity as a reflected component instead. Register its type with
+//! [add_scene_event](AddSceneEvent::add_scene_event) and every key or button
+//! sequence spawned without a system sends a clone of that component when it
+//! matches.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, scene::AddSceneEvent};
+//!
+//! #[derive(Event, Reflect, Clone, Default)]
+//! #[reflect(Component, Default)]
+//! struct Teleport {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(InputSequencePlugin::default())
+//!    .add_scene_event::<Teleport>();
+//! ```
+//!
+//! An entity in the scene then carries both components:
+//!
+//! ```ron
+//! "bevy_input_sequence::input_sequence::InputSequence<bevy_input_sequence::chord::KeyChord, ()>": (
+//!     acts: [((0), KeyT)],
+//!     time_limit: None,
+//!     presses: [],
+//!     intervals: [],
+//! ),
+//! "my_game::Teleport": (x: 4.0, y: 2.0),
+//! ```
+use crate::input_sequence::{ButtonSequence, InputSequence, KeySequence};
+use bevy::{
+    app::{App, PreUpdate},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        query::{Added, Or, With},
+        system::{Commands, In, Query, SystemInput},
+        world::World,
+    },
+    reflect::{FromReflect, GetTypeRegistration, TypePath},
+};
+
+/// Send events stored on sequence entities, e.g., ones spawned from a scene.
+pub trait AddSceneEvent {
+    /// Have key and button sequences without a system send the `E` component
+    /// on their entity when they match.
+    fn add_scene_event<E>(&mut self) -> &mut Self
+    where
+        E: Event + Component + Clone + FromReflect + TypePath + GetTypeRegistration;
+}
+
+impl AddSceneEvent for App {
+    fn add_scene_event<E>(&mut self) -> &mut Self
+    where
+        E: Event + Component + Clone + FromReflect + TypePath + GetTypeRegistration,
+    {
+        self.register_type::<E>().add_event::<E>().add_systems(
+            PreUpdate,
+            (
+                assign_key_scene_event::<E>,
+                assign_button_scene_event::<E>,
+            ),
+        )
+    }
+}
+
+/// Return true if no system has been assigned to the sequence, i.e., it was
+/// built from reflection.
+pub fn is_unassigned<Act, I: SystemInput + 'static>(sequence: &InputSequence<Act, I>) -> bool {
+    sequence.system_id.entity() == Entity::PLACEHOLDER
+}
+
+fn send_component<E: Event + Component + Clone>(
+    entity: Entity,
+    events: &Query<&E>,
+    writer: &mut EventWriter<E>,
+) {
+    if let Ok(event) = events.get(entity) {
+        writer.send(event.clone());
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn assign_key_scene_event<E: Event + Component + Clone>(
+    sequences: Query<(Entity, &KeySequence), (With<E>, Or<(Added<KeySequence>, Added<E>)>)>,
+    mut commands: Commands,
+) {
+    for (id, sequence) in &sequences {
+        if !is_unassigned(sequence) {
+            continue;
+        }
+        commands.queue(move |world: &mut World| {
+            let system_id = world.register_system(
+                move |events: Query<&E>, mut writer: EventWriter<E>| {
+                    send_component(id, &events, &mut writer)
+                },
+            );
+            if let Some(mut sequence) = world.get_mut::<KeySequence>(id) {
+                sequence.system_id = system_id;
+            }
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn assign_button_scene_event<E: Event + Component + Clone>(
+    sequences: Query<(Entity, &ButtonSequence), (With<E>, Or<(Added<ButtonSequence>, Added<E>)>)>,
+    mut commands: Commands,
+) {
+    for (id, sequence) in &sequences {
+        if !is_unassigned(sequence) {
+            continue;
+        }
+        commands.queue(move |world: &mut World| {
+            let system_id = world.register_system(
+                move |_: In<Entity>, events: Query<&E>, mut writer: EventWriter<E>| {
+                    send_component(id, &events, &mut writer)
+                },
+            );
+            if let Some(mut sequence) = world.get_mut::<ButtonSequence>(id) {
+                sequence.system_id = system_id;
+            }
+        });
+    }
+}
@@ -0,0 +1,116 @@
+//! Match some sequences in another schedule
+//!
+//! The plugin matches sequences in each schedule it is configured to
+//! [run_in](crate::InputSequencePlugin::run_in). Sequences match in the first
+//! of them, `Update` by default, unless they have a [MatchSchedule], which the
+//! builder's [match_in](crate::input_sequence::InputSequenceBuilder::match_in)
+//! option inserts, e.g., combat combos in `FixedUpdate` while UI hotkeys stay
+//! in `Update`. Each schedule keeps its own partial matches.
+//!
+//! Every kind of sequence honors [MatchSchedule]. The builders without a
+//! `match_in` option, e.g., for mashes and pattern sequences, take it when it
+//! is inserted on the sequence's entity.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(KeySequence::new(|| info!("menu"), keyseq! { Escape }));
+//!     commands.queue(
+//!         KeySequence::new(|| info!("uppercut"), keyseq! { ArrowDown ArrowUp P })
+//!             .match_in(FixedUpdate),
+//!     );
+//! }
+//!
+//! App::new()
+//!     .add_plugins(MinimalPlugins)
+//!     .add_plugins(InputSequencePlugin::default().run_in(FixedUpdate))
+//!     .add_systems(Startup, setup);
+//! ```
+use bevy::ecs::{
+    component::Component,
+    intern::Interned,
+    schedule::ScheduleLabel,
+    system::{Res, ResMut, Resource},
+};
+use std::collections::HashMap;
+
+type Label = Interned<dyn ScheduleLabel>;
+
+/// Match the sequence on this entity in this schedule instead of the
+/// plugin's first one.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MatchSchedule(pub Label);
+
+impl MatchSchedule {
+    /// Match in `schedule`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self(schedule.intern())
+    }
+}
+
+/// The schedule the matchers are running in.
+#[derive(Resource, Clone, Copy, Debug)]
+pub(crate) struct CurrentSchedule {
+    current: Label,
+    first: Label,
+}
+
+impl CurrentSchedule {
+    pub(crate) fn new(first: Label) -> Self {
+        Self {
+            current: first,
+            first,
+        }
+    }
+
+    /// Return true if a sequence with `schedule` matches in the current
+    /// schedule.
+    pub(crate) fn accepts(&self, schedule: Option<&MatchSchedule>) -> bool {
+        schedule.map_or(self.first, |schedule| schedule.0) == self.current
+    }
+}
+
+/// Caches of type `C` of the schedules other than the first, which keeps its
+/// own in the `C` resource.
+#[derive(Resource)]
+pub(crate) struct ScheduleCaches<C>(HashMap<Label, C>);
+
+impl<C> Default for ScheduleCaches<C> {
+    fn default() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+/// Make `schedule` current before matchers that keep no cache run.
+pub(crate) fn set_schedule(schedule: Label) -> impl FnMut(ResMut<CurrentSchedule>) {
+    move |mut current: ResMut<CurrentSchedule>| current.current = schedule
+}
+
+/// Make `schedule` current and swap in its `C` cache before its matchers run.
+pub(crate) fn enter_schedule<C: Resource + Default>(
+    schedule: Label,
+) -> impl FnMut(ResMut<CurrentSchedule>, ResMut<ScheduleCaches<C>>, ResMut<C>) {
+    move |mut current: ResMut<CurrentSchedule>,
+          mut caches: ResMut<ScheduleCaches<C>>,
+          mut cache: ResMut<C>| {
+        current.current = schedule;
+        if schedule != current.first {
+            std::mem::swap(&mut *cache, caches.0.entry(schedule).or_default());
+        }
+    }
+}
+
+/// Swap the `C` cache of `schedule` back out after its matchers ran.
+pub(crate) fn leave_schedule<C: Resource + Default>(
+    schedule: Label,
+) -> impl FnMut(Res<CurrentSchedule>, ResMut<ScheduleCaches<C>>, ResMut<C>) {
+    move |current: Res<CurrentSchedule>,
+          mut caches: ResMut<ScheduleCaches<C>>,
+          mut cache: ResMut<C>| {
+        if schedule != current.first {
+            std::mem::swap(&mut *cache, caches.0.entry(schedule).or_default());
+        }
+    }
+}
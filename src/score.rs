@@ -0,0 +1,100 @@
+//! Grade how well a sequence was executed
+//!
+//! Give a sequence a par time with the builder's
+//! [score](crate::input_sequence::InputSequenceBuilder::score) option and its
+//! [Matched](crate::matched::Matched) events carry a [MatchScore]. Timing
+//! compares the par time to how long the sequence took; accuracy drops with
+//! each wrong input its [Tolerance](crate::tolerance::Tolerance) forgave.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, matched::Matched, score::Rank};
+//! use std::time::Duration;
+//!
+//! #[derive(Event, Clone)]
+//! struct Combo;
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn_empty().queue(
+//!         KeySequence::new(action::send_matched(Combo), keyseq! { S D P })
+//!             .tolerate(1)
+//!             .score(Duration::from_millis(300)));
+//! }
+//!
+//! fn grade(mut reader: EventReader<Matched<Combo>>) {
+//!     for matched in reader.read() {
+//!         if matched.score.is_some_and(|score| score.rank == Rank::S) {
+//!             info!("perfect");
+//!         }
+//!     }
+//! }
+//! ```
+use bevy::{ecs::component::Component, reflect::Reflect};
+use std::time::Duration;
+
+/// Score matches of the sequence against a par time.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub struct Scoring {
+    /// Duration at or under which timing is perfect.
+    pub par: Duration,
+}
+
+/// Letter grade of a [MatchScore].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum Rank {
+    /// At least 0.9.
+    S,
+    /// At least 0.75.
+    A,
+    /// At least 0.5.
+    B,
+    /// Below 0.5.
+    C,
+}
+
+impl Rank {
+    /// Return the rank of a score in `[0, 1]`.
+    pub fn from_value(value: f32) -> Self {
+        if value >= 0.9 {
+            Rank::S
+        } else if value >= 0.75 {
+            Rank::A
+        } else if value >= 0.5 {
+            Rank::B
+        } else {
+            Rank::C
+        }
+    }
+}
+
+/// Quality of a match. All values are in `[0, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct MatchScore {
+    /// Par time over the time taken, at most 1.
+    pub timing: f32,
+    /// One over one plus the number of wrong inputs.
+    pub accuracy: f32,
+    /// Product of timing and accuracy.
+    pub value: f32,
+    /// Grade of the value.
+    pub rank: Rank,
+}
+
+impl Scoring {
+    /// Score a match that took `duration` and forgave `mistakes` wrong inputs.
+    pub fn score(&self, duration: Duration, mistakes: u32) -> MatchScore {
+        let timing = if duration <= self.par {
+            1.0
+        } else {
+            self.par.as_secs_f32() / duration.as_secs_f32()
+        };
+        let accuracy = 1.0 / (1.0 + mistakes as f32);
+        let value = timing * accuracy;
+        MatchScore {
+            timing,
+            accuracy,
+            value,
+            rank: Rank::from_value(value),
+        }
+    }
+}
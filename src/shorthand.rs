@@ -0,0 +1,219 @@
+//! Convert inputs to and from `keyseq!` shorthand
+//!
+//! The [keyseq](crate::keyseq) macro turns shorthand like `Ctrl-X '['` into
+//! key chords. These functions go the other way so that captured rebinds can
+//! be saved in the same syntax users write by hand, and [parse_key_sequence]
+//! reads it back at runtime, e.g., cheat codes from a config file.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, shorthand};
+//!
+//! let acts: Vec<KeyChord> = keyseq! { Ctrl-X '[' 1 Shift-; }
+//!     .into_iter()
+//!     .map(KeyChord::from)
+//!     .collect();
+//! assert_eq!(shorthand::key_sequence(&acts).as_deref(), Some("Ctrl-X '[' 1 Shift-;"));
+//! assert_eq!(shorthand::key_code(KeyCode::ArrowUp).as_deref(), Some("ArrowUp"));
+//! assert_eq!(shorthand::gamepad_button(GamepadButton::South).as_deref(), Some("South"));
+//! assert_eq!(shorthand::parse_key_sequence("Ctrl-X '[' 1 Shift-;"), Ok(acts));
+//!
+//! let mut world = World::new();
+//! let seq = KeySequence::parse(|| info!("god mode"), "ctrl-W A S D")
+//!     .unwrap()
+//!     .build(&mut world);
+//! assert_eq!(seq.acts[0], KeyChord(Modifiers::CONTROL, KeyCode::KeyW));
+//! ```
+use crate::{
+    cond_system::IntoCondSystem,
+    input_sequence::{InputSequenceBuilder, KeySequence},
+    modifier, KeyChord, Modifiers,
+};
+use bevy::{
+    input::{gamepad::GamepadButton, keyboard::{Key, KeyCode}},
+    reflect::{Enum, TypeInfo, Typed, VariantInfo},
+};
+use std::fmt;
+
+/// Return the shorthand token for a key code or `None` if it has none, e.g.,
+/// [KeyCode::Unidentified].
+pub fn key_code(key: KeyCode) -> Option<String> {
+    use KeyCode::*;
+    let token = match key {
+        Unidentified(_) => return None,
+        BracketLeft => "'['",
+        BracketRight => "']'",
+        Quote => "'\\''",
+        Backquote => "'`'",
+        Backslash => "'\\\\'",
+        Semicolon => ";",
+        Comma => ",",
+        Period => ".",
+        Equal => "=",
+        Slash => "/",
+        Minus => "-",
+        key => {
+            let name = key.variant_name();
+            return Some(
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .filter(|rest| rest.len() == 1)
+                    .unwrap_or(name)
+                    .to_string(),
+            );
+        }
+    };
+    Some(token.to_string())
+}
+
+/// Return the shorthand for a key chord, e.g., "Ctrl-Shift-A". Returns `None`
+/// if the chord uses game-defined modifiers, which have no shorthand.
+pub fn key_chord(chord: &KeyChord) -> Option<String> {
+    let KeyChord(mods, key) = chord;
+    if mods.intersects(modifier::CUSTOM) {
+        return None;
+    }
+    let key = key_code(*key)?;
+    Some(if mods.is_empty() {
+        key
+    } else {
+        format!("{mods}-{key}")
+    })
+}
+
+/// Return the shorthand for a key sequence with chords separated by spaces.
+pub fn key_sequence(acts: &[KeyChord]) -> Option<String> {
+    let chords: Option<Vec<String>> = acts.iter().map(key_chord).collect();
+    Some(chords?.join(" "))
+}
+
+/// Return the shorthand token for a logical key. Characters are returned as
+/// is; named keys use their variant name.
+pub fn logical_key(key: &Key) -> Option<String> {
+    match key {
+        Key::Character(c) => Some(c.to_string()),
+        Key::Unidentified(_) | Key::Dead(_) => None,
+        key => Some(key.variant_name().to_string()),
+    }
+}
+
+/// Return the shorthand token for a gamepad button or `None` for
+/// [GamepadButton::Other].
+pub fn gamepad_button(button: GamepadButton) -> Option<String> {
+    match button {
+        GamepadButton::Other(_) => None,
+        button => Some(button.variant_name().to_string()),
+    }
+}
+
+/// Return every key code that has a shorthand token along with the token.
+pub fn key_code_table() -> Vec<(KeyCode, String)> {
+    let TypeInfo::Enum(info) = KeyCode::type_info() else {
+        unreachable!("KeyCode is an enum");
+    };
+    info.iter()
+        .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+        .filter_map(|variant| crate::chord::key_code_from_name(variant.name()))
+        .filter_map(|key| key_code(key).map(|token| (key, token)))
+        .collect()
+}
+
+/// Error parsing shorthand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// There are no chords.
+    Empty,
+    /// A chord has modifiers but no key, e.g., "Ctrl-".
+    MissingKey(String),
+    /// A token does not name a key.
+    UnknownKey(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "no key chords"),
+            ParseError::MissingKey(chord) => write!(f, "no key in chord {chord:?}"),
+            ParseError::UnknownKey(key) => write!(f, "unknown key {key:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub(crate) fn parse_key_code(token: &str, table: &[(KeyCode, String)]) -> Option<KeyCode> {
+    let lookup = |token: &str| {
+        table
+            .iter()
+            .find(|(_, t)| t == token)
+            .map(|(key, _)| *key)
+    };
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        // Accept lowercase letters and unquoted brackets.
+        (Some(c), None) => lookup(&c.to_ascii_uppercase().to_string())
+            .or_else(|| lookup(&format!("'{c}'")))
+            .or_else(|| lookup(&format!("'\\{c}'"))),
+        _ => lookup(token),
+    }
+    .or_else(|| crate::chord::key_code_from_name(token))
+}
+
+fn parse_key_chord_with(token: &str, table: &[(KeyCode, String)]) -> Result<KeyChord, ParseError> {
+    let mut mods = Modifiers::empty();
+    let mut rest = token;
+    while let Some((name, tail)) = rest.split_once('-') {
+        let modifier = match name.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => Modifiers::CONTROL,
+            "alt" => Modifiers::ALT,
+            "shift" => Modifiers::SHIFT,
+            "super" => Modifiers::SUPER,
+            // The key itself may be "-".
+            _ => break,
+        };
+        mods |= modifier;
+        rest = tail;
+    }
+    if rest.is_empty() {
+        return Err(ParseError::MissingKey(token.to_string()));
+    }
+    parse_key_code(rest, table)
+        .map(|key| KeyChord(mods, key))
+        .ok_or_else(|| ParseError::UnknownKey(rest.to_string()))
+}
+
+/// Parse a key chord like "Ctrl-Shift-A". Modifiers and letters are case
+/// insensitive.
+pub fn parse_key_chord(token: &str) -> Result<KeyChord, ParseError> {
+    parse_key_chord_with(token, &key_code_table())
+}
+
+/// Parse key chords separated by whitespace, e.g., "Ctrl-W A S D". Accepts the
+/// same shorthand as the [keyseq](crate::keyseq) macro.
+pub fn parse_key_sequence(text: &str) -> Result<Vec<KeyChord>, ParseError> {
+    let table = key_code_table();
+    let acts = text
+        .split_whitespace()
+        .map(|token| parse_key_chord_with(token, &table))
+        .collect::<Result<Vec<_>, _>>()?;
+    if acts.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(acts)
+}
+
+impl KeySequence {
+    /// Create a key sequence from shorthand. Not operant until added to an
+    /// entity. See [parse_key_sequence].
+    pub fn parse<C, M>(
+        system: C,
+        text: &str,
+    ) -> Result<InputSequenceBuilder<KeyChord, C::System, ()>, ParseError>
+    where
+        C: IntoCondSystem<(), (), M> + 'static,
+    {
+        let mut builder = InputSequenceBuilder::new(system);
+        builder.acts = parse_key_sequence(text)?;
+        Ok(builder)
+    }
+}
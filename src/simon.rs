@@ -0,0 +1,198 @@
+//! Generate "Simon says" rounds
+//!
+//! A [SimonSays] round picks random chords from a pool and registers them as a
+//! key sequence the player must enter within a time limit, the core loop of
+//! Simon-style minigames and quick time events. The round's entity carries a
+//! [SimonRound] with the chords to show the player. When the round ends it
+//! sends [SimonOutcome] and is despawned. The player fails by dropping the
+//! sequence partway through or by running out of time.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, simon::{SimonOutcome, SimonRound, SimonSays}};
+//!
+//! fn start(mut commands: Commands) {
+//!     commands.queue(
+//!         SimonSays::new(keyseq! { W A S D }, 4, Duration::from_secs(5))
+//!             .intervals([Duration::from_secs(1); 3]),
+//!     );
+//! }
+//!
+//! fn show(rounds: Query<&SimonRound, Added<SimonRound>>) {
+//!     for round in &rounds {
+//!         let chords: Vec<String> = round.acts.iter().map(ToString::to_string).collect();
+//!         info!("Simon says {}", chords.join(" "));
+//!     }
+//! }
+//!
+//! fn score(mut outcomes: EventReader<SimonOutcome>) {
+//!     for outcome in outcomes.read() {
+//!         info!("{}", if outcome.success { "well done" } else { "try again" });
+//!     }
+//! }
+//! ```
+use crate::{input_sequence::KeySequence, matched::SequenceAborted, KeyChord, TimeLimit};
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        system::{Commands, EntityCommand, Query, Res},
+        world::{Command, World},
+    },
+    hierarchy::DespawnRecursiveExt,
+    time::{Real, Time},
+};
+use std::time::Duration;
+
+/// A round of "Simon says": `len` chords drawn from `pool` to be entered
+/// within `within`. Queue it as a command to start it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimonSays {
+    /// Chords to draw from
+    pub pool: Vec<KeyChord>,
+    /// Number of chords in the round
+    pub len: usize,
+    /// Time to enter the whole round
+    pub within: Duration,
+    /// Longest time between successive chords
+    pub intervals: Vec<Duration>,
+    /// Seed for drawing the chords; `None` seeds from the clock
+    pub seed: Option<u64>,
+}
+
+impl SimonSays {
+    /// Create a round of `len` chords drawn from `pool` to be entered within
+    /// `within`.
+    pub fn new<T>(pool: impl IntoIterator<Item = T>, len: usize, within: Duration) -> Self
+    where
+        KeyChord: From<T>,
+    {
+        Self {
+            pool: pool.into_iter().map(KeyChord::from).collect(),
+            len,
+            within,
+            intervals: Vec::new(),
+            seed: None,
+        }
+    }
+
+    /// Allow at most each of `windows` between successive chords.
+    pub fn intervals(mut self, windows: impl IntoIterator<Item = Duration>) -> Self {
+        self.intervals = windows.into_iter().collect();
+        self
+    }
+
+    /// Draw the chords with `seed`, e.g., to replay a round.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Draw the chords of the round with `seed`. The same seed and pool
+    /// always give the same chords. Returns nothing if the pool is empty.
+    pub fn generate(&self, seed: u64) -> Vec<KeyChord> {
+        if self.pool.is_empty() {
+            return Vec::new();
+        }
+        // xorshift64*; zero is its only fixed point.
+        let mut state = seed | 1;
+        (0..self.len)
+            .map(|_| {
+                state ^= state >> 12;
+                state ^= state << 25;
+                state ^= state >> 27;
+                let n = state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32;
+                self.pool[n as usize % self.pool.len()].clone()
+            })
+            .collect()
+    }
+}
+
+/// A round in progress.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SimonRound {
+    /// Chords the player must enter
+    pub acts: Vec<KeyChord>,
+    /// Game time at which the round fails
+    pub deadline: Duration,
+}
+
+/// Sent when a [SimonRound] ends.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct SimonOutcome {
+    /// Entity the round was on
+    pub entity: Entity,
+    /// Chords of the round
+    pub acts: Vec<KeyChord>,
+    /// True if the player entered them in time
+    pub success: bool,
+}
+
+impl Command for SimonSays {
+    fn apply(self, world: &mut World) {
+        let id = world.spawn_empty().id();
+        EntityCommand::apply(self, id, world);
+    }
+}
+
+impl EntityCommand for SimonSays {
+    fn apply(self, id: Entity, world: &mut World) {
+        let seed = self.seed.unwrap_or_else(|| {
+            world
+                .get_resource::<Time<Real>>()
+                .map(|time| time.elapsed().as_nanos() as u64)
+                .unwrap_or_default()
+        });
+        let acts = self.generate(seed);
+        let now = world
+            .get_resource::<Time>()
+            .map(|time| time.elapsed())
+            .unwrap_or_default();
+        let round = SimonRound {
+            acts: acts.clone(),
+            deadline: now + self.within,
+        };
+        let outcome = round.clone();
+        EntityCommand::apply(
+            KeySequence::new(
+                move |mut writer: EventWriter<SimonOutcome>, mut commands: Commands| {
+                    writer.send(SimonOutcome {
+                        entity: id,
+                        acts: outcome.acts.clone(),
+                        success: true,
+                    });
+                    commands.entity(id).despawn_recursive();
+                },
+                acts,
+            )
+            .time_limit(TimeLimit::Duration(self.within))
+            .intervals(self.intervals),
+            id,
+            world,
+        );
+        world.entity_mut(id).insert(round);
+    }
+}
+
+/// Fail the rounds that were dropped or ran out of time.
+pub(crate) fn fail_simon_rounds(
+    rounds: Query<(Entity, &SimonRound)>,
+    mut aborted: EventReader<SequenceAborted>,
+    mut writer: EventWriter<SimonOutcome>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let aborted: Vec<Entity> = aborted.read().map(|abort| abort.entity).collect();
+    for (id, round) in &rounds {
+        if time.elapsed() < round.deadline && !aborted.contains(&id) {
+            continue;
+        }
+        writer.send(SimonOutcome {
+            entity: id,
+            acts: round.acts.clone(),
+            success: false,
+        });
+        commands.entity(id).despawn_recursive();
+    }
+}
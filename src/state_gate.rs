@@ -0,0 +1,88 @@
+//! Only match sequences in some states
+//!
+//! Requires the "state" feature. Call
+//! [match_sequences_in](MatchSequencesIn::match_sequences_in) and sequences
+//! only match while `State<S>` is one of the given states. Leaving them drops
+//! partial matches, the same as holding a
+//! [SuppressToken](crate::guard::SuppressToken).
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, state_gate::MatchSequencesIn};
+//!
+//! #[derive(States, Clone, Debug, Default, PartialEq, Eq, Hash)]
+//! enum GameState {
+//!     #[default]
+//!     Menu,
+//!     Playing,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(bevy::state::app::StatesPlugin)
+//!    .add_plugins(InputSequencePlugin::default())
+//!    .init_state::<GameState>()
+//!    .match_sequences_in(GameState::Playing);
+//! ```
+use crate::guard::{InputSequenceGuard, SuppressToken};
+use bevy::{
+    app::App,
+    ecs::{
+        change_detection::DetectChanges,
+        schedule::IntoSystemConfigs,
+        system::{Res, ResMut, Resource},
+    },
+    state::state::{State, StateTransition, StateTransitionSteps, States},
+};
+
+/// States of `S` in which sequences match.
+#[derive(Resource, Debug)]
+pub struct SequenceStates<S: States> {
+    /// States in which sequences match.
+    pub states: Vec<S>,
+    token: Option<SuppressToken>,
+}
+
+/// Restrict sequence matching to some states.
+pub trait MatchSequencesIn {
+    /// Only match sequences while `State<S>` equals `state` or another state
+    /// given in an earlier call.
+    fn match_sequences_in<S: States>(&mut self, state: S) -> &mut Self;
+}
+
+impl MatchSequencesIn for App {
+    fn match_sequences_in<S: States>(&mut self, state: S) -> &mut Self {
+        if let Some(mut states) = self.world_mut().get_resource_mut::<SequenceStates<S>>() {
+            states.states.push(state);
+            return self;
+        }
+        self.insert_resource(SequenceStates {
+            states: vec![state],
+            token: None,
+        })
+        .init_resource::<InputSequenceGuard>()
+        .add_systems(
+            StateTransition,
+            gate_sequences::<S>.after(StateTransitionSteps::EnterSchedules),
+        )
+    }
+}
+
+fn gate_sequences<S: States>(
+    state: Option<Res<State<S>>>,
+    mut states: ResMut<SequenceStates<S>>,
+    guard: Res<InputSequenceGuard>,
+) {
+    let Some(state) = state else {
+        return;
+    };
+    if !state.is_changed() && !states.is_changed() {
+        return;
+    }
+    let allowed = states.states.contains(state.get());
+    if allowed {
+        states.token = None;
+    } else if states.token.is_none() {
+        states.token = Some(guard.suppress());
+    }
+}
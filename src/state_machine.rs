@@ -0,0 +1,151 @@
+//! Export an input sequence as an explicit state machine
+//!
+//! The plugin matches all sequences at once with a trie. For custom rendering
+//! of combo trees or for unit-testing game logic against binding definitions,
+//! it can be handier to have one sequence spelled out as states and
+//! transitions.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::prelude::*;
+//!
+//! let mut world = World::new();
+//! let seq = KeySequence::new(|| {}, keyseq! { W A S D }).build(&mut world);
+//! let machine = seq.state_machine();
+//! assert_eq!(machine.states.len(), 5);
+//! assert!(machine.accepts(keyseq! { W A S D }.map(KeyChord::from)));
+//! assert!(machine.accepts(keyseq! { W W A S D }.map(KeyChord::from)));
+//! assert!(!machine.accepts(keyseq! { W A X S D }.map(KeyChord::from)));
+//! ```
+use crate::{input_sequence::InputSequence, time_limit::TimeLimit};
+use bevy::ecs::system::SystemInput;
+
+/// The role a state plays in the state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StateKind {
+    /// Nothing has been matched.
+    Start,
+    /// Some acts have been matched.
+    Partial,
+    /// All acts have been matched; the sequence fires on entering this state.
+    Accept,
+}
+
+/// A state of the state machine.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct State {
+    /// Number of acts matched to reach this state.
+    pub matched: usize,
+    /// Role of this state.
+    pub kind: StateKind,
+}
+
+/// A transition between states on an act.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Transition<Act> {
+    /// Index of the state this transition leaves.
+    pub from: usize,
+    /// Index of the state this transition enters.
+    pub to: usize,
+    /// Act that causes this transition.
+    pub act: Act,
+}
+
+/// An input sequence as an explicit state machine.
+///
+/// State `0` is the start state and the last state accepts. Any act without an
+/// explicit transition returns to the start state. The accept state behaves
+/// like the start state since the matcher resets after firing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceStateMachine<Act> {
+    /// States in order of acts matched.
+    pub states: Vec<State>,
+    /// Explicit transitions.
+    pub transitions: Vec<Transition<Act>>,
+    /// Time limit from the first matched act to acceptance.
+    pub time_limit: Option<TimeLimit>,
+}
+
+impl<Act: Clone + PartialEq> SequenceStateMachine<Act> {
+    /// Create a state machine from a list of acts.
+    pub fn new(acts: &[Act], time_limit: Option<TimeLimit>) -> Self {
+        let n = acts.len();
+        let states = (0..=n)
+            .map(|matched| State {
+                matched,
+                kind: if matched == n {
+                    StateKind::Accept
+                } else if matched == 0 {
+                    StateKind::Start
+                } else {
+                    StateKind::Partial
+                },
+            })
+            .collect();
+        let mut transitions = Vec::new();
+        for from in 0..=n {
+            if from < n {
+                transitions.push(Transition {
+                    from,
+                    to: from + 1,
+                    act: acts[from].clone(),
+                });
+            }
+            // A mismatch resets the search and the act is tried again as the
+            // start of a new match.
+            if let Some(first) = acts.first() {
+                if from != 0 && (from == n || acts[from] != *first) {
+                    transitions.push(Transition {
+                        from,
+                        to: 1,
+                        act: first.clone(),
+                    });
+                }
+            }
+        }
+        Self {
+            states,
+            transitions,
+            time_limit,
+        }
+    }
+
+    /// Index of the start state.
+    pub fn start(&self) -> usize {
+        0
+    }
+
+    /// Index of the accept state.
+    pub fn accept(&self) -> usize {
+        self.states.len() - 1
+    }
+
+    /// Return the state entered from `state` on `act`.
+    pub fn next(&self, state: usize, act: &Act) -> usize {
+        self.transitions
+            .iter()
+            .find(|t| t.from == state && t.act == *act)
+            .map(|t| t.to)
+            .unwrap_or(self.start())
+    }
+
+    /// Return true if feeding `acts` reaches the accept state. Ignores the time
+    /// limit.
+    pub fn accepts(&self, acts: impl IntoIterator<Item = Act>) -> bool {
+        let mut state = self.start();
+        for act in acts {
+            state = self.next(state, &act);
+            if state == self.accept() {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<Act: Clone + PartialEq, I: SystemInput> InputSequence<Act, I> {
+    /// Export this sequence as an explicit state machine.
+    pub fn state_machine(&self) -> SequenceStateMachine<Act> {
+        SequenceStateMachine::new(&self.acts, self.time_limit.clone())
+    }
+}
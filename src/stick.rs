@@ -0,0 +1,236 @@
+//! Analog stick calibration and directions
+//!
+//! Stick directions are recognized from the gamepad's axis values after
+//! applying [StickCalibration]. The button matcher reads the resource every
+//! frame, so changes from a calibration screen take effect at once. It
+//! implements [Reflect], and with the "serde" feature serde's traits, so it can
+//! be saved and loaded with the rest of the user's settings.
+//!
+//! An engaged stick is quantized into one of eight [Direction]s, and each
+//! time it engages or rolls into another direction the button matcher sees a
+//! [ButtonAct::Stick](crate::button::ButtonAct::Stick), so motion inputs like
+//! a quarter-circle forward can be written as button sequences:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, button::ButtonAct, stick::{Direction, Stick}};
+//!
+//! let hadouken = ButtonSequence::new(
+//!     |In(_gamepad): In<Entity>| info!("hadouken"),
+//!     [ButtonAct::Stick(Stick::Left, Direction::Down),
+//!      ButtonAct::Stick(Stick::Left, Direction::DownRight),
+//!      ButtonAct::Stick(Stick::Left, Direction::Right),
+//!      ButtonAct::Press(GamepadButton::West)],
+//! );
+//! ```
+//!
+//! D-pad presses and left stick directions are distinct acts. Mark a sequence
+//! with the builder's
+//! [stick_as_dpad](crate::input_sequence::InputSequenceBuilder::stick_as_dpad)
+//! option to let either satisfy a step written with the other.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::stick::StickCalibration;
+//!
+//! let calibration = StickCalibration {
+//!     deadzone: 0.25,
+//!     ..default()
+//! };
+//! // Inside the deadzone the stick is at rest.
+//! assert_eq!(calibration.apply_deadzone(Vec2::new(0.1, 0.1)), Vec2::ZERO);
+//! // Outside it the magnitude is rescaled to start from zero.
+//! assert_eq!(calibration.apply_deadzone(Vec2::new(0.625, 0.0)), Vec2::new(0.5, 0.0));
+//! // The stick engages past the press threshold and stays engaged until it
+//! // falls below the release threshold.
+//! assert!(calibration.is_engaged(0.7, false));
+//! assert!(!calibration.is_engaged(0.45, false));
+//! assert!(calibration.is_engaged(0.45, true));
+//! ```
+use crate::button::ButtonAct;
+use bevy::{
+    ecs::{component::Component, reflect::ReflectResource, system::Resource},
+    input::gamepad::GamepadButton,
+    math::Vec2,
+    reflect::{std_traits::ReflectDefault, Reflect},
+};
+use std::fmt;
+
+/// An analog stick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stick {
+    /// The left stick.
+    Left,
+    /// The right stick.
+    Right,
+}
+
+impl Stick {
+    /// Return the button pressed by clicking the stick.
+    pub fn thumb_button(&self) -> GamepadButton {
+        match self {
+            Stick::Left => GamepadButton::LeftThumb,
+            Stick::Right => GamepadButton::RightThumb,
+        }
+    }
+}
+
+/// Let the left stick's cardinal directions and the D-pad satisfy each
+/// other's steps in the button sequence on this entity.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StickAsDpad;
+
+/// Return the D-pad press for a left stick direction or vice versa.
+pub(crate) fn dpad_counterpart(act: &ButtonAct) -> Option<ButtonAct> {
+    use GamepadButton::*;
+    const PAIRS: [(GamepadButton, Direction); 4] = [
+        (DPadUp, Direction::Up),
+        (DPadRight, Direction::Right),
+        (DPadDown, Direction::Down),
+        (DPadLeft, Direction::Left),
+    ];
+    PAIRS.iter().find_map(|(button, direction)| match act {
+        ButtonAct::Press(b) if b == button => Some(ButtonAct::Stick(Stick::Left, *direction)),
+        ButtonAct::Stick(Stick::Left, d) if d == direction => Some(ButtonAct::Press(*button)),
+        _ => None,
+    })
+}
+
+/// Return every way to write `acts` with D-pad presses and left stick
+/// directions swapped.
+pub(crate) fn dpad_variants(acts: &[ButtonAct]) -> Vec<Vec<ButtonAct>> {
+    let mut variants = vec![Vec::with_capacity(acts.len())];
+    for act in acts {
+        match dpad_counterpart(act) {
+            Some(other) => {
+                let mut swapped = variants.clone();
+                for variant in &mut variants {
+                    variant.push(*act);
+                }
+                for variant in &mut swapped {
+                    variant.push(other);
+                }
+                variants.extend(swapped);
+            }
+            None => {
+                for variant in &mut variants {
+                    variant.push(*act);
+                }
+            }
+        }
+    }
+    variants
+}
+
+/// One of eight stick directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Up.
+    Up,
+    /// Up and right.
+    UpRight,
+    /// Right.
+    Right,
+    /// Down and right.
+    DownRight,
+    /// Down.
+    Down,
+    /// Down and left.
+    DownLeft,
+    /// Left.
+    Left,
+    /// Up and left.
+    UpLeft,
+}
+
+impl Direction {
+    /// Directions clockwise from up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Return the direction nearest to `stick`, which must not be zero.
+    pub fn from_vec2(stick: Vec2) -> Self {
+        // Angle clockwise from up, in eighths of a turn.
+        let angle = stick.x.atan2(stick.y) / std::f32::consts::FRAC_PI_4;
+        Self::ALL[(angle.round() as i32).rem_euclid(8) as usize]
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Up => "↑",
+            Direction::UpRight => "↗",
+            Direction::Right => "→",
+            Direction::DownRight => "↘",
+            Direction::Down => "↓",
+            Direction::DownLeft => "↙",
+            Direction::Left => "←",
+            Direction::UpLeft => "↖",
+        })
+    }
+}
+
+/// Deadzone and thresholds for reading analog sticks.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StickCalibration {
+    /// Radial deadzone in `[0, 1)`. Smaller magnitudes read as zero.
+    pub deadzone: f32,
+    /// Magnitude after the deadzone at which the stick engages a direction.
+    pub press_threshold: f32,
+    /// Magnitude after the deadzone below which an engaged stick releases.
+    /// Keep it below `press_threshold` to avoid chatter.
+    pub release_threshold: f32,
+}
+
+impl Default for StickCalibration {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.15,
+            press_threshold: 0.5,
+            release_threshold: 0.4,
+        }
+    }
+}
+
+impl StickCalibration {
+    /// Remove the deadzone and rescale the remaining range to `[0, 1]`.
+    pub fn apply_deadzone(&self, stick: Vec2) -> Vec2 {
+        let length = stick.length();
+        if length <= self.deadzone {
+            return Vec2::ZERO;
+        }
+        let scaled = ((length - self.deadzone) / (1.0 - self.deadzone)).min(1.0);
+        stick * (scaled / length)
+    }
+
+    /// Return true if a stick with `magnitude` is engaged given whether it was
+    /// engaged before.
+    pub fn is_engaged(&self, magnitude: f32, was_engaged: bool) -> bool {
+        if was_engaged {
+            magnitude >= self.release_threshold
+        } else {
+            magnitude >= self.press_threshold
+        }
+    }
+
+    /// Return the direction a stick at `stick` points in given the direction
+    /// it pointed in before, or `None` if it is not engaged.
+    pub fn direction(&self, stick: Vec2, previous: Option<Direction>) -> Option<Direction> {
+        let stick = self.apply_deadzone(stick);
+        self.is_engaged(stick.length(), previous.is_some())
+            .then(|| Direction::from_vec2(stick))
+    }
+}
@@ -0,0 +1,111 @@
+//! Pen and stylus pressure acts
+//!
+//! Where the platform reports touch force, e.g., Apple Pencil on iPadOS, a
+//! touch's pressure crossing the thresholds of [StylusCalibration] becomes a
+//! [StylusAct]. Enable with
+//! [match_stylus](crate::InputSequencePlugin::match_stylus).
+//!
+//! Barrel buttons are not reported by winit, so they can't be matched yet.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, stylus::{StylusAct, StylusSequence}};
+//!
+//! fn setup(mut commands: Commands) {
+//!     // Tap lightly, then press hard to open the color picker.
+//!     commands.queue(StylusSequence::new(
+//!         || info!("color picker"),
+//!         [StylusAct::Press, StylusAct::Release, StylusAct::Press, StylusAct::HardPress],
+//!     ));
+//! }
+//! ```
+use crate::{
+    act_queue::ActQueue,
+    button::{PullLevel, TriggerCalibration},
+    input_sequence::InputSequence,
+};
+use bevy::{
+    ecs::{
+        event::EventReader,
+        reflect::ReflectResource,
+        system::{Local, Res, ResMut, Resource},
+    },
+    input::touch::{ForceTouch, TouchInput, TouchPhase},
+    prelude::{Deref, DerefMut},
+    reflect::{std_traits::ReflectDefault, Reflect},
+};
+use std::collections::HashMap;
+
+/// An act of a stylus sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum StylusAct {
+    /// Pressure rises past the press threshold.
+    Press,
+    /// Pressure rises past the hard press threshold.
+    HardPress,
+    /// Pressure falls below the release threshold or the stylus lifts.
+    Release,
+}
+
+/// Represents a stylus sequence
+pub type StylusSequence = InputSequence<StylusAct, ()>;
+
+/// Pressure thresholds for stylus acts, as fractions of the most pressure
+/// the device reports.
+#[derive(Resource, Clone, Debug, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Resource, Default)]
+pub struct StylusCalibration(pub TriggerCalibration);
+
+impl Default for StylusCalibration {
+    fn default() -> Self {
+        Self(TriggerCalibration {
+            soft_press: 0.2,
+            soft_release: 0.1,
+            hard_press: 0.8,
+            hard_release: 0.7,
+        })
+    }
+}
+
+/// Return the pressure of a touch from 0 to 1.
+fn pressure(force: &ForceTouch) -> f32 {
+    match force {
+        ForceTouch::Calibrated {
+            force,
+            max_possible_force,
+            ..
+        } => (force / max_possible_force) as f32,
+        ForceTouch::Normalized(force) => *force as f32,
+    }
+}
+
+/// Turn the pressure of touches that report force into acts.
+pub(crate) fn queue_stylus_acts(
+    mut touches: EventReader<TouchInput>,
+    calibration: Res<StylusCalibration>,
+    mut levels: Local<HashMap<u64, PullLevel>>,
+    mut queue: ResMut<ActQueue<StylusAct>>,
+) {
+    for touch in touches.read() {
+        let level = levels.entry(touch.id).or_default();
+        let new = match (touch.phase, &touch.force) {
+            (TouchPhase::Ended | TouchPhase::Canceled, _) => PullLevel::Rest,
+            (_, Some(force)) => calibration.level(pressure(force), *level),
+            // Fingers and pens without pressure.
+            (_, None) => continue,
+        };
+        if *level == PullLevel::Rest && new >= PullLevel::Soft {
+            queue.push_back(StylusAct::Press);
+        }
+        if *level < PullLevel::Hard && new == PullLevel::Hard {
+            queue.push_back(StylusAct::HardPress);
+        }
+        if *level >= PullLevel::Soft && new == PullLevel::Rest {
+            queue.push_back(StylusAct::Release);
+        }
+        *level = new;
+        if matches!(touch.phase, TouchPhase::Ended | TouchPhase::Canceled) {
+            levels.remove(&touch.id);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Classify sequences with tags and act on them in bulk
+//!
+//! A sequence has at most one [Namespace](crate::namespace::Namespace), the
+//! crate that registered it, but any number of [Tags], e.g., "combat" and
+//! "tutorial", added with the builder's
+//! [tag](crate::input_sequence::InputSequenceBuilder::tag) option. The
+//! commands [DisableTag], [EnableTag], and [ResetTag] act on every sequence
+//! with a tag, and [TagStats] counts how often each tag's sequences fired.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, tag::{DisableTag, EnableTag, TagStats}};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(
+//!         KeySequence::new(|| info!("hadouken"), keyseq! { ArrowDown ArrowRight P })
+//!             .tag("combat"),
+//!     );
+//! }
+//!
+//! fn open_menu(mut commands: Commands) {
+//!     commands.queue(DisableTag::new("combat"));
+//! }
+//!
+//! fn close_menu(mut commands: Commands, stats: Res<TagStats>) {
+//!     commands.queue(EnableTag::new("combat"));
+//!     info!("{} combat moves so far", stats.fired("combat"));
+//! }
+//! ```
+use crate::{
+    disabled::{reset_sequences, Disabled},
+    matched::SequenceComplete,
+};
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    observer::Trigger,
+    system::{Query, ResMut, Resource},
+    world::{Command, World},
+};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+};
+
+/// Tags of the sequences on this entity.
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tags(pub BTreeSet<Cow<'static, str>>);
+
+impl Tags {
+    /// Return true if `tag` is among the tags.
+    pub fn contains(&self, tag: &str) -> bool {
+        self.0.contains(tag)
+    }
+}
+
+/// How many times the sequences of each tag fired.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TagStats(HashMap<Cow<'static, str>, usize>);
+
+impl TagStats {
+    /// Return how many times sequences tagged `tag` fired.
+    pub fn fired(&self, tag: &str) -> usize {
+        self.0.get(tag).copied().unwrap_or(0)
+    }
+}
+
+/// Count a completed sequence under each of its tags.
+pub(crate) fn count_tagged(
+    trigger: Trigger<SequenceComplete>,
+    tags: Query<&Tags>,
+    mut stats: ResMut<TagStats>,
+) {
+    if let Ok(tags) = tags.get(trigger.entity()) {
+        for tag in &tags.0 {
+            *stats.0.entry(tag.clone()).or_default() += 1;
+        }
+    }
+}
+
+fn tagged(world: &mut World, tag: &str) -> Vec<Entity> {
+    world
+        .query::<(Entity, &Tags)>()
+        .iter(world)
+        .filter(|(_, tags)| tags.contains(tag))
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// Insert [Disabled] on every entity with this tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisableTag(pub Cow<'static, str>);
+
+impl DisableTag {
+    /// Disable the sequences tagged `tag`.
+    pub fn new(tag: impl Into<Cow<'static, str>>) -> Self {
+        Self(tag.into())
+    }
+}
+
+impl Command for DisableTag {
+    fn apply(self, world: &mut World) {
+        for id in tagged(world, &self.0) {
+            world.entity_mut(id).insert(Disabled);
+        }
+    }
+}
+
+/// Remove [Disabled] from every entity with this tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnableTag(pub Cow<'static, str>);
+
+impl EnableTag {
+    /// Enable the sequences tagged `tag`.
+    pub fn new(tag: impl Into<Cow<'static, str>>) -> Self {
+        Self(tag.into())
+    }
+}
+
+impl Command for EnableTag {
+    fn apply(self, world: &mut World) {
+        for id in tagged(world, &self.0) {
+            world.entity_mut(id).remove::<Disabled>();
+        }
+    }
+}
+
+/// Drop the partial matches of the sequences with this tag, as though they
+/// were just added. Key sequences share their partial match, so resetting one
+/// resets them all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResetTag(pub Cow<'static, str>);
+
+impl ResetTag {
+    /// Reset the sequences tagged `tag`.
+    pub fn new(tag: impl Into<Cow<'static, str>>) -> Self {
+        Self(tag.into())
+    }
+}
+
+impl Command for ResetTag {
+    fn apply(self, world: &mut World) {
+        for id in tagged(world, &self.0) {
+            reset_sequences(world, id);
+        }
+    }
+}
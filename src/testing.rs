@@ -0,0 +1,104 @@
+//! Test key bindings in a few lines
+//!
+//! These helpers press keys directly on the `ButtonInput<KeyCode>` resource,
+//! so use them with an app that does not run bevy's `InputPlugin`, e.g., one
+//! built with `MinimalPlugins`.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, assert_sequence_fires, assert_sequence_does_not_fire};
+//!
+//! #[derive(Event, Clone)]
+//! struct Dash;
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!     .add_plugins(InputSequencePlugin::default().match_key(true))
+//!     .init_resource::<ButtonInput<KeyCode>>()
+//!     .add_event::<Dash>();
+//! app.world_mut().commands().queue(
+//!     KeySequence::new(action::send_event(Dash), keyseq! { Shift-D D }));
+//! app.update();
+//!
+//! assert_sequence_fires!(app, keyseq! { Shift-D D }, Dash);
+//! assert_sequence_does_not_fire!(app, keyseq! { D D }, Dash);
+//! ```
+use crate::{KeyChord, Modifiers};
+use bevy::{
+    app::App,
+    ecs::event::{Event, Events},
+    input::{keyboard::KeyCode, ButtonInput},
+};
+
+/// Return the left-hand keys that produce `mods`. Game-defined modifiers have
+/// no keys.
+pub fn modifier_keys(mods: Modifiers) -> Vec<KeyCode> {
+    [
+        (Modifiers::CONTROL, KeyCode::ControlLeft),
+        (Modifiers::ALT, KeyCode::AltLeft),
+        (Modifiers::SHIFT, KeyCode::ShiftLeft),
+        (Modifiers::SUPER, KeyCode::SuperLeft),
+    ]
+    .into_iter()
+    .filter(|(modifier, _)| mods.contains(*modifier))
+    .map(|(_, key)| key)
+    .collect()
+}
+
+/// Press and release each chord for one update and return how many `E`
+/// events were sent meanwhile.
+pub fn type_chords<E: Event>(
+    app: &mut App,
+    chords: impl IntoIterator<Item = impl Into<KeyChord>>,
+) -> usize {
+    let mut cursor = app.world().resource::<Events<E>>().get_cursor_current();
+    let mut sent = 0;
+    for chord in chords {
+        let KeyChord(mods, key) = chord.into();
+        let mut keys = modifier_keys(mods);
+        keys.push(key);
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        for key in &keys {
+            input.press(*key);
+        }
+        app.update();
+        sent += cursor.read(app.world().resource::<Events<E>>()).count();
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        for key in &keys {
+            input.clear_just_pressed(*key);
+            input.release(*key);
+        }
+    }
+    sent
+}
+
+/// Assert that typing the chords sends at least one event of the given type.
+///
+/// See the [testing](crate::testing) module for an example.
+#[macro_export]
+macro_rules! assert_sequence_fires {
+    ($app:expr, $chords:expr, $event:ty) => {
+        assert!(
+            $crate::testing::type_chords::<$event>(&mut $app, $chords) > 0,
+            "expected {} from {}",
+            stringify!($event),
+            stringify!($chords)
+        )
+    };
+}
+
+/// Assert that typing the chords sends no event of the given type.
+///
+/// See the [testing](crate::testing) module for an example.
+#[macro_export]
+macro_rules! assert_sequence_does_not_fire {
+    ($app:expr, $chords:expr, $event:ty) => {
+        assert_eq!(
+            $crate::testing::type_chords::<$event>(&mut $app, $chords),
+            0,
+            "expected no {} from {}",
+            stringify!($event),
+            stringify!($chords)
+        )
+    };
+}
@@ -0,0 +1,53 @@
+//! Match sequences of typed characters
+//!
+//! A [CharSequence] matches the characters keys produce, read from the
+//! `logical_key` of `KeyboardInput` events, rather than the physical keys
+//! pressed. Typing "idkfa" therefore works on any keyboard layout without
+//! learning it first, unlike
+//! [KeySequence::from_text](crate::input_sequence::KeySequence::from_text).
+//! Enable with [match_text](crate::InputSequencePlugin::match_text).
+//!
+//! Characters match exactly, so "A" and "a" are different acts. Key repeats
+//! are ignored.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, text::CharSequence};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.queue(CharSequence::new(|| info!("all weapons"), "idkfa".chars()));
+//! }
+//!
+//! App::new()
+//!     .add_plugins(MinimalPlugins)
+//!     .add_plugins(InputSequencePlugin::default().match_text(true))
+//!     .add_systems(Startup, setup);
+//! ```
+use crate::{act_queue::ActQueue, input_sequence::InputSequence};
+use bevy::{
+    ecs::{event::EventReader, system::ResMut},
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+};
+
+/// Represents a sequence of typed characters
+pub type CharSequence = InputSequence<char, ()>;
+
+/// Queue the characters typed this update.
+pub(crate) fn queue_char_acts(
+    mut inputs: EventReader<KeyboardInput>,
+    mut queue: ResMut<ActQueue<char>>,
+) {
+    for input in inputs.read() {
+        if input.state != ButtonState::Pressed || input.repeat {
+            continue;
+        }
+        match &input.logical_key {
+            Key::Character(s) => queue.extend(s.chars()),
+            Key::Space => queue.push_back(' '),
+            _ => {}
+        }
+    }
+}
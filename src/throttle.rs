@@ -0,0 +1,118 @@
+//! Ignore presses from a device that exceeds an act rate
+//!
+//! Turbo controllers and scripted input can press faster than any player.
+//! Insert an [ActThrottle] resource and each device—the keyboard or a
+//! gamepad—may only contribute [max_acts](ActThrottle::max_acts) presses per
+//! [window](ActThrottle::window); the rest never reach the matchers.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, throttle::ActThrottle};
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!    .add_plugins(InputSequencePlugin::default())
+//!    .insert_resource(ActThrottle::new(2));
+//! ```
+use crate::dedupe::DuplicatePresses;
+use bevy::{
+    core::FrameCount,
+    ecs::{
+        entity::Entity,
+        system::{Query, Res, ResMut, Resource},
+    },
+    input::{gamepad::Gamepad, keyboard::KeyCode, ButtonInput},
+    time::{Real, Time},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+/// Limit how many presses each device may make in a window of time.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct ActThrottle {
+    /// Most presses accepted from one device within the window.
+    pub max_acts: usize,
+    /// Length of the window in real time.
+    pub window: Duration,
+}
+
+impl ActThrottle {
+    /// Accept at most `max_acts` presses per device every 10 ms.
+    pub fn new(max_acts: usize) -> Self {
+        Self {
+            max_acts,
+            window: Duration::from_millis(10),
+        }
+    }
+
+    /// Set the window.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+}
+
+/// Times of the presses accepted from each device. `None` is the keyboard.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct ThrottleHistory {
+    frame: Option<u32>,
+    accepted: HashMap<Option<Entity>, VecDeque<Duration>>,
+}
+
+impl ThrottleHistory {
+    /// Return true if a press from `device` at `now` is within the limit, and
+    /// record it if so.
+    fn accept(&mut self, throttle: &ActThrottle, device: Option<Entity>, now: Duration) -> bool {
+        let accepted = self.accepted.entry(device).or_default();
+        while accepted
+            .front()
+            .is_some_and(|t| now.saturating_sub(*t) >= throttle.window)
+        {
+            accepted.pop_front();
+        }
+        if accepted.len() >= throttle.max_acts {
+            return false;
+        }
+        accepted.push_back(now);
+        true
+    }
+}
+
+/// Drop the presses over the limit this frame. Runs once per frame even if
+/// added to several schedules.
+pub(crate) fn throttle_presses(
+    throttle: Option<Res<ActThrottle>>,
+    frame_count: Res<FrameCount>,
+    real_time: Res<Time<Real>>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut history: ResMut<ThrottleHistory>,
+    mut duplicates: ResMut<DuplicatePresses>,
+) {
+    let Some(throttle) = throttle else {
+        return;
+    };
+    if history.frame == Some(frame_count.0) {
+        return;
+    }
+    history.frame = Some(frame_count.0);
+    let now = real_time.elapsed();
+    if let Some(keys) = keys {
+        for key in keys.get_just_pressed() {
+            if !duplicates.keys.contains(key) && !history.accept(&throttle, None, now) {
+                duplicates.keys.insert(*key);
+            }
+        }
+    }
+    for (id, gamepad) in &gamepads {
+        for button in gamepad.get_just_pressed() {
+            if !duplicates.buttons.contains(&(id, *button))
+                && !history.accept(&throttle, Some(id), now)
+            {
+                duplicates.buttons.insert((id, *button));
+            }
+        }
+    }
+}
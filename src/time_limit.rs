@@ -1,12 +1,21 @@
 use bevy::{reflect::Reflect};
 use std::time::Duration;
-/// A time limit specified as frame counts or duration.
-#[derive(Clone, Debug, Reflect)]
+/// A time limit specified as frame counts or duration. More kinds may be
+/// added, so matches on it need a wildcard arm.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum TimeLimit {
-    /// Time limit for frame count
+    /// Time limit for frame count. Frames while virtual time is paused don't
+    /// count.
     Frames(u32),
-    /// Time limit for duration
+    /// Time limit for duration of game time. Measured with the schedule's
+    /// `Time`, so it stretches when virtual time is slowed down or paused.
     Duration(Duration),
+    /// Time limit for duration of real time, unaffected by the relative speed
+    /// of virtual time. Real time spent with virtual time paused doesn't
+    /// count.
+    RealDuration(Duration),
 }
 
 impl From<Duration> for TimeLimit {
@@ -0,0 +1,78 @@
+//! Forgive wrong inputs in a key sequence
+//!
+//! Tutorial prompts can be forgiving. A key sequence with a [Tolerance] skips
+//! over up to that many wrong inputs once it has started instead of resetting.
+//! The number of wrong inputs is reported in
+//! [Matched::mistakes](crate::matched::Matched::mistakes).
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     // Also matches W A X S D.
+//!     commands.queue(
+//!         KeySequence::new(|| info!("nice"), keyseq! { W A S D }).tolerate(1));
+//! }
+//! ```
+//!
+//! Key sequences are [MatchMode::Strict] by default: any wrong input resets a
+//! started sequence. A [MatchMode::Lenient] sequence ignores every input that
+//! doesn't continue it.
+use crate::frame_time::FrameTime;
+use bevy::{ecs::component::Component, reflect::Reflect};
+
+/// Most wrong inputs a started key sequence forgives before it resets.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub struct Tolerance(pub u32);
+
+/// How a started key sequence treats inputs that don't continue it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum MatchMode {
+    /// Reset on any wrong input.
+    #[default]
+    Strict,
+    /// Ignore wrong inputs, i.e., an unlimited [Tolerance].
+    Lenient,
+}
+
+/// How far a tolerant sequence has gotten.
+#[derive(Clone, Debug)]
+pub(crate) struct Progress {
+    pub(crate) matched: usize,
+    pub(crate) mistakes: u32,
+    pub(crate) start: FrameTime,
+}
+
+impl Progress {
+    /// Advance by `act` and return true if the sequence is complete. A wrong
+    /// act before the sequence starts is not a mistake.
+    pub(crate) fn advance<Act: PartialEq>(
+        progress: &mut Option<Progress>,
+        acts: &[Act],
+        tolerance: Tolerance,
+        act: &Act,
+        now: &FrameTime,
+    ) -> bool {
+        let start = |now: &FrameTime| Progress {
+            matched: 1,
+            mistakes: 0,
+            start: now.clone(),
+        };
+        match progress {
+            None => {
+                if acts.first() == Some(act) {
+                    *progress = Some(start(now));
+                }
+            }
+            Some(p) if acts.get(p.matched) == Some(act) => p.matched += 1,
+            Some(p) => {
+                p.mistakes = p.mistakes.saturating_add(1);
+                if p.mistakes > tolerance.0 {
+                    *progress = (acts.first() == Some(act)).then(|| start(now));
+                }
+            }
+        }
+        progress.as_ref().is_some_and(|p| p.matched == acts.len())
+    }
+}
@@ -0,0 +1,84 @@
+//! Drive a tutorial overlay from a key sequence's progress
+//!
+//! Mark a key sequence with the builder's
+//! [tutorial](crate::input_sequence::InputSequenceBuilder::tutorial) option
+//! and its entity gets a [TutorialHint] that is kept current as the player
+//! types: how many chords they have matched, which one comes next, and a
+//! ready-made prompt.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_input_sequence::{prelude::*, tutorial::TutorialHint};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn_empty().queue(
+//!         KeySequence::new(|| info!("dash"), keyseq! { D D }).tutorial());
+//! }
+//!
+//! fn show(hints: Query<&TutorialHint, Changed<TutorialHint>>) {
+//!     for hint in &hints {
+//!         info!("{}", hint.prompt);
+//!     }
+//! }
+//! ```
+use crate::{cache::KeySequenceCache, input_sequence::KeySequence, KeyChord};
+use bevy::ecs::{
+    component::Component,
+    query::With,
+    system::{Query, Res},
+};
+
+/// Mark a key sequence whose progress should be reported in a
+/// [TutorialHint].
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[require(TutorialHint)]
+pub struct Tutorial;
+
+/// Progress of a [Tutorial] sequence.
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TutorialHint {
+    /// Chords matched so far.
+    pub step: usize,
+    /// Chords in the sequence.
+    pub total: usize,
+    /// Chord expected next.
+    pub next: Option<KeyChord>,
+    /// Prompt for the next chord, e.g., "Press Ctrl-S (2/3)".
+    pub prompt: String,
+}
+
+impl TutorialHint {
+    /// Describe a sequence of `acts` after `step` of them have matched.
+    pub fn new(acts: &[KeyChord], step: usize) -> Self {
+        let next = acts.get(step).cloned();
+        let prompt = next
+            .as_ref()
+            .map(|chord| format!("Press {chord} ({}/{})", step + 1, acts.len()))
+            .unwrap_or_default();
+        Self {
+            step,
+            total: acts.len(),
+            next,
+            prompt,
+        }
+    }
+}
+
+/// Update the hints of tutorial sequences from the partial match.
+pub(crate) fn update_tutorial_hints(
+    mut sequences: Query<(&KeySequence, &mut TutorialHint), With<Tutorial>>,
+    cache: Res<KeySequenceCache>,
+) {
+    let prefix = cache.prefix();
+    for (sequence, mut hint) in &mut sequences {
+        let step = if sequence.acts.starts_with(&prefix) {
+            prefix.len()
+        } else {
+            0
+        };
+        let new = TutorialHint::new(&sequence.acts, step);
+        if *hint != new {
+            *hint = new;
+        }
+    }
+}
@@ -23,6 +23,21 @@ fn test_keyseq_macro() {
     );
 }
 
+// The macros already follow the key code names of Bevy 0.13 and later.
+#[test]
+fn keyseq_macro_key_code_names() {
+    assert_eq!(
+        [
+            (Modifiers::empty(), KeyCode::KeyA),
+            (Modifiers::empty(), KeyCode::Digit1),
+            (Modifiers::empty(), KeyCode::Semicolon),
+            (Modifiers::empty(), KeyCode::BracketLeft),
+            (Modifiers::empty(), KeyCode::Backquote),
+        ],
+        keyseq! { A 1 ; '[' '`' }
+    );
+}
+
 #[test]
 fn eq_if_contains_key_in_lhs() {
     let lhs = KeyChord(Modifiers::CONTROL, KeyCode::KeyA);
@@ -209,3 +224,212 @@ fn test_key_eq_vec() {
     assert!(e != b);
     assert!(e != c);
 }
+
+#[test]
+fn key_chord_from_web_key_value() {
+    assert_eq!(
+        KeyChord::from_web_key_value("a"),
+        Some(KeyChord(Modifiers::empty(), KeyCode::KeyA))
+    );
+    assert_eq!(
+        KeyChord::from_web_key_value("A"),
+        Some(KeyChord(Modifiers::SHIFT, KeyCode::KeyA))
+    );
+    assert_eq!(
+        KeyChord::from_web_key_value("7"),
+        Some(KeyChord(Modifiers::empty(), KeyCode::Digit7))
+    );
+    assert_eq!(
+        KeyChord::from_web_key_value(" "),
+        Some(KeyChord(Modifiers::empty(), KeyCode::Space))
+    );
+    assert_eq!(
+        KeyChord::from_web_key_value("F12"),
+        Some(KeyChord(Modifiers::empty(), KeyCode::F12))
+    );
+    assert_eq!(
+        KeyChord::from_web_key_value("Control"),
+        Some(KeyChord(Modifiers::empty(), KeyCode::ControlLeft))
+    );
+    assert_eq!(KeyChord::from_web_key_value("NoSuchKey"), None);
+    assert_eq!(KeyChord::from_web_key_value("é"), None);
+}
+
+#[test]
+fn key_chord_from_logical_key() {
+    use bevy::input::keyboard::Key;
+    assert_eq!(
+        KeyChord::try_from(&Key::ArrowLeft),
+        Ok(KeyChord(Modifiers::empty(), KeyCode::ArrowLeft))
+    );
+    assert_eq!(
+        KeyChord::try_from(&Key::Character(";".into())),
+        Ok(KeyChord(Modifiers::empty(), KeyCode::Semicolon))
+    );
+    assert_eq!(
+        KeyChord::try_from(&Key::Space),
+        Ok(KeyChord(Modifiers::empty(), KeyCode::Space))
+    );
+    assert_eq!(KeyChord::try_from(&Key::Dead(None)), Err(()));
+}
+
+#[test]
+fn shorthand_table() {
+    use bevy_input_sequence::shorthand;
+    use std::collections::HashSet;
+    let table = shorthand::key_code_table();
+    assert!(table.contains(&(KeyCode::KeyA, "A".to_string())));
+    assert!(table.contains(&(KeyCode::Digit0, "0".to_string())));
+    assert!(table.contains(&(KeyCode::F1, "F1".to_string())));
+    assert!(table.contains(&(KeyCode::Backslash, "'\\\\'".to_string())));
+    let tokens: HashSet<_> = table.iter().map(|(_, token)| token).collect();
+    assert_eq!(tokens.len(), table.len());
+    assert_eq!(
+        shorthand::key_chord(&KeyChord(
+            bevy_input_sequence::modifier::CUSTOM_1,
+            KeyCode::KeyA
+        )),
+        None
+    );
+    assert_eq!(
+        shorthand::logical_key(&bevy::input::keyboard::Key::Character("q".into())).as_deref(),
+        Some("q")
+    );
+}
+
+#[test]
+fn shorthand_parse() {
+    use bevy_input_sequence::shorthand::{self, ParseError};
+    let acts: Vec<KeyChord> = keyseq! { Ctrl-Alt-Delete '\\' Shift-A F12 ']' }
+        .into_iter()
+        .map(KeyChord::from)
+        .collect();
+    assert_eq!(
+        shorthand::parse_key_sequence(r"ctrl-alt-Delete '\\' shift-a F12 ]"),
+        Ok(acts.clone())
+    );
+    assert_eq!(
+        shorthand::parse_key_sequence(&shorthand::key_sequence(&acts).unwrap()),
+        Ok(acts)
+    );
+    assert_eq!(
+        shorthand::parse_key_chord("Ctrl--"),
+        Ok(KeyChord(Modifiers::CONTROL, KeyCode::Minus))
+    );
+    assert_eq!(shorthand::parse_key_chord("\\"), Ok(KeyChord::from(KeyCode::Backslash)));
+    assert_eq!(shorthand::parse_key_sequence("  "), Err(ParseError::Empty));
+    assert_eq!(
+        shorthand::parse_key_chord("Ctrl-"),
+        Err(ParseError::MissingKey("Ctrl-".into()))
+    );
+    assert_eq!(
+        shorthand::parse_key_chord("Hyper-A"),
+        Err(ParseError::UnknownKey("Hyper-A".into()))
+    );
+}
+
+#[test]
+fn import_bindings() {
+    use bevy_input_sequence::{import, shorthand::ParseError};
+    let chords = |acts: &[(Modifiers, KeyCode)]| -> Vec<KeyChord> {
+        acts.iter().copied().map(KeyChord::from).collect()
+    };
+    assert_eq!(
+        import::vscode("ctrl+k ctrl+shift+b cmd+[ f5 numpad0"),
+        Ok(chords(&[
+            (Modifiers::CONTROL, KeyCode::KeyK),
+            (Modifiers::CONTROL | Modifiers::SHIFT, KeyCode::KeyB),
+            (Modifiers::SUPER, KeyCode::BracketLeft),
+            (Modifiers::empty(), KeyCode::F5),
+            (Modifiers::empty(), KeyCode::Numpad0),
+        ]))
+    );
+    assert_eq!(
+        import::emacs("C-x C-s M-<f1> C-- X RET DEL"),
+        Ok(chords(&[
+            (Modifiers::CONTROL, KeyCode::KeyX),
+            (Modifiers::CONTROL, KeyCode::KeyS),
+            (Modifiers::ALT, KeyCode::F1),
+            (Modifiers::CONTROL, KeyCode::Minus),
+            (Modifiers::SHIFT, KeyCode::KeyX),
+            (Modifiers::empty(), KeyCode::Enter),
+            (Modifiers::empty(), KeyCode::Backspace),
+        ]))
+    );
+    assert_eq!(import::vscode("ctrl+"), Err(ParseError::MissingKey("ctrl+".into())));
+    assert_eq!(import::vscode("hyper+a"), Err(ParseError::UnknownKey("hyper".into())));
+    assert_eq!(import::emacs("C-<bogus>"), Err(ParseError::UnknownKey("<bogus>".into())));
+    assert_eq!(import::emacs(""), Err(ParseError::Empty));
+}
+
+#[test]
+fn export_bindings() {
+    use bevy_input_sequence::{export, import};
+    let acts: Vec<KeyChord> = [
+        (Modifiers::CONTROL, KeyCode::KeyK),
+        (Modifiers::CONTROL | Modifiers::SHIFT, KeyCode::KeyB),
+        (Modifiers::ALT, KeyCode::F1),
+        (Modifiers::SHIFT, KeyCode::Enter),
+        (Modifiers::SUPER, KeyCode::BracketLeft),
+        (Modifiers::empty(), KeyCode::Numpad7),
+        (Modifiers::empty(), KeyCode::ArrowUp),
+    ]
+    .into_iter()
+    .map(KeyChord::from)
+    .collect();
+    let vscode = export::vscode(&acts).unwrap();
+    assert_eq!(vscode, "ctrl+k ctrl+shift+b alt+f1 shift+enter meta+[ numpad7 up");
+    assert_eq!(import::vscode(&vscode), Ok(acts.clone()));
+    let emacs = export::emacs(&acts).unwrap();
+    assert_eq!(emacs, "C-k C-B M-<f1> S-RET s-[ <kp-7> <up>");
+    assert_eq!(import::emacs(&emacs), Ok(acts));
+
+    let custom = [KeyChord(Modifiers::from_bits_retain(0x10), KeyCode::KeyA)];
+    assert_eq!(export::vscode(&custom), None);
+}
+
+#[test]
+fn key_chord_region_display() {
+    use bevy_input_sequence::region::KeyboardRegion;
+    let chord = KeyChord(Modifiers::SHIFT, KeyCode::BracketLeft);
+    assert_eq!(chord.display(KeyboardRegion::Ansi).to_string(), "Shift-[");
+    assert_eq!(chord.display(KeyboardRegion::Iso).to_string(), "Shift-[");
+    assert_eq!(chord.display(KeyboardRegion::Jis).to_string(), "Shift-@");
+    assert_eq!(
+        KeyChord::from(KeyCode::KeyA).display(KeyboardRegion::Jis).to_string(),
+        KeyChord::from(KeyCode::KeyA).to_string()
+    );
+    assert_eq!(KeyboardRegion::Iso.label(KeyCode::IntlBackslash), "\\");
+}
+
+#[test]
+fn fingerprints() {
+    use bevy_input_sequence::{
+        fingerprint::fingerprint,
+        input_sequence::{KeySequence, Press},
+        keymap::{Binding, Keymap},
+    };
+    use std::time::Duration;
+    let mut world = World::new();
+    let save = KeySequence::new(|| {}, keyseq! { Ctrl-X Ctrl-S }).build(&mut world);
+    let same = KeySequence::new(|| {}, keyseq! { Ctrl-X Ctrl-S })
+        .press(0, Press::JustPressed)
+        .build(&mut world);
+    let slow = KeySequence::new(|| {}, keyseq! { Ctrl-X Ctrl-S })
+        .time_limit(Duration::from_secs(1))
+        .build(&mut world);
+    assert_eq!(fingerprint(&save), fingerprint(&same));
+    assert_ne!(fingerprint(&save), fingerprint(&slow));
+    // The same in every process.
+    assert_eq!(fingerprint(&save), 0x37da_bcad_d74f_c7a6);
+
+    let keymap = |name: &str| Keymap {
+        bindings: vec![Binding {
+            name: name.into(),
+            acts: save.acts.clone(),
+            time_limit: None,
+        }],
+    };
+    assert_eq!(fingerprint(&keymap("save")), fingerprint(&keymap("save")));
+    assert_ne!(fingerprint(&keymap("save")), fingerprint(&keymap("quit")));
+}
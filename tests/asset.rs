@@ -0,0 +1,46 @@
+#![cfg(feature = "asset")]
+use bevy::prelude::*;
+use bevy_input_sequence::{
+    asset::{AssetSequences, SequenceAsset, SequenceAssetPlugin},
+    prelude::*,
+    testing::type_chords,
+};
+
+#[derive(Event, Clone)]
+struct GodMode;
+
+#[test]
+fn respawn_on_change() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(AssetPlugin::default())
+        .init_resource::<ButtonInput<KeyCode>>()
+        .add_plugins(InputSequencePlugin::default())
+        .add_plugins(SequenceAssetPlugin)
+        .add_event::<GodMode>();
+    let keymap = ron::from_str(r#"(bindings: [(name: "god mode", acts: ["I", "D", "D", "Q", "D"])])"#)
+        .unwrap();
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<SequenceAsset>>()
+        .add(SequenceAsset(keymap));
+    app.world_mut()
+        .spawn(AssetSequences::new(handle.clone()).event("god mode", GodMode));
+    app.update();
+    app.update();
+    assert_eq!(type_chords::<GodMode>(&mut app, keyseq! { I D D Q D }), 1);
+
+    // Editing the asset replaces its sequences.
+    app.world_mut()
+        .resource_mut::<Assets<SequenceAsset>>()
+        .get_mut(&handle)
+        .unwrap()
+        .0
+        .bindings[0]
+        .acts = keyseq! { I D K F A }.into_iter().map(KeyChord::from).collect();
+    app.update();
+    app.update();
+    assert_eq!(type_chords::<GodMode>(&mut app, keyseq! { I D D Q D }), 0);
+    assert_eq!(type_chords::<GodMode>(&mut app, keyseq! { I D K F A }), 1);
+    assert_eq!(app.world_mut().query::<&KeySequence>().iter(app.world()).count(), 1);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "ui")]
+use bevy::{color::palettes::css, ecs::system::EntityCommand, prelude::*};
+use bevy_input_sequence::{hint::KeySequenceHint, prelude::*};
+
+fn colors(app: &mut App, hint: Entity) -> Vec<Color> {
+    let world = app.world();
+    world
+        .get::<Children>(hint)
+        .unwrap()
+        .iter()
+        .map(|child| world.get::<TextColor>(*child).unwrap().0)
+        .collect()
+}
+
+#[test]
+fn hint_shows_progress() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputSequencePlugin::default().match_key(true))
+        .init_resource::<ButtonInput<KeyCode>>();
+    let seq = app.world_mut().spawn_empty().id();
+    EntityCommand::apply(KeySequence::new(|| {}, keyseq! { A B C }), seq, app.world_mut());
+    let hint = app.world_mut().spawn(KeySequenceHint::new(seq)).id();
+    app.update();
+    let gray: Color = css::GRAY.into();
+    let gold: Color = css::GOLD.into();
+    assert_eq!(colors(&mut app, hint), vec![gray, gray, gray]);
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::KeyA);
+    app.update();
+    assert_eq!(colors(&mut app, hint), vec![gold, gray, gray]);
+}
+
+#[test]
+fn hint_follows_sequence() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputSequencePlugin::default().match_key(true))
+        .init_resource::<ButtonInput<KeyCode>>();
+    let seq = app.world_mut().spawn_empty().id();
+    let hint = app.world_mut().spawn(KeySequenceHint::new(seq)).id();
+    app.update();
+    assert!(app.world().get::<Children>(hint).is_none());
+
+    // The sequence is added after the hint.
+    EntityCommand::apply(KeySequence::new(|| {}, keyseq! { A B C }), seq, app.world_mut());
+    app.update();
+    assert_eq!(colors(&mut app, hint).len(), 3);
+
+    // The sequence changes.
+    app.world_mut().get_mut::<KeySequence>(seq).unwrap().acts.pop();
+    app.update();
+    assert_eq!(colors(&mut app, hint).len(), 2);
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use bevy_input_sequence::{
+    keymap::{Binding, Keymap, KeymapError},
+    modifier, prelude::*,
+};
+
+fn keymap() -> Keymap {
+    Keymap {
+        bindings: vec![
+            Binding {
+                name: "dash".into(),
+                acts: keyseq! { D D }.into_iter().map(KeyChord::from).collect(),
+                time_limit: Some(TimeLimit::Frames(300)),
+            },
+            Binding {
+                name: "aimed shot".into(),
+                acts: vec![KeyChord(modifier::CUSTOM_1 | Modifiers::SHIFT, KeyCode::KeyF)],
+                time_limit: None,
+            },
+            Binding {
+                name: "konami".into(),
+                acts: keyseq! { ArrowUp ArrowUp ArrowDown ArrowDown ArrowLeft ArrowRight ArrowLeft ArrowRight B A }
+                    .into_iter()
+                    .map(KeyChord::from)
+                    .collect(),
+                time_limit: Some(Duration::from_millis(2500).into()),
+            },
+        ],
+    }
+}
+
+#[test]
+fn keymap_round_trip() {
+    let keymap = keymap();
+    let bytes = keymap.to_bytes();
+    assert_eq!(Keymap::from_bytes(&bytes), Ok(keymap));
+}
+
+#[test]
+fn keymap_errors() {
+    let bytes = keymap().to_bytes();
+    assert_eq!(Keymap::from_bytes(b"nope"), Err(KeymapError::BadMagic));
+    assert_eq!(
+        Keymap::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(KeymapError::UnexpectedEnd)
+    );
+    let mut newer = bytes.clone();
+    newer[4] = 99;
+    assert_eq!(
+        Keymap::from_bytes(&newer),
+        Err(KeymapError::UnsupportedVersion(99))
+    );
+}
+
+#[test]
+fn rebind_suggestions() {
+    use bevy_input_sequence::{
+        namespace::ConflictKind,
+        rebind::{suggest, Resolution},
+    };
+    let chords = |acts: &[(Modifiers, KeyCode)]| acts.iter().cloned().map(KeyChord::from).collect::<Vec<_>>();
+    let mut keymap = keymap();
+    assert!(suggest(&keymap, "dash", &chords(&keyseq! { E E })).is_empty());
+
+    let conflicts = suggest(&keymap, "dash", &chords(&keyseq! { ArrowUp }));
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].other, "konami");
+    assert_eq!(conflicts[0].kind, ConflictKind::Prefix);
+    assert_eq!(
+        conflicts[0].resolutions,
+        vec![
+            Resolution::Swap { other: "konami".into(), acts: chords(&keyseq! { D D }) },
+            Resolution::UnbindOther { other: "konami".into() },
+            Resolution::AppendModifier {
+                modifiers: Modifiers::CONTROL,
+                acts: chords(&keyseq! { Ctrl-ArrowUp }),
+            },
+        ]
+    );
+
+    conflicts[0].resolutions[1].apply(&mut keymap, "dash", &chords(&keyseq! { ArrowUp }));
+    assert_eq!(keymap.bindings.len(), 2);
+    assert_eq!(keymap.bindings[0].acts, chords(&keyseq! { ArrowUp }));
+}
+
+#[test]
+fn keymap_round_trips_every_key() {
+    use bevy::{
+        input::keyboard::NativeKeyCode,
+        reflect::{DynamicEnum, DynamicVariant, FromReflect, TypeInfo, Typed, VariantInfo},
+    };
+    let TypeInfo::Enum(info) = KeyCode::type_info() else {
+        unreachable!("KeyCode is an enum");
+    };
+    let mut acts: Vec<KeyChord> = info
+        .iter()
+        .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+        .map(|variant| {
+            KeyCode::from_reflect(&DynamicEnum::new(variant.name(), DynamicVariant::Unit)).unwrap()
+        })
+        .map(KeyChord::from)
+        .collect();
+    acts.extend(
+        [
+            NativeKeyCode::Unidentified,
+            NativeKeyCode::Android(1),
+            NativeKeyCode::MacOS(2),
+            NativeKeyCode::Windows(3),
+            NativeKeyCode::Xkb(4),
+        ]
+        .map(|native| KeyChord::from(KeyCode::Unidentified(native))),
+    );
+    let keymap = Keymap {
+        bindings: vec![Binding {
+            name: "every key".into(),
+            acts,
+            time_limit: None,
+        }],
+    };
+    assert_eq!(Keymap::from_bytes(&keymap.to_bytes()), Ok(keymap));
+}
+
+#[test]
+fn keymap_rejects_out_of_range_time_limits() {
+    let mut bytes = Keymap::default().to_bytes();
+    // One binding named "a" with no acts and a duration of u64::MAX seconds
+    // and two billion nanoseconds.
+    bytes[5] = 1;
+    bytes.extend([1, b'a', 0, 2]);
+    bytes.extend([0xff; 9]);
+    bytes.push(0x01);
+    bytes.extend([0x80, 0xa8, 0xd6, 0xb9, 0x07]);
+    assert_eq!(Keymap::from_bytes(&bytes), Err(KeymapError::TimeLimitOutOfRange));
+}
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy_input_sequence::{
+    keymap::{Binding, Keymap},
+    lint::{lint, lint_text, Lint, TextError},
+    prelude::*,
+    shorthand::ParseError,
+};
+
+fn binding(name: &str, acts: Vec<KeyChord>, time_limit: Option<TimeLimit>) -> Binding {
+    Binding {
+        name: name.into(),
+        acts,
+        time_limit,
+    }
+}
+
+#[test]
+fn lint_keymap() {
+    let chords = |acts: &[(Modifiers, KeyCode)]| acts.iter().cloned().map(KeyChord::from).collect();
+    let keymap = Keymap {
+        bindings: vec![
+            binding("a", chords(&keyseq! { A B }), None),
+            binding("b", chords(&keyseq! { A B }), None),
+            binding("c", chords(&keyseq! { A B C }), None),
+            binding("d", vec![KeyChord::from(KeyCode::ShiftLeft)], None),
+            binding("e", chords(&keyseq! { X Y Z }), Some(TimeLimit::Frames(1))),
+            binding("f", chords(&keyseq! { X Y Z }), Some(TimeLimit::Frames(2))),
+        ],
+    };
+    let lints = lint(&keymap);
+    assert_eq!(
+        &lints[..3],
+        &[
+            Lint::Conflict {
+                first: "a".into(),
+                second: "b".into()
+            },
+            Lint::Shadowed {
+                prefix: "a".into(),
+                binding: "c".into()
+            },
+            Lint::Shadowed {
+                prefix: "b".into(),
+                binding: "c".into()
+            },
+        ]
+    );
+    let unreachable: Vec<_> = lints
+        .iter()
+        .filter_map(|lint| match lint {
+            Lint::Unreachable { binding, .. } => Some(binding.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(unreachable, vec!["d", "e"]);
+}
+
+#[test]
+fn lint_text_errors() {
+    assert_eq!(
+        lint_text("ok: A\nmissing colon"),
+        Err(TextError {
+            line: 2,
+            error: None
+        })
+    );
+    assert_eq!(
+        lint_text("bad: Hyper-A").unwrap_err().error,
+        Some(ParseError::UnknownKey("Hyper-A".into()))
+    );
+    let (_, lints) = lint_text("spaced:  Ctrl-X   Ctrl-S").unwrap();
+    assert!(lints.is_empty());
+}
@@ -89,3 +89,87 @@ fn test_keyseq_doc() {
         [(Modifiers::CONTROL, KeyCode::Semicolon)]
     );
 }
+
+#[cfg(feature = "macros")]
+#[test]
+fn chord_macros() {
+    use bevy_input_sequence::macros;
+    let chord: KeyChord = macros::key! { Ctrl-A };
+    assert_eq!(chord, KeyChord(Modifiers::CONTROL, KeyCode::KeyA));
+    let chords: [KeyChord; 2] = macros::keyseq! { Ctrl-A B };
+    assert_eq!(
+        chords,
+        [
+            KeyChord(Modifiers::CONTROL, KeyCode::KeyA),
+            KeyChord(Modifiers::empty(), KeyCode::KeyB),
+        ]
+    );
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn chord_macro_spellings() {
+    use bevy_input_sequence::macros;
+    assert_eq!(
+        macros::keyseq! { ctrl-F5 Up Up Down Down Left Right PgUp PgDn Esc Num7 NumEnter },
+        [
+            KeyChord(Modifiers::CONTROL, KeyCode::F5),
+            KeyChord::from(KeyCode::ArrowUp),
+            KeyChord::from(KeyCode::ArrowUp),
+            KeyChord::from(KeyCode::ArrowDown),
+            KeyChord::from(KeyCode::ArrowDown),
+            KeyChord::from(KeyCode::ArrowLeft),
+            KeyChord::from(KeyCode::ArrowRight),
+            KeyChord::from(KeyCode::PageUp),
+            KeyChord::from(KeyCode::PageDown),
+            KeyChord::from(KeyCode::Escape),
+            KeyChord::from(KeyCode::Numpad7),
+            KeyChord::from(KeyCode::NumpadEnter),
+        ]
+    );
+    assert_eq!(
+        macros::keyseq! { F24 Home End Tab Enter Escape Space },
+        [
+            KeyChord::from(KeyCode::F24),
+            KeyChord::from(KeyCode::Home),
+            KeyChord::from(KeyCode::End),
+            KeyChord::from(KeyCode::Tab),
+            KeyChord::from(KeyCode::Enter),
+            KeyChord::from(KeyCode::Escape),
+            KeyChord::from(KeyCode::Space),
+        ]
+    );
+    assert_eq!(macros::key! { shift-Del }, KeyChord(Modifiers::SHIFT, KeyCode::Delete));
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn padseq_macro() {
+    use bevy_input_sequence::{
+        button::ButtonAct,
+        macros,
+        stick::{Direction, Stick},
+    };
+    assert_eq!(
+        macros::padseq! { A Y lb rt start up },
+        [
+            ButtonAct::Press(GamepadButton::South),
+            ButtonAct::Press(GamepadButton::North),
+            ButtonAct::Press(GamepadButton::LeftTrigger),
+            ButtonAct::Press(GamepadButton::RightTrigger2),
+            ButtonAct::Press(GamepadButton::Start),
+            ButtonAct::Press(GamepadButton::DPadUp),
+        ]
+    );
+    assert_eq!(
+        macros::padseq! { b db d df f + west },
+        [
+            ButtonAct::Stick(Stick::Left, Direction::Left),
+            ButtonAct::Stick(Stick::Left, Direction::DownLeft),
+            ButtonAct::Stick(Stick::Left, Direction::Down),
+            ButtonAct::Stick(Stick::Left, Direction::DownRight),
+            ButtonAct::Stick(Stick::Left, Direction::Right),
+            ButtonAct::Press(GamepadButton::West),
+        ]
+    );
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "replay")]
+use bevy::prelude::*;
+use bevy_input_sequence::{
+    button::ButtonAct,
+    prelude::*,
+    replay::{Replay, ReplayAct, ReplayError},
+};
+use std::time::Duration;
+
+#[test]
+fn replay_round_trip() {
+    let mut replay = Replay::default();
+    replay.push(Duration::ZERO, None, KeyChord(Modifiers::SHIFT, KeyCode::KeyA));
+    replay.push(
+        Duration::from_millis(16),
+        Some(1),
+        ButtonAct::Hold(GamepadButton::North),
+    );
+    let text = replay.export().unwrap();
+    assert!(text.contains("\"Shift-A\""));
+    assert_eq!(Replay::import(&text).unwrap(), replay);
+}
+
+#[test]
+fn replay_versions() {
+    // The device may be left out.
+    let replay = Replay::import(
+        r#"(version: 1, entries: [(time: (secs: 1, nanos: 0), act: Key("Ctrl-S"))])"#,
+    )
+    .unwrap();
+    assert_eq!(replay.entries[0].device, None);
+    assert_eq!(
+        replay.entries[0].act,
+        ReplayAct::Key(KeyChord(Modifiers::CONTROL, KeyCode::KeyS))
+    );
+
+    assert!(matches!(
+        Replay::import("(version: 2, entries: [])"),
+        Err(ReplayError::Unsupported(2))
+    ));
+}
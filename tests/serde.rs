@@ -0,0 +1,41 @@
+#![cfg(feature = "serde")]
+use bevy::prelude::*;
+use bevy_input_sequence::{
+    button::ButtonAct,
+    keymap::{Binding, Keymap},
+    prelude::*,
+};
+
+#[test]
+fn keymap_ron_round_trip() {
+    let keymap = Keymap {
+        bindings: vec![Binding {
+            name: "god mode".into(),
+            acts: keyseq! { Ctrl-W A S D }.into_iter().map(KeyChord::from).collect(),
+            time_limit: Some(TimeLimit::Frames(60)),
+        }],
+    };
+    let text = ron::to_string(&keymap).unwrap();
+    assert!(text.contains(r#"["Ctrl-W","A","S","D"]"#));
+    assert_eq!(ron::from_str::<Keymap>(&text).unwrap(), keymap);
+
+    let keymap: Keymap = ron::from_str(
+        r#"(bindings: [(name: "save", acts: ["ctrl-x", "Ctrl-S"], time_limit: None)])"#,
+    )
+    .unwrap();
+    assert_eq!(
+        keymap.bindings[0].acts,
+        keyseq! { Ctrl-X Ctrl-S }.into_iter().map(KeyChord::from).collect::<Vec<_>>()
+    );
+    assert!(ron::from_str::<KeyChord>(r#""Hyper-A""#).is_err());
+}
+
+#[test]
+fn button_act_ron() {
+    let acts = vec![
+        ButtonAct::Tap(GamepadButton::South),
+        ButtonAct::Hold(GamepadButton::South),
+    ];
+    let text = ron::to_string(&acts).unwrap();
+    assert_eq!(ron::from_str::<Vec<ButtonAct>>(&text).unwrap(), acts);
+}
@@ -20,6 +20,7 @@ mod simulate_app {
                 //commands::Commands,
                 Query,
             },
+            system::EntityCommand,
             world::{Command, World},
         },
         input::{
@@ -462,6 +463,596 @@ mod simulate_app {
             .is_some());
     }
 
+    #[test]
+    fn game_pad_button_group() {
+        use bevy_input_sequence::button::{ButtonAct, ButtonGroup};
+        let mut app = new_app();
+
+        let id = app.send_gamepad_connection_event(None);
+
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [ButtonAct::Any(ButtonGroup::Face); 3],
+        ));
+        app.update();
+
+        for button in [GamepadButton::North, GamepadButton::East, GamepadButton::South] {
+            app.press_pad_button(button, id);
+            app.update();
+            app.clear_just_pressed_pad_button(button, id);
+        }
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn game_pad_connects_on_first_press() {
+        // Browsers only report a gamepad once one of its buttons is pressed,
+        // so the connection and the first press arrive in the same frame.
+        let mut app = new_app();
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [GamepadButton::North, GamepadButton::East],
+        ));
+        app.update();
+
+        let id = app.send_gamepad_connection_event(None);
+        app.press_pad_button(GamepadButton::North, id);
+        app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 0);
+
+        app.clear_just_pressed_pad_button(GamepadButton::North, id);
+        app.press_pad_button(GamepadButton::East, id);
+        app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn face_buttons_by_label() {
+        use bevy_input_sequence::layout::{FaceSemantics, GamepadLayout, GamepadProfile};
+        let mut app = new_app();
+        let positional = app.send_gamepad_connection_event(None);
+        let labeled = app.send_gamepad_connection_event(None);
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [GamepadButton::South],
+        ));
+        app.update();
+        for (id, semantics) in [(positional, FaceSemantics::Position), (labeled, FaceSemantics::Label)] {
+            app.world_mut().entity_mut(id).insert(GamepadProfile {
+                layout: GamepadLayout::SwitchPro,
+                semantics,
+            });
+        }
+
+        // The Switch Pro's A button sits in the east.
+        app.press_pad_button(GamepadButton::East, positional);
+        app.press_pad_button(GamepadButton::East, labeled);
+        app.update();
+        let sent: u8 = app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum();
+        assert_eq!(sent, 1);
+        app.clear_just_pressed_pad_button(GamepadButton::East, positional);
+        app.clear_just_pressed_pad_button(GamepadButton::East, labeled);
+
+        app.press_pad_button(GamepadButton::South, positional);
+        app.press_pad_button(GamepadButton::South, labeled);
+        app.update();
+        let sent: u8 = app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum();
+        assert_eq!(sent, 2);
+    }
+
+    #[test]
+    fn dedupe_device_presses() {
+        use bevy_input_sequence::dedupe::DeviceDedupe;
+        let mut app = new_app();
+        app.insert_resource(DeviceDedupe::steam_deck());
+        let pad = app.send_gamepad_connection_event(None);
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [GamepadButton::South],
+        ));
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), [KeyCode::Enter]));
+        app.update();
+
+        // One press reported twice only counts once.
+        app.press_pad_button(GamepadButton::South, pad);
+        press_key(&mut app, KeyCode::Enter);
+        app.update();
+        let sent = |app: &mut TestContext| -> u8 {
+            app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum()
+        };
+        assert_eq!(sent(&mut app), 1);
+        app.clear_just_pressed_pad_button(GamepadButton::South, pad);
+        clear_just_pressed(&mut app, KeyCode::Enter);
+        release(&mut app, KeyCode::Enter);
+
+        // A late duplicate is dropped too.
+        press_key(&mut app, KeyCode::Enter);
+        app.update();
+        assert_eq!(sent(&mut app), 1);
+        clear_just_pressed(&mut app, KeyCode::Enter);
+        release(&mut app, KeyCode::Enter);
+
+        // A press outside the window counts.
+        app.update();
+        app.update();
+        press_key(&mut app, KeyCode::Enter);
+        app.update();
+        assert_eq!(sent(&mut app), 2);
+    }
+
+    #[test]
+    fn throttle_presses() {
+        use bevy_input_sequence::throttle::ActThrottle;
+        use std::time::Duration;
+        let mut app = new_app();
+        app.insert_resource(ActThrottle::new(2).window(Duration::from_secs(3600)));
+        let pad = app.send_gamepad_connection_event(None);
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [GamepadButton::South],
+        ));
+        app.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            [KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC],
+        ));
+        app.update();
+        let sent = |app: &mut TestContext| -> u8 {
+            app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum()
+        };
+
+        // The third key press is over the limit.
+        for key in [KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC] {
+            press_key(&mut app, key);
+            app.update();
+            clear_just_pressed(&mut app, key);
+            release(&mut app, key);
+        }
+        assert_eq!(sent(&mut app), 0);
+
+        // Each device has its own limit.
+        app.press_pad_button(GamepadButton::South, pad);
+        app.update();
+        assert_eq!(sent(&mut app), 1);
+    }
+
+    #[test]
+    fn toggle_flags() {
+        use bevy_input_sequence::testing::type_chords;
+        #[derive(Resource, Default)]
+        struct Debug {
+            overlay: bool,
+        }
+        #[derive(Component, Default)]
+        struct Player {
+            god_mode: bool,
+        }
+        #[derive(Event, Clone, Debug, PartialEq)]
+        enum Toggled {
+            On,
+            Off,
+        }
+        let mut app = new_app();
+        app.init_resource::<Debug>().add_event::<Toggled>();
+        app.world_mut().add(KeySequence::new(
+            action::toggle(|debug: &mut Debug| &mut debug.overlay, Toggled::On, Toggled::Off),
+            keyseq! { A },
+        ));
+        let player = app.world_mut().spawn(Player::default()).id();
+        EntityCommand::apply(
+            KeySequence::new(
+                action::toggle_component(|p: &mut Player| &mut p.god_mode, Toggled::On, Toggled::Off),
+                keyseq! { B },
+            ),
+            player,
+            app.world_mut(),
+        );
+        app.update();
+        assert_eq!(type_chords::<Toggled>(&mut app, keyseq! { A }), 1);
+        assert!(app.world().resource::<Debug>().overlay);
+        assert_eq!(type_chords::<Toggled>(&mut app, keyseq! { A }), 1);
+        assert!(!app.world().resource::<Debug>().overlay);
+
+        let mut cursor = app.world().resource::<Events<Toggled>>().get_cursor_current();
+        type_chords::<Toggled>(&mut app, keyseq! { B });
+        assert!(app.world().get::<Player>(player).unwrap().god_mode);
+        let events = app.world().resource::<Events<Toggled>>();
+        assert_eq!(cursor.read(events).cloned().collect::<Vec<_>>(), vec![Toggled::On]);
+    }
+
+    #[test]
+    fn send_to_channel() {
+        use bevy_input_sequence::testing::type_chords;
+        use std::sync::mpsc;
+        let mut app = new_app();
+        let (sender, receiver) = mpsc::channel();
+        app.world_mut().add(KeySequence::new(
+            action::send_to(sender, "chime"),
+            keyseq! { A B },
+        ));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 0);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec!["chime"]);
+    }
+
+    #[test]
+    fn spawn_task() {
+        use bevy_input_sequence::testing::type_chords;
+        use std::{sync::mpsc, time::Duration};
+        let mut app = new_app();
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(
+                action::spawn_task(move |info| {
+                    let sender = sender.clone();
+                    async move {
+                        let _ = sender.send(info.map(|info| info.entity));
+                    }
+                }),
+                keyseq! { A B },
+            ),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        type_chords::<MyEvent>(&mut app, keyseq! { A B });
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(Some(id)));
+    }
+
+    #[test]
+    fn apps_are_isolated() {
+        let mut server = new_app();
+        let mut client = new_app();
+        server.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            [KeyCode::KeyA, KeyCode::KeyB],
+        ));
+        client.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            [KeyCode::KeyA, KeyCode::KeyC],
+        ));
+        server.update();
+        client.update();
+        let sent = |app: &mut TestContext| -> u8 {
+            app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum()
+        };
+        let tap = |app: &mut TestContext, key: KeyCode| {
+            press_key(app, key);
+            app.update();
+            clear_just_pressed(app, key);
+            release(app, key);
+            app.update();
+        };
+
+        // Progress in one app doesn't carry over to the other.
+        tap(&mut server, KeyCode::KeyA);
+        tap(&mut client, KeyCode::KeyC);
+        assert_eq!(sent(&mut client), 0);
+
+        // Each app only matches its own sequences.
+        tap(&mut client, KeyCode::KeyA);
+        tap(&mut client, KeyCode::KeyB);
+        assert_eq!(sent(&mut client), 0);
+        tap(&mut server, KeyCode::KeyB);
+        assert_eq!(sent(&mut server), 1);
+
+        tap(&mut client, KeyCode::KeyA);
+        tap(&mut client, KeyCode::KeyC);
+        assert_eq!(sent(&mut client), 1);
+        assert_eq!(sent(&mut server), 1);
+    }
+
+    #[test]
+    fn stick_directions() {
+        use bevy_input_sequence::{
+            button::ButtonAct,
+            stick::{Direction, Stick},
+        };
+        let mut app = new_app();
+        let pad = app.send_gamepad_connection_event(None);
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [
+                ButtonAct::Stick(Stick::Left, Direction::Down),
+                ButtonAct::Stick(Stick::Left, Direction::DownRight),
+                ButtonAct::Stick(Stick::Left, Direction::Right),
+                ButtonAct::Press(GamepadButton::West),
+            ],
+        ));
+        app.update();
+        let sent = |app: &mut TestContext| -> u8 {
+            app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum()
+        };
+        let stick = |app: &mut TestContext, x: f32, y: f32| {
+            for (axis, value) in [(GamepadAxis::LeftStickX, x), (GamepadAxis::LeftStickY, y)] {
+                app.send_raw_gamepad_event(
+                    RawGamepadAxisChangedEvent { gamepad: pad, axis, value }.into(),
+                );
+            }
+            app.update();
+        };
+
+        // Roll from down to right, wobbling inside the release threshold.
+        stick(&mut app, 0.0, -1.0);
+        stick(&mut app, 0.05, -0.55);
+        stick(&mut app, 0.7, -0.7);
+        stick(&mut app, 1.0, 0.0);
+        stick(&mut app, 0.0, 0.0);
+        app.press_pad_button(GamepadButton::West, pad);
+        app.update();
+        assert_eq!(sent(&mut app), 1);
+
+        // Skipping the diagonal doesn't count.
+        app.clear_just_pressed_pad_button(GamepadButton::West, pad);
+        stick(&mut app, 0.0, -1.0);
+        stick(&mut app, 1.0, 0.0);
+        stick(&mut app, 0.0, 0.0);
+        app.press_pad_button(GamepadButton::West, pad);
+        app.update();
+        assert_eq!(sent(&mut app), 1);
+    }
+
+    #[test]
+    fn stick_calibration_at_runtime() {
+        use bevy_input_sequence::{
+            button::ButtonAct,
+            stick::{Direction, Stick, StickCalibration},
+        };
+        let mut app = new_app();
+        let pad = app.send_gamepad_connection_event(None);
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [ButtonAct::Stick(Stick::Left, Direction::Right)],
+        ));
+        app.update();
+        let sent = |app: &mut TestContext| -> u8 {
+            app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum()
+        };
+        let stick = |app: &mut TestContext, x: f32| {
+            app.send_raw_gamepad_event(
+                RawGamepadAxisChangedEvent { gamepad: pad, axis: GamepadAxis::LeftStickX, value: x }
+                    .into(),
+            );
+            app.update();
+        };
+
+        // A light push stays below the default press threshold.
+        stick(&mut app, 0.45);
+        stick(&mut app, 0.0);
+        assert_eq!(sent(&mut app), 0);
+
+        // A calibration screen lowers it.
+        *app.world_mut().resource_mut::<StickCalibration>() = StickCalibration {
+            press_threshold: 0.3,
+            release_threshold: 0.2,
+            ..Default::default()
+        };
+        stick(&mut app, 0.45);
+        stick(&mut app, 0.0);
+        assert_eq!(sent(&mut app), 1);
+    }
+
+    #[test]
+    fn stick_as_dpad() {
+        let mut app = new_app();
+        let pad = app.send_gamepad_connection_event(None);
+        for lenient in [false, true] {
+            let builder = ButtonSequence::new(
+                action::send_event_with_input(|_: Entity| MyEvent),
+                [GamepadButton::DPadDown, GamepadButton::DPadRight, GamepadButton::East],
+            );
+            app.world_mut().add(if lenient { builder.stick_as_dpad() } else { builder });
+        }
+        app.update();
+        let sent = |app: &mut TestContext| -> u8 {
+            app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum()
+        };
+        let stick = |app: &mut TestContext, x: f32, y: f32| {
+            for (axis, value) in [(GamepadAxis::LeftStickX, x), (GamepadAxis::LeftStickY, y)] {
+                app.send_raw_gamepad_event(
+                    RawGamepadAxisChangedEvent { gamepad: pad, axis, value }.into(),
+                );
+            }
+            app.update();
+        };
+
+        // Only the lenient sequence takes the stick.
+        stick(&mut app, 0.0, -1.0);
+        stick(&mut app, 0.0, 0.0);
+        app.press_pad_button(GamepadButton::DPadRight, pad);
+        app.update();
+        app.clear_just_pressed_pad_button(GamepadButton::DPadRight, pad);
+        app.update();
+        app.press_pad_button(GamepadButton::East, pad);
+        app.update();
+        assert_eq!(sent(&mut app), 1);
+    }
+
+    #[test]
+    fn gamepad_gate() {
+        use bevy_input_sequence::health::{
+            GamepadGate, GamepadGateChanged, GamepadHealth, GateReason,
+        };
+        let mut app = new_app();
+        app.insert_resource(GamepadGate::default());
+        let pad = app.send_gamepad_connection_event(None);
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [GamepadButton::South],
+        ));
+        app.update();
+        app.world_mut().entity_mut(pad).insert(GamepadHealth {
+            battery: Some(0.01),
+            ..Default::default()
+        });
+        let sent = |app: &mut TestContext| -> u8 {
+            app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum()
+        };
+        let changes = |app: &mut TestContext| -> Vec<Option<GateReason>> {
+            app.world_mut()
+                .resource_mut::<Events<GamepadGateChanged>>()
+                .drain()
+                .map(|e| e.reason)
+                .collect()
+        };
+
+        app.press_pad_button(GamepadButton::South, pad);
+        app.update();
+        assert_eq!(sent(&mut app), 0);
+        assert_eq!(changes(&mut app), vec![Some(GateReason::CriticalBattery)]);
+
+        app.clear_just_pressed_pad_button(GamepadButton::South, pad);
+        app.world_mut().entity_mut(pad).insert(GamepadHealth {
+            battery: Some(0.9),
+            ..Default::default()
+        });
+        app.update();
+        assert_eq!(changes(&mut app), vec![None]);
+        app.press_pad_button(GamepadButton::South, pad);
+        app.update();
+        assert_eq!(sent(&mut app), 1);
+    }
+
+    #[test]
+    fn trigger_pulls() {
+        use bevy_input_sequence::button::ButtonAct;
+        let mut app = new_app();
+        let id = app.send_gamepad_connection_event(None);
+        let trigger = GamepadButton::RightTrigger2;
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [
+                ButtonAct::SoftPull(trigger),
+                ButtonAct::HardPull(trigger),
+                ButtonAct::Release(trigger),
+            ],
+        ));
+        app.update();
+
+        let pull = |app: &mut TestContext, value: f32| {
+            app.send_raw_gamepad_event(
+                RawGamepadButtonChangedEvent {
+                    gamepad: id,
+                    button: trigger,
+                    value,
+                }
+                .into(),
+            );
+            app.update();
+            app.world_mut()
+                .query::<&EventSent>()
+                .iter(app.world())
+                .map(|sent| sent.0)
+                .sum::<u8>()
+        };
+        assert_eq!(pull(&mut app, 0.5), 0);
+        assert_eq!(pull(&mut app, 1.0), 0);
+        assert_eq!(pull(&mut app, 0.85), 0);
+        assert_eq!(pull(&mut app, 0.0), 1);
+
+        // A quick full pull passes the soft threshold in the same frame.
+        assert_eq!(pull(&mut app, 1.0), 1);
+        assert_eq!(pull(&mut app, 0.0), 2);
+    }
+
+    #[test]
+    fn tap_and_hold() {
+        use bevy::time::TimeUpdateStrategy;
+        use bevy_input_sequence::button::ButtonAct;
+        use std::time::Duration;
+        let mut app = new_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        let id = app.send_gamepad_connection_event(None);
+        let south = GamepadButton::South;
+        app.world_mut().add(ButtonSequence::new(
+            action::send_event_with_input(|_: Entity| MyEvent),
+            [ButtonAct::Tap(south), ButtonAct::Tap(south), ButtonAct::Hold(south)],
+        ));
+        app.update();
+
+        let set = |app: &mut TestContext, value: f32| {
+            app.send_raw_gamepad_event(
+                RawGamepadButtonChangedEvent {
+                    gamepad: id,
+                    button: south,
+                    value,
+                }
+                .into(),
+            );
+            app.update();
+            app.world_mut()
+                .query::<&EventSent>()
+                .iter(app.world())
+                .map(|sent| sent.0)
+                .sum::<u8>()
+        };
+        for _ in 0..2 {
+            assert_eq!(set(&mut app, 1.0), 0);
+            assert_eq!(set(&mut app, 0.0), 0);
+        }
+        assert_eq!(set(&mut app, 1.0), 0);
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(set(&mut app, 1.0), 1);
+        assert_eq!(set(&mut app, 0.0), 1);
+    }
+
+    #[test]
+    fn evict_partial_matches() {
+        use bevy_input_sequence::{cache::ButtonSequenceCache, EvictionPolicy, InputSequencePluginSettings};
+        for (policy, survivor) in [(EvictionPolicy::Oldest, 2), (EvictionPolicy::LeastProgress, 0)] {
+            let mut app = new_app();
+            {
+                let mut settings = app.world_mut().resource_mut::<InputSequencePluginSettings>();
+                settings.max_partial_matches = Some(1);
+                settings.eviction = policy;
+            }
+            let pads: Vec<Entity> = (0..3).map(|_| app.send_gamepad_connection_event(None)).collect();
+            app.world_mut().add(ButtonSequence::new(
+                action::send_event_with_input(|_: Entity| MyEvent),
+                [GamepadButton::North, GamepadButton::East, GamepadButton::South],
+            ));
+            app.update();
+
+            // The first pad gets ahead, then the others start.
+            app.press_pad_button(GamepadButton::North, pads[0]);
+            app.update();
+            app.clear_just_pressed_pad_button(GamepadButton::North, pads[0]);
+            app.press_pad_button(GamepadButton::East, pads[0]);
+            app.press_pad_button(GamepadButton::North, pads[1]);
+            app.update();
+            app.clear_just_pressed_pad_button(GamepadButton::East, pads[0]);
+            app.clear_just_pressed_pad_button(GamepadButton::North, pads[1]);
+            app.press_pad_button(GamepadButton::North, pads[2]);
+            app.update();
+            app.clear_just_pressed_pad_button(GamepadButton::North, pads[2]);
+            let cache = app.world().resource::<ButtonSequenceCache>();
+            assert_eq!(cache.partial_matches(), 1);
+            assert_eq!(cache.evictions(), 2);
+
+            // Only the surviving pad can finish the sequence.
+            let survivor = pads[survivor];
+            let remaining: &[GamepadButton] = if survivor == pads[0] {
+                &[GamepadButton::South]
+            } else {
+                &[GamepadButton::East, GamepadButton::South]
+            };
+            for button in remaining {
+                for pad in &pads {
+                    app.press_pad_button(*button, *pad);
+                }
+                app.update();
+                for pad in &pads {
+                    app.clear_just_pressed_pad_button(*button, *pad);
+                }
+            }
+            let sent: u8 = app.world_mut().query::<&EventSent>().iter(app.world()).map(|e| e.0).sum();
+            assert_eq!(sent, 1, "{policy:?}");
+        }
+    }
+
     #[test]
     fn multiple_inputs() {
         let mut app = new_app();
@@ -563,8 +1154,17 @@ mod simulate_app {
     }
 
     #[test]
-    fn test_modifier() {
-        let mut app = new_app();
+    fn default_time_limit() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(
+                InputSequencePlugin::default()
+                    .match_key(true)
+                    .default_time_limit(TimeLimit::Frames(1)),
+            )
+            .add_systems(PostUpdate, read)
+            .add_event::<MyEvent>()
+            .init_resource::<Input<KeyCode>>();
 
         app.world_mut().add(KeySequence::new(
             action::send_event(MyEvent),
@@ -573,35 +1173,2040 @@ mod simulate_app {
 
         press_key(&mut app, KeyCode::KeyA);
         app.update();
-        assert!(app
-            .world_mut()
-            .query::<&EventSent>()
-            .iter(app.world_mut())
-            .next()
-            .is_none());
+        clear_just_pressed(&mut app, KeyCode::KeyA);
+        app.update();
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 0);
+        clear_just_pressed(&mut app, KeyCode::KeyB);
+        release(&mut app, KeyCode::KeyA);
+        release(&mut app, KeyCode::KeyB);
 
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
         clear_just_pressed(&mut app, KeyCode::KeyA);
+        press_key(&mut app, KeyCode::KeyB);
         app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
 
-        press_key(&mut app, KeyCode::ControlLeft);
+    #[test]
+    fn gesture_sequence() {
+        use bevy::{input::gestures::{PanGesture, PinchGesture}, math::Vec2};
+        use bevy_input_sequence::gesture::{GestureAct, GestureSequence};
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputSequencePlugin::default().match_key(false).match_gesture(true))
+            .add_systems(PostUpdate, read)
+            .add_event::<MyEvent>();
+        app.world_mut().add(GestureSequence::new(
+            action::send_event(MyEvent),
+            [GestureAct::SwipeLeft, GestureAct::PinchOut],
+        ));
         app.update();
-        assert!(app
-            .world_mut()
-            .query::<&EventSent>()
-            .iter(app.world_mut())
-            .next()
-            .is_none());
-        release(&mut app, KeyCode::ControlLeft);
+
+        // A swipe counts once however long it goes on.
+        for _ in 0..3 {
+            app.world_mut().send_event(PanGesture(Vec2::new(-30.0, 5.0)));
+            app.world_mut().send_event(PanGesture(Vec2::new(-30.0, 5.0)));
+            app.update();
+        }
+        app.update();
+        app.world_mut().send_event(PinchGesture(0.05));
+        app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 0);
+        app.world_mut().send_event(PinchGesture(0.05));
         app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
 
-        press_key(&mut app, KeyCode::KeyB);
+    #[test]
+    fn stylus_sequence() {
+        use bevy::{input::touch::{ForceTouch, TouchInput, TouchPhase}, math::Vec2};
+        use bevy_input_sequence::stylus::{StylusAct, StylusSequence};
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputSequencePlugin::default().match_key(false).match_stylus(true))
+            .add_systems(PostUpdate, read)
+            .add_event::<MyEvent>();
+        app.world_mut().add(StylusSequence::new(
+            action::send_event(MyEvent),
+            [StylusAct::Press, StylusAct::HardPress, StylusAct::Release],
+        ));
         app.update();
-        assert!(app
+        let window = app.world_mut().spawn_empty().id();
+        for (phase, force) in [
+            (TouchPhase::Started, 0.05),
+            (TouchPhase::Moved, 0.5),
+            (TouchPhase::Moved, 0.9),
+            (TouchPhase::Moved, 0.75),
+            (TouchPhase::Ended, 0.0),
+        ] {
+            app.world_mut().send_event(TouchInput {
+                phase,
+                position: Vec2::ZERO,
+                window,
+                force: Some(ForceTouch::Normalized(force)),
+                id: 0,
+            });
+            app.update();
+        }
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn hold_sequence() {
+        use bevy::{prelude::Trigger, time::TimeUpdateStrategy};
+        use bevy_input_sequence::{
+            hold::{HoldAct, HoldSequence},
+            matched::SequenceComplete,
+        };
+        use std::time::Duration;
+        #[derive(Resource, Default)]
+        struct Held(Vec<Option<Duration>>);
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputSequencePlugin::default().match_key(false).match_hold(true))
+            .add_systems(PostUpdate, read)
+            .add_event::<MyEvent>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<Held>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        let id = app
             .world_mut()
-            .query::<&EventSent>()
-            .iter(app.world_mut())
-            .next()
-            .is_some());
+            .spawn_empty()
+            .observe(|trigger: Trigger<SequenceComplete>, mut held: ResMut<Held>| {
+                held.0.push(trigger.held);
+            })
+            .id();
+        EntityCommand::apply(
+            HoldSequence::new(
+                action::send_event(MyEvent),
+                [HoldAct::new(
+                    KeyCode::Space,
+                    Duration::from_millis(250),
+                    Duration::from_millis(350),
+                )],
+            ),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        for held in [2, 3] {
+            press_key(&mut app, KeyCode::Space);
+            app.update();
+            clear_just_pressed(&mut app, KeyCode::Space);
+            for _ in 1..held {
+                app.update();
+            }
+            release(&mut app, KeyCode::Space);
+            app.update();
+            app.world_mut()
+                .resource_mut::<Input<KeyCode>>()
+                .clear();
+        }
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+        assert_eq!(app.world().resource::<Held>().0, [Some(Duration::from_millis(300))]);
+    }
+
+    #[test]
+    fn char_sequence() {
+        use bevy::input::{keyboard::{Key, KeyboardInput}, ButtonState};
+        use bevy_input_sequence::text::CharSequence;
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputSequencePlugin::default().match_key(false).match_text(true))
+            .add_systems(PostUpdate, read)
+            .add_event::<MyEvent>();
+        app.world_mut()
+            .add(CharSequence::new(action::send_event(MyEvent), "idkfa".chars()));
+        app.update();
+        let window = app.world_mut().spawn_empty().id();
+        // An AZERTY layout types these letters from different physical keys.
+        for (key_code, c) in [
+            (KeyCode::KeyI, "i"),
+            (KeyCode::KeyD, "d"),
+            (KeyCode::KeyK, "k"),
+            (KeyCode::KeyF, "f"),
+            (KeyCode::KeyQ, "a"),
+        ] {
+            app.world_mut().send_event(KeyboardInput {
+                key_code,
+                logical_key: Key::Character(c.into()),
+                state: ButtonState::Pressed,
+                repeat: false,
+                window,
+            });
+            app.update();
+        }
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn disable_char_sequence() {
+        use bevy::input::{keyboard::{Key, KeyboardInput}, ButtonState};
+        use bevy_input_sequence::{disabled::Disabled, text::CharSequence};
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputSequencePlugin::default().match_key(false).match_text(true))
+            .add_systems(PostUpdate, read)
+            .add_event::<MyEvent>();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            CharSequence::new(action::send_event(MyEvent), "ok".chars()),
+            id,
+            app.world_mut(),
+        );
+        app.world_mut().entity_mut(id).insert(Disabled);
+        app.update();
+        assert!(app.world().get::<CharSequence>(id).is_none());
+        let window = app.world_mut().spawn_empty().id();
+        let type_text = |app: &mut App| {
+            for (key_code, c) in [(KeyCode::KeyO, "o"), (KeyCode::KeyK, "k")] {
+                app.world_mut().send_event(KeyboardInput {
+                    key_code,
+                    logical_key: Key::Character(c.into()),
+                    state: ButtonState::Pressed,
+                    repeat: false,
+                    window,
+                });
+                app.update();
+            }
+        };
+        type_text(&mut app);
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 0);
+
+        app.world_mut().entity_mut(id).remove::<Disabled>();
+        app.update();
+        type_text(&mut app);
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn mash_sequence() {
+        use bevy::time::TimeUpdateStrategy;
+        use bevy_input_sequence::{
+            mash::{MashProgress, MashSequence},
+            testing::type_chords,
+        };
+        use std::time::Duration;
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputSequencePlugin::default().match_key(false).match_mash(true))
+            .add_event::<MyEvent>()
+            .init_resource::<Input<KeyCode>>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.world_mut().add(MashSequence::new(
+            action::send_event(MyEvent),
+            KeyCode::Space,
+            4,
+            Duration::from_millis(500),
+        ));
+        app.update();
+        let mut cursor = app.world().resource::<Events<MashProgress>>().get_cursor();
+        let mut progress = Vec::new();
+        let mut read = |app: &App, progress: &mut Vec<usize>| {
+            progress.extend(
+                cursor
+                    .read(app.world().resource::<Events<MashProgress>>())
+                    .map(|p| p.presses),
+            );
+        };
+
+        // Too slow: presses 600ms apart never have two in the window.
+        for _ in 0..4 {
+            assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Space }), 0);
+            read(&app, &mut progress);
+            for _ in 0..5 {
+                app.update();
+            }
+        }
+        for chord in keyseq! { X Space Space Space } {
+            assert_eq!(type_chords::<MyEvent>(&mut app, [chord]), 0);
+            read(&app, &mut progress);
+        }
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Space }), 1);
+        read(&app, &mut progress);
+        assert_eq!(progress, [1, 1, 1, 1, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn mash_of_no_presses() {
+        use bevy_input_sequence::{mash::MashSequence, testing::type_chords};
+        use std::time::Duration;
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputSequencePlugin::default().match_key(false).match_mash(true))
+            .add_event::<MyEvent>()
+            .init_resource::<Input<KeyCode>>();
+        app.world_mut().add(MashSequence::new(
+            action::send_event(MyEvent),
+            KeyCode::Space,
+            0,
+            Duration::from_secs(1),
+        ));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Space }), 1);
+    }
+
+    #[test]
+    fn mouse_sequence() {
+        use bevy::input::{mouse::MouseButton, ButtonInput};
+        use bevy_input_sequence::mouse::MouseSequence;
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<MouseButton>>()
+            .add_plugins(InputSequencePlugin::default().match_key(false))
+            .add_systems(PostUpdate, read)
+            .add_event::<MyEvent>();
+        app.world_mut().add(MouseSequence::new(
+            action::send_event(MyEvent),
+            [MouseButton::Right, MouseButton::Right, MouseButton::Middle],
+        ));
+        app.update();
+        for button in [MouseButton::Right, MouseButton::Right, MouseButton::Middle] {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+            input.release_all();
+            input.clear();
+            input.press(button);
+            app.update();
+        }
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn mixed_sequence() {
+        use bevy::input::{mouse::MouseButton, ButtonInput};
+        use bevy_input_sequence::mixed::{MixedAct, MixedSequence};
+        let mut app = TestContext::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputSequencePlugin::default().match_mixed(true))
+            .add_systems(PostUpdate, read)
+            .add_event::<MyEvent>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>();
+        let pad = app.send_gamepad_connection_event(None);
+        app.world_mut().add(MixedSequence::new(
+            action::send_event(MyEvent),
+            [
+                MixedAct::from(KeyCode::ControlLeft),
+                MixedAct::from(MouseButton::Left),
+                MixedAct::from(GamepadButton::South),
+            ],
+        ));
+        app.update();
+
+        press_key(&mut app, KeyCode::ControlLeft);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::ControlLeft);
+        release(&mut app, KeyCode::ControlLeft);
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+        app.world_mut().resource_mut::<ButtonInput<MouseButton>>().clear();
+        app.press_pad_button(GamepadButton::South, pad);
+        app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn deferred_registration() {
+        use bevy_input_sequence::deferred::DeferredSequences;
+        let mut app = new_app();
+        let sender = app.world().resource::<DeferredSequences>().sender();
+        std::thread::spawn(move || {
+            assert!(sender.send(KeySequence::new(
+                action::send_event(MyEvent),
+                [KeyCode::KeyA, KeyCode::KeyB],
+            )));
+        })
+        .join()
+        .unwrap();
+
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyA);
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn registry_complete() {
+        use bevy::ecs::system::RunSystemOnce;
+        use bevy_input_sequence::{key, registry::KeySequenceRegistry};
+        let mut app = new_app();
+        for acts in [
+            keyseq! { Ctrl-X Ctrl-S }.to_vec(),
+            keyseq! { Ctrl-X K }.to_vec(),
+            keyseq! { Ctrl-X }.to_vec(),
+            keyseq! { Ctrl-C Ctrl-C }.to_vec(),
+        ] {
+            app.world_mut()
+                .add(KeySequence::new(action::send_event(MyEvent), acts));
+        }
+        app.update();
+        let completions = app
+            .world_mut()
+            .run_system_once(|registry: KeySequenceRegistry| {
+                registry.complete(&[KeyChord::from(key! { Ctrl-X })])
+            })
+            .unwrap();
+        let rests: Vec<_> = completions.into_iter().map(|(_, rest)| rest).collect();
+        assert_eq!(rests.len(), 3);
+        assert!(rests[0].is_empty());
+        assert!(rests.contains(&vec![KeyChord::from(key! { K })]));
+        assert!(rests.contains(&vec![KeyChord::from(key! { Ctrl-S })]));
+    }
+
+    #[test]
+    fn registry_extend() {
+        use bevy::{ecs::system::RunSystemOnce, hierarchy::Parent};
+        use bevy_input_sequence::{
+            namespace::ConflictKind,
+            registry::{BatchConflict, ConflictPolicy, ConflictWith, KeySequenceRegistry},
+        };
+        let mut app = new_app();
+        let save = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { Ctrl-X Ctrl-S }),
+            save,
+            app.world_mut(),
+        );
+        let batch = |world: &mut World| -> Vec<KeySequence> {
+            [keyseq! { Ctrl-X }, keyseq! { Ctrl-C }, keyseq! { Ctrl-C }]
+                .into_iter()
+                .map(|acts| KeySequence::new(action::send_event(MyEvent), acts).build(world))
+                .collect()
+        };
+        let count = |app: &mut App| app.world_mut().query::<&KeySequence>().iter(app.world()).count();
+
+        let rejected = batch(app.world_mut());
+        let systems: Vec<Entity> = rejected.iter().map(|seq| seq.system_id.entity()).collect();
+        let result = app
+            .world_mut()
+            .run_system_once(move |mut registry: KeySequenceRegistry| {
+                registry.extend(rejected.clone(), ConflictPolicy::RejectPrefix)
+            })
+            .unwrap();
+        assert_eq!(
+            result.unwrap_err().conflicts,
+            vec![
+                BatchConflict { index: 0, with: ConflictWith::Registered(save), kind: ConflictKind::Prefix },
+                BatchConflict { index: 2, with: ConflictWith::Batch(1), kind: ConflictKind::Same },
+            ]
+        );
+        assert_eq!(count(&mut app), 1);
+        // The rejected sequences' systems don't linger.
+        assert!(systems.iter().all(|system| app.world().get_entity(*system).is_err()));
+
+        let accepted = batch(app.world_mut());
+        let systems: Vec<Entity> = accepted.iter().map(|seq| seq.system_id.entity()).collect();
+        let ids = app
+            .world_mut()
+            .run_system_once(move |mut registry: KeySequenceRegistry| {
+                registry.extend(accepted.clone(), ConflictPolicy::Allow)
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(count(&mut app), 4);
+        // Each system is a child of its sequence, as with the builders.
+        for (system, id) in systems.into_iter().zip(ids) {
+            assert_eq!(app.world().get::<Parent>(system).map(|parent| parent.get()), Some(id));
+        }
+    }
+
+    #[test]
+    fn registry_iter() {
+        use bevy::ecs::system::RunSystemOnce;
+        use bevy_input_sequence::registry::KeySequenceRegistry;
+        let mut app = new_app();
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { I D D Q D })
+                .namespace("inspector")
+                .cheat(),
+        );
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { Ctrl-Shift-I })
+                .namespace("inspector"),
+        );
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), keyseq! { Ctrl-S }));
+        app.update();
+        let counts = app
+            .world_mut()
+            .run_system_once(|registry: KeySequenceRegistry| {
+                (
+                    registry.iter().count(),
+                    registry.iter().namespace("inspector").count(),
+                    registry.iter().namespace("inspector").cheat(false).count(),
+                    registry.iter().without_namespace().count(),
+                    registry.iter().focus_owner(None).count(),
+                )
+            })
+            .unwrap();
+        assert_eq!(counts, (3, 2, 1, 1, 3));
+    }
+
+    #[test]
+    fn assertion_macros() {
+        use bevy_input_sequence::{assert_sequence_does_not_fire, assert_sequence_fires};
+        let mut app = new_app();
+        app.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            keyseq! { Ctrl-W A S D },
+        ));
+        app.update();
+        assert_sequence_fires!(app, keyseq! { Ctrl-W A S D }, MyEvent);
+        assert_sequence_does_not_fire!(app, keyseq! { W A S D }, MyEvent);
+        assert_sequence_does_not_fire!(app, keyseq! { Ctrl-W A D }, MyEvent);
+    }
+
+    #[test]
+    fn time_dilation() {
+        use bevy::time::{Time, TimeUpdateStrategy, Virtual};
+        use std::time::Duration;
+        for (time_limit, speed, fires) in [
+            (TimeLimit::Duration(Duration::from_millis(250)), 1.0, false),
+            (TimeLimit::Duration(Duration::from_millis(250)), 0.25, true),
+            (TimeLimit::RealDuration(Duration::from_millis(250)), 0.25, false),
+            (TimeLimit::RealDuration(Duration::from_millis(350)), 0.25, true),
+        ] {
+            let mut app = new_app();
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+            app.world_mut()
+                .resource_mut::<Time<Virtual>>()
+                .set_relative_speed(speed);
+            app.world_mut().add(
+                KeySequence::new(action::send_event(MyEvent), [KeyCode::KeyA, KeyCode::KeyB])
+                    .time_limit(time_limit.clone()),
+            );
+            app.update();
+
+            press_key(&mut app, KeyCode::KeyA);
+            app.update();
+            clear_just_pressed(&mut app, KeyCode::KeyA);
+            for _ in 0..2 {
+                app.update();
+            }
+            press_key(&mut app, KeyCode::KeyB);
+            app.update();
+            assert_eq!(
+                app.world_mut().query::<&EventSent>().iter(app.world()).count(),
+                fires as usize,
+                "{time_limit:?} at speed {speed}"
+            );
+        }
+    }
+
+    #[test]
+    fn pause_freezes_time_limits() {
+        use bevy::time::{Time, TimeUpdateStrategy, Virtual};
+        use std::time::Duration;
+        for time_limit in [
+            TimeLimit::Frames(2),
+            TimeLimit::Duration(Duration::from_millis(250)),
+            TimeLimit::RealDuration(Duration::from_millis(250)),
+        ] {
+            let mut app = new_app();
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+            app.world_mut().add(
+                KeySequence::new(action::send_event(MyEvent), [KeyCode::KeyA, KeyCode::KeyB])
+                    .time_limit(time_limit.clone()),
+            );
+            app.update();
+
+            press_key(&mut app, KeyCode::KeyA);
+            app.update();
+            clear_just_pressed(&mut app, KeyCode::KeyA);
+            app.world_mut().resource_mut::<Time<Virtual>>().pause();
+            for _ in 0..5 {
+                app.update();
+            }
+            app.world_mut().resource_mut::<Time<Virtual>>().unpause();
+            press_key(&mut app, KeyCode::KeyB);
+            app.update();
+            assert_eq!(
+                app.world_mut().query::<&EventSent>().iter(app.world()).count(),
+                1,
+                "{time_limit:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn cheats_gate() {
+        use bevy_input_sequence::{
+            assert_sequence_does_not_fire, assert_sequence_fires, cheat::CheatsEnabled,
+        };
+        let mut app = new_app();
+        app.insert_resource(CheatsEnabled(false));
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { I D K F A }).cheat(),
+        );
+        app.update();
+        assert_sequence_does_not_fire!(app, keyseq! { I D K F A }, MyEvent);
+
+        app.insert_resource(CheatsEnabled(true));
+        assert_sequence_fires!(app, keyseq! { I D K F A }, MyEvent);
+    }
+
+    #[test]
+    fn namespaces() {
+        use bevy_input_sequence::{
+            namespace::{conflicts, ConflictKind, Namespace, Namespaces},
+            testing::type_chords,
+        };
+        let mut app = new_app();
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { G H }).namespace("inspector"),
+        );
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), keyseq! { G }));
+        app.update();
+        let report = conflicts(
+            app.world_mut()
+                .query::<(Entity, &KeySequence, Option<&Namespace>)>()
+                .iter(app.world()),
+        );
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].kind, ConflictKind::Prefix);
+        assert_eq!(report[0].first_namespace, None);
+        assert_eq!(report[0].second_namespace, Some(Namespace::new("inspector")));
+
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { G H }), 2);
+        app.world_mut().resource_mut::<Namespaces>().disable("inspector");
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { G H }), 1);
+        app.world_mut().resource_mut::<Namespaces>().enable("inspector");
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { G H }), 2);
+    }
+
+    #[test]
+    fn matched_carries_entity() {
+        use bevy_input_sequence::matched::Matched;
+        let mut app = new_app();
+        app.add_event::<Matched<MyEvent>>();
+        let a = app.world_mut().spawn_empty().id();
+        let b = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_matched(MyEvent), [KeyCode::KeyA]),
+            a,
+            app.world_mut(),
+        );
+        EntityCommand::apply(
+            KeySequence::new(action::send_matched(MyEvent), [KeyCode::KeyB]),
+            b,
+            app.world_mut(),
+        );
+        let mut cursor = app.world().resource::<Events<Matched<MyEvent>>>().get_cursor();
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        let events = app.world().resource::<Events<Matched<MyEvent>>>();
+        let entities: Vec<Entity> = cursor.read(events).map(|m| m.entity).collect();
+        assert_eq!(entities, vec![b]);
+    }
+
+    #[test]
+    fn observe_sequence_complete() {
+        use bevy::prelude::Trigger;
+        use bevy_input_sequence::{matched::SequenceComplete, testing::type_chords};
+        #[derive(Component)]
+        struct Completed;
+        let mut app = new_app();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B }),
+            id,
+            app.world_mut(),
+        );
+        app.world_mut().entity_mut(id).observe(
+            |trigger: Trigger<SequenceComplete>, mut commands: Commands| {
+                commands.entity(trigger.entity()).insert(Completed);
+            },
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+        app.update();
+        assert!(app.world().get::<Completed>(id).is_some());
+    }
+
+    #[test]
+    fn tutorial_hints() {
+        use bevy_input_sequence::{testing::type_chords, tutorial::TutorialHint};
+        let mut app = new_app();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A Ctrl-B }).tutorial(),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        let prompt = |app: &TestContext| app.world().get::<TutorialHint>(id).unwrap().prompt.clone();
+        assert_eq!(prompt(&app), "Press A (1/2)");
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A }), 0);
+        assert_eq!(prompt(&app), "Press Ctrl-B (2/2)");
+        let hint = app.world().get::<TutorialHint>(id).unwrap();
+        assert_eq!(hint.step, 1);
+        assert_eq!(hint.next, Some(KeyChord(Modifiers::CONTROL, KeyCode::KeyB)));
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Ctrl-B }), 1);
+        assert_eq!(prompt(&app), "Press A (1/2)");
+    }
+
+    #[test]
+    fn progress_events() {
+        use bevy::time::TimeUpdateStrategy;
+        use bevy_input_sequence::{progress::SequenceProgress, testing::type_chords};
+        use std::time::Duration;
+        let mut app = new_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B C })
+                .time_limit(Duration::from_secs(1))
+                .report_progress(),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        let mut cursor = app.world().resource::<Events<SequenceProgress>>().get_cursor();
+        let mut progress = Vec::new();
+        for chord in keyseq! { A B C } {
+            type_chords::<MyEvent>(&mut app, [chord]);
+            let events = app.world().resource::<Events<SequenceProgress>>();
+            progress.extend(
+                cursor
+                    .read(events)
+                    .map(|p| (p.entity, p.matched, p.total, p.remaining.clone())),
+            );
+        }
+        assert_eq!(
+            progress,
+            vec![
+                (id, 1, 3, Some(TimeLimit::Duration(Duration::from_secs(1)))),
+                (id, 2, 3, Some(TimeLimit::Duration(Duration::from_millis(900)))),
+            ]
+        );
+    }
+
+    #[test]
+    fn aborted_events() {
+        use bevy_input_sequence::{
+            matched::{AbortReason, SequenceAborted},
+            testing::type_chords,
+        };
+        let mut app = new_app();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B C })
+                .time_limit(TimeLimit::Frames(1)),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        let mut cursor = app.world().resource::<Events<SequenceAborted>>().get_cursor();
+        let mut aborts = Vec::new();
+        for chord in keyseq! { A X A B C } {
+            type_chords::<MyEvent>(&mut app, [chord]);
+            let events = app.world().resource::<Events<SequenceAborted>>();
+            aborts.extend(cursor.read(events).copied());
+        }
+        assert_eq!(
+            aborts,
+            vec![
+                SequenceAborted { entity: id, matched_len: 1, reason: AbortReason::WrongInput },
+                SequenceAborted { entity: id, matched_len: 2, reason: AbortReason::TimedOut },
+            ]
+        );
+    }
+
+    #[test]
+    fn adaptive_windows() {
+        use bevy::time::TimeUpdateStrategy;
+        use bevy_input_sequence::{
+            adaptive::{AdaptiveWindow, WindowAdjusted},
+            testing::type_chords,
+        };
+        use std::time::Duration;
+        let ms = Duration::from_millis;
+        let mut app = new_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(ms(200)));
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B C })
+                .time_limit(ms(300))
+                .adaptive_window(AdaptiveWindow::new(ms(300), ms(900)).step(ms(300)).after(2)),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        let mut cursor = app.world().resource::<Events<WindowAdjusted>>().get_cursor();
+        let mut round = |app: &mut App| {
+            let mut matches = 0;
+            let mut adjusted = Vec::new();
+            for chord in keyseq! { A B C } {
+                matches += type_chords::<MyEvent>(app, [chord]);
+                let events = app.world().resource::<Events<WindowAdjusted>>();
+                adjusted.extend(cursor.read(events).map(|e| e.time_limit));
+            }
+            (matches, adjusted)
+        };
+        // Each chord takes 200ms, so typing takes 400ms.
+        assert_eq!(round(&mut app), (0, vec![]));
+        assert_eq!(round(&mut app), (0, vec![ms(600)]));
+        assert_eq!(round(&mut app), (1, vec![]));
+        assert_eq!(round(&mut app), (1, vec![ms(300)]));
+        assert_eq!(round(&mut app), (0, vec![]));
+    }
+
+    #[test]
+    fn adaptive_window_needs_key_or_button_sequence() {
+        use bevy::input::mouse::MouseButton;
+        use bevy_input_sequence::{adaptive::AdaptiveWindow, mouse::MouseSequence};
+        use std::time::Duration;
+        let window = AdaptiveWindow::new(Duration::from_millis(300), Duration::from_millis(900));
+        let mut app = new_app();
+        let key = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(|| {}, keyseq! { A B }).adaptive_window(window),
+            key,
+            app.world_mut(),
+        );
+        let mouse = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            MouseSequence::new(|| {}, [MouseButton::Left, MouseButton::Right]).adaptive_window(window),
+            mouse,
+            app.world_mut(),
+        );
+        assert!(app.world().get::<AdaptiveWindow>(key).is_some());
+        assert!(app.world().get::<AdaptiveWindow>(mouse).is_none());
+    }
+
+    #[test]
+    fn prerequisites() {
+        use bevy::time::TimeUpdateStrategy;
+        use bevy_input_sequence::testing::type_chords;
+        use std::time::Duration;
+        let mut app = new_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(200)));
+        let first = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(|| {}, keyseq! { A }),
+            first,
+            app.world_mut(),
+        );
+        let second = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { B })
+                .requires(first, Duration::from_millis(500)),
+            second,
+            app.world_mut(),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { B }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { B }), 1);
+        app.update();
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { B }), 0);
+    }
+
+    #[test]
+    fn pattern_sequences() {
+        use bevy_input_sequence::{
+            pattern::{KeyPattern, PatternSequence},
+            testing::type_chords,
+        };
+        let mut app = new_app();
+        app.world_mut().add(PatternSequence::new(
+            action::send_event(MyEvent),
+            [KeyCode::Escape.into(), KeyPattern::Any, KeyCode::Escape.into()],
+        ));
+        app.world_mut().add(PatternSequence::new(
+            action::send_event(MyEvent),
+            [KeyPattern::any_of(keyseq! { A B }), KeyCode::KeyC.into()],
+        ));
+        app.world_mut().add(PatternSequence::new(
+            action::send_event(MyEvent),
+            [KeyPattern::AnyDigit, KeyPattern::AnyLetter],
+        ));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Escape X Escape }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Escape Ctrl-Z Escape }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Escape Escape }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A C B C }), 2);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { D C }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { 4 Z 7 Q }), 2);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Shift-4 Z }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { F4 Z }), 0);
+    }
+
+    #[test]
+    fn pattern_sequence_once() {
+        use bevy_input_sequence::{
+            disabled::Once,
+            pattern::{KeyPattern, PatternSequence},
+            testing::type_chords,
+        };
+        let mut app = new_app();
+        let id = app.world_mut().spawn(Once).id();
+        EntityCommand::apply(
+            PatternSequence::new(action::send_event(MyEvent), [KeyPattern::Any, KeyCode::KeyQ.into()]),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A Q }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A Q }), 0);
+    }
+
+    #[test]
+    fn match_priority() {
+        use bevy_input_sequence::{matched::MatchPriority, testing::type_chords};
+        #[derive(Event, Clone)]
+        struct Long;
+        #[derive(Event, Clone)]
+        struct Short;
+        let fired = |global: MatchPriority, short: Option<MatchPriority>| {
+            let mut app = App::new();
+            app.add_plugins(MinimalPlugins)
+                .add_plugins(InputSequencePlugin::default().match_key(true).priority(global))
+                .init_resource::<Input<KeyCode>>()
+                .add_event::<Long>()
+                .add_event::<Short>();
+            app.world_mut().add(
+                KeySequence::new(action::send_event(Long), keyseq! { A B C }).rolling(),
+            );
+            let builder = KeySequence::new(action::send_event(Short), keyseq! { B C }).rolling();
+            app.world_mut().add(match short {
+                Some(priority) => builder.priority(priority),
+                None => builder,
+            });
+            app.update();
+            let mut cursor = app.world().resource::<Events<Short>>().get_cursor_current();
+            let long = type_chords::<Long>(&mut app, keyseq! { A B C });
+            let short = cursor.read(app.world().resource::<Events<Short>>()).count();
+            (long, short)
+        };
+        assert_eq!(fired(MatchPriority::AllFire, None), (1, 1));
+        assert_eq!(fired(MatchPriority::LongestWins, None), (1, 0));
+        assert_eq!(fired(MatchPriority::FirstRegistered, None), (1, 0));
+        assert_eq!(fired(MatchPriority::AllFire, Some(MatchPriority::LongestWins)), (1, 0));
+        assert_eq!(fired(MatchPriority::LongestWins, Some(MatchPriority::AllFire)), (1, 1));
+    }
+
+    #[test]
+    fn suppress_prefixes() {
+        use bevy_input_sequence::testing::type_chords;
+        #[derive(Event, Clone)]
+        struct Long;
+        #[derive(Event, Clone)]
+        struct Short;
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(
+                InputSequencePlugin::default()
+                    .match_key(true)
+                    .suppress_prefixes(TimeLimit::Frames(2)),
+            )
+            .init_resource::<Input<KeyCode>>()
+            .add_event::<Long>()
+            .add_event::<Short>();
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(Long), keyseq! { Z X C }));
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(Short), keyseq! { Z }));
+        app.update();
+        let mut cursor = app.world().resource::<Events<Short>>().get_cursor_current();
+        let mut shorts = |app: &App| cursor.read(app.world().resource::<Events<Short>>()).count();
+
+        // The longer sequence completes, so the prefix never runs.
+        assert_eq!(type_chords::<Long>(&mut app, keyseq! { Z X C }), 1);
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(shorts(&app), 0);
+
+        // The prefix runs once the timeout passes.
+        assert_eq!(type_chords::<Short>(&mut app, keyseq! { Z }), 0);
+        assert_eq!(shorts(&app), 0);
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(shorts(&app), 1);
+
+        // The prefix runs as soon as the input diverges.
+        assert_eq!(type_chords::<Short>(&mut app, keyseq! { Z }), 0);
+        assert_eq!(type_chords::<Short>(&mut app, keyseq! { A }), 1);
+    }
+
+    #[test]
+    fn disable_sequences() {
+        use bevy_input_sequence::{disabled::Disabled, testing::type_chords};
+        let mut app = new_app();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B }),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A }), 0);
+        app.world_mut().entity_mut(id).insert(Disabled);
+        app.update();
+        assert!(app.world().get::<KeySequence>(id).is_none());
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { B A B }), 0);
+
+        // The partial match from before was dropped.
+        app.world_mut().entity_mut(id).remove::<Disabled>();
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { B }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+    }
+
+    #[test]
+    fn cooldown_drops_repeats() {
+        use bevy_input_sequence::testing::type_chords;
+        use std::time::Duration;
+        let mut app = new_app();
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A })
+                .cooldown(Duration::from_secs(3600)),
+        );
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), keyseq! { B }));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A A A }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { B B }), 2);
+    }
+
+    #[test]
+    fn once_disables_after_firing() {
+        use bevy_input_sequence::{disabled::Disabled, testing::type_chords};
+        let mut app = new_app();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B }).once(),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+        app.update();
+        assert!(app.world().get::<Disabled>(id).is_some());
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 0);
+    }
+
+    #[test]
+    fn match_in_unconfigured_schedule() {
+        use bevy::app::FixedUpdate;
+        use bevy_input_sequence::testing::type_chords;
+        let mut app = new_app();
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B })
+                .match_in(FixedUpdate),
+        );
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), keyseq! { C D }));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { C D }), 1);
+    }
+
+    /// Return an app that matches in `Update` and then `PreUpdate`.
+    fn two_schedule_app(plugin: InputSequencePlugin) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(plugin.run_in(PreUpdate))
+            .add_event::<MyEvent>()
+            .init_resource::<Input<KeyCode>>();
+        app
+    }
+
+    /// Add `sequence` on an entity that matches in `PreUpdate`.
+    fn add_in_pre_update(app: &mut App, sequence: impl EntityCommand) {
+        use bevy_input_sequence::schedule::MatchSchedule;
+        let id = app.world_mut().spawn(MatchSchedule::new(PreUpdate)).id();
+        sequence.apply(id, app.world_mut());
+    }
+
+    #[test]
+    fn key_sequence_in_two_schedules() {
+        use bevy_input_sequence::testing::type_chords;
+        let mut app = two_schedule_app(InputSequencePlugin::default().match_key(true));
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), keyseq! { A B }));
+        add_in_pre_update(
+            &mut app,
+            KeySequence::new(action::send_event(MyEvent), keyseq! { C D }),
+        );
+        app.update();
+        // Each fires once, in its own schedule.
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { C D }), 1);
+    }
+
+    #[test]
+    fn hashed_sequence_in_two_schedules() {
+        use bevy_input_sequence::{
+            hashed::{HashedKeySequence, SequenceDigest},
+            testing::type_chords,
+        };
+        let mut app = two_schedule_app(InputSequencePlugin::default().match_key(true));
+        let acts: Vec<KeyChord> = keyseq! { A B }.into_iter().map(KeyChord::from).collect();
+        let digest = SequenceDigest::new(&acts, 1);
+        app.world_mut()
+            .add(HashedKeySequence::new(action::send_event(MyEvent), digest));
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), keyseq! { A B }).rolling());
+        add_in_pre_update(&mut app, HashedKeySequence::new(action::send_event(MyEvent), digest));
+        add_in_pre_update(
+            &mut app,
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B }).rolling(),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 4);
+    }
+
+    #[test]
+    fn pattern_sequence_in_two_schedules() {
+        use bevy_input_sequence::{
+            pattern::{KeyPattern, PatternSequence},
+            testing::type_chords,
+        };
+        let mut app = two_schedule_app(InputSequencePlugin::default().match_key(true));
+        let patterns = [KeyPattern::Any, KeyCode::KeyQ.into()];
+        app.world_mut()
+            .add(PatternSequence::new(action::send_event(MyEvent), patterns.clone()));
+        add_in_pre_update(&mut app, PatternSequence::new(action::send_event(MyEvent), patterns));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A Q }), 2);
+    }
+
+    #[test]
+    fn tolerant_sequence_in_two_schedules() {
+        use bevy_input_sequence::testing::type_chords;
+        let mut app = two_schedule_app(InputSequencePlugin::default().match_key(true));
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { W A S D }).tolerate(1),
+        );
+        add_in_pre_update(
+            &mut app,
+            KeySequence::new(action::send_event(MyEvent), keyseq! { W A S D }).tolerate(1),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { W A X S D }), 2);
+    }
+
+    #[test]
+    fn mash_sequence_in_two_schedules() {
+        use bevy_input_sequence::{mash::MashSequence, testing::type_chords};
+        use std::time::Duration;
+        let mut app = two_schedule_app(
+            InputSequencePlugin::default().match_key(false).match_mash(true),
+        );
+        let mash = || {
+            MashSequence::new(
+                action::send_event(MyEvent),
+                KeyCode::Space,
+                2,
+                Duration::from_secs(60),
+            )
+        };
+        app.world_mut().add(mash());
+        add_in_pre_update(&mut app, mash());
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Space Space }), 2);
+    }
+
+    #[test]
+    fn act_sequence_in_two_schedules() {
+        use bevy::input::mouse::MouseButton;
+        use bevy_input_sequence::mouse::MouseSequence;
+        let mut app = two_schedule_app(
+            InputSequencePlugin::default().match_key(false).match_mouse(true),
+        );
+        app.init_resource::<Input<MouseButton>>();
+        let update = [MouseButton::Right, MouseButton::Middle];
+        let pre_update = [MouseButton::Left, MouseButton::Middle];
+        app.world_mut()
+            .add(MouseSequence::new(action::send_event(MyEvent), update));
+        add_in_pre_update(&mut app, MouseSequence::new(action::send_event(MyEvent), pre_update));
+        app.update();
+        let mut cursor = app.world().resource::<Events<MyEvent>>().get_cursor_current();
+        for buttons in [update, pre_update] {
+            let mut sent = 0;
+            for button in buttons {
+                let mut input = app.world_mut().resource_mut::<Input<MouseButton>>();
+                input.release_all();
+                input.clear();
+                input.press(button);
+                app.update();
+                sent += cursor.read(app.world().resource::<Events<MyEvent>>()).count();
+            }
+            assert_eq!(sent, 1);
+        }
+    }
+
+    #[test]
+    fn raw_modifier_mask() {
+        use bevy_input_sequence::testing::type_chords;
+        let mut app = new_app();
+        app.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            [(1, KeyCode::KeyW), (0, KeyCode::KeyQ)],
+        ));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Ctrl-W Q }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Ctrl-Shift-W Q }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { W Q }), 0);
+    }
+
+    #[test]
+    fn tag_bulk_operations() {
+        use bevy_input_sequence::{
+            tag::{DisableTag, EnableTag, ResetTag, TagStats},
+            testing::type_chords,
+        };
+        let mut app = new_app();
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B })
+                .tag("combat")
+                .tag("tutorial"),
+        );
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { C D }).tag("menu"),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+
+        app.world_mut().add(DisableTag::new("combat"));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { C D }), 1);
+
+        app.world_mut().add(EnableTag::new("combat"));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { C }), 0);
+        app.world_mut().add(ResetTag::new("menu"));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { D }), 0);
+
+        let stats = app.world().resource::<TagStats>();
+        assert_eq!(stats.fired("combat"), 2);
+        assert_eq!(stats.fired("tutorial"), 2);
+        assert_eq!(stats.fired("menu"), 1);
+    }
+
+    #[test]
+    fn match_modes() {
+        use bevy_input_sequence::{testing::type_chords, tolerance::MatchMode};
+        let mut app = new_app();
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { I D K F A })
+                .match_mode(MatchMode::Strict),
+        );
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { I D D Q D })
+                .match_mode(MatchMode::Lenient),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { I D X K F A }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { I D X D Y Z Q D }), 1);
+    }
+
+    #[test]
+    fn emit_matched() {
+        use bevy_input_sequence::{matched::{Emit, Matched}, testing::type_chords};
+        let mut app = new_app();
+        app.add_event::<Matched<MyEvent>>();
+        let only = app.world_mut().spawn_empty().id();
+        let both = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B }).emit(Emit::Matched),
+            only,
+            app.world_mut(),
+        );
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { C D }).emit(Emit::Both),
+            both,
+            app.world_mut(),
+        );
+        app.update();
+        let mut cursor = app.world().resource::<Events<Matched<MyEvent>>>().get_cursor();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { C D }), 1);
+        let events = app.world().resource::<Events<Matched<MyEvent>>>();
+        let matched: Vec<_> = cursor.read(events).map(|m| (m.entity, m.device)).collect();
+        assert_eq!(matched, vec![(only, None), (both, None)]);
+    }
+
+    #[test]
+    fn tolerate_mistakes() {
+        use bevy_input_sequence::{matched::{Emit, Matched}, testing::type_chords};
+        let mut app = new_app();
+        app.add_event::<Matched<MyEvent>>();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { W A S D })
+                .tolerate(1)
+                .emit(Emit::Both),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        let mut cursor = app.world().resource::<Events<Matched<MyEvent>>>().get_cursor();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { W A S D }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { W A X S D }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { W A X Y S D }), 0);
+        let events = app.world().resource::<Events<Matched<MyEvent>>>();
+        let matched: Vec<_> = cursor
+            .read(events)
+            .map(|m| (m.mistakes, m.lenient, m.inputs))
+            .collect();
+        assert_eq!(matched, vec![(0, false, 4), (1, true, 5)]);
+    }
+
+    #[test]
+    fn score_matches() {
+        use bevy_input_sequence::{matched::Matched, score::Rank, testing::type_chords};
+        use std::time::Duration;
+        let mut app = new_app();
+        app.add_event::<Matched<MyEvent>>();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(action::send_matched(MyEvent), keyseq! { A B C })
+                .tolerate(2)
+                .score(Duration::from_secs(60)),
+            id,
+            app.world_mut(),
+        );
+        app.update();
+        let mut cursor = app.world().resource::<Events<Matched<MyEvent>>>().get_cursor();
+        assert_eq!(type_chords::<Matched<MyEvent>>(&mut app, keyseq! { A B C }), 1);
+        assert_eq!(type_chords::<Matched<MyEvent>>(&mut app, keyseq! { A X B C }), 1);
+        assert_eq!(type_chords::<Matched<MyEvent>>(&mut app, keyseq! { A X B Y C }), 1);
+        let events = app.world().resource::<Events<Matched<MyEvent>>>();
+        let ranks: Vec<_> = cursor
+            .read(events)
+            .map(|m| m.score.map(|score| score.rank))
+            .collect();
+        assert_eq!(ranks, vec![Some(Rank::S), Some(Rank::B), Some(Rank::C)]);
+    }
+
+    #[test]
+    fn fixed_sequence() {
+        use bevy_input_sequence::{fixed::FixedSequence, testing::type_chords};
+        const CODE: FixedSequence<(Modifiers, KeyCode), 3> =
+            FixedSequence::new(keyseq! { Ctrl-A B C });
+        let mut app = new_app();
+        app.world_mut()
+            .add(KeySequence::from_fixed(action::send_event(MyEvent), &CODE));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B C }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Ctrl-A B C }), 1);
+    }
+
+    #[cfg(feature = "state")]
+    #[test]
+    fn active_after_state() {
+        use bevy::state::{
+            app::{AppExtStates, StatesPlugin},
+            state::{NextState, States},
+        };
+        use bevy_input_sequence::{
+            loading::{AddSequenceState, PendingSequences},
+            testing::type_chords,
+        };
+        #[derive(States, Clone, Debug, Default, PartialEq, Eq, Hash)]
+        enum LoadingState {
+            #[default]
+            Loading,
+            Ready,
+        }
+        let mut app = new_app();
+        app.add_plugins(StatesPlugin)
+            .init_state::<LoadingState>()
+            .add_sequence_state::<LoadingState>();
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B })
+                .active_after(LoadingState::Ready),
+        );
+        app.update();
+        assert_eq!(app.world().resource::<PendingSequences<LoadingState>>().len(), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 0);
+
+        app.world_mut()
+            .resource_mut::<NextState<LoadingState>>()
+            .set(LoadingState::Ready);
+        app.update();
+        assert!(app.world().resource::<PendingSequences<LoadingState>>().is_empty());
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+    }
+
+    #[cfg(feature = "state")]
+    #[test]
+    fn match_in_state() {
+        use bevy::state::{
+            app::{AppExtStates, StatesPlugin},
+            state::{NextState, States},
+        };
+        use bevy_input_sequence::{state_gate::MatchSequencesIn, testing::type_chords};
+        #[derive(States, Clone, Debug, Default, PartialEq, Eq, Hash)]
+        enum GameState {
+            #[default]
+            Menu,
+            Playing,
+        }
+        let mut app = new_app();
+        app.add_plugins(StatesPlugin)
+            .init_state::<GameState>()
+            .match_sequences_in(GameState::Playing);
+        app.world_mut().add(KeySequence::new(action::send_event(MyEvent), keyseq! { A B }));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 0);
+
+        let set_state = |app: &mut TestContext, state| {
+            app.world_mut().resource_mut::<NextState<GameState>>().set(state);
+            app.update();
+        };
+        set_state(&mut app, GameState::Playing);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B }), 1);
+
+        // Leaving the state drops the partial match.
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A }), 0);
+        set_state(&mut app, GameState::Menu);
+        set_state(&mut app, GameState::Playing);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { B }), 0);
+    }
+
+    #[test]
+    fn debug_only() {
+        use bevy_input_sequence::{assert_sequence_fires, debug_only};
+        let mut app = new_app();
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { N O C L I P }).debug_only(),
+        );
+        debug_only! {
+            app.world_mut()
+                .add(KeySequence::new(action::send_event(MyEvent), keyseq! { I D D Q D }));
+        }
+        app.update();
+        if cfg!(debug_assertions) {
+            assert_sequence_fires!(app, keyseq! { N O C L I P }, MyEvent);
+            assert_sequence_fires!(app, keyseq! { I D D Q D }, MyEvent);
+        }
+        let sequences = app.world_mut().query::<&KeySequence>().iter(app.world()).count();
+        assert_eq!(sequences, if cfg!(debug_assertions) { 2 } else { 0 });
+    }
+
+    #[test]
+    fn hashed_sequence() {
+        use bevy_input_sequence::{
+            hashed::{HashedKeySequence, SequenceDigest},
+            testing::type_chords,
+        };
+        let mut app = new_app();
+        let acts: Vec<KeyChord> = keyseq! { A A B }.into_iter().map(KeyChord::from).collect();
+        let digest = SequenceDigest::new(&acts, 42);
+        assert_ne!(digest, SequenceDigest::new(&acts, 43));
+        app.world_mut()
+            .add(HashedKeySequence::new(action::send_event(MyEvent), digest));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B B }), 0);
+        // Matches the most recent chords regardless of what came before.
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A A A B }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A A B A A B }), 2);
+    }
+
+    #[test]
+    fn rolling_sequence_does_not_overlap() {
+        use bevy_input_sequence::testing::type_chords;
+        let mut app = new_app();
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), keyseq! { A A }).rolling());
+        app.update();
+        // The second A of a match doesn't start the next one.
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A A A }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A }), 1);
+    }
+
+    #[test]
+    fn hashed_sequence_reveals_no_progress() {
+        use bevy_input_sequence::{
+            cache::KeySequenceCache,
+            hashed::{HashedKeySequence, SequenceDigest},
+            matched::SequenceAborted,
+            progress::SequenceProgress,
+            testing::type_chords,
+        };
+        let mut app = new_app();
+        let acts: Vec<KeyChord> = keyseq! { I D K F A }.into_iter().map(KeyChord::from).collect();
+        app.world_mut().add(HashedKeySequence::new(
+            action::send_event(MyEvent),
+            SequenceDigest::new(&acts, 7),
+        ));
+        app.update();
+        let mut aborts = app.world().resource::<Events<SequenceAborted>>().get_cursor_current();
+        let mut progress = app.world().resource::<Events<SequenceProgress>>().get_cursor_current();
+        for chords in [keyseq! { I D K }.to_vec(), keyseq! { X }.to_vec(), keyseq! { I D K F }.to_vec()] {
+            for chord in chords {
+                assert_eq!(type_chords::<MyEvent>(&mut app, [chord]), 0);
+                assert!(app.world().resource::<KeySequenceCache>().prefix().is_empty());
+                assert_eq!(aborts.read(app.world().resource::<Events<SequenceAborted>>()).count(), 0);
+                assert_eq!(progress.read(app.world().resource::<Events<SequenceProgress>>()).count(), 0);
+            }
+        }
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A }), 1);
+    }
+
+    #[test]
+    fn rolling_sequence() {
+        use bevy_input_sequence::testing::type_chords;
+        let mut app = new_app();
+        let code = keyseq! { ArrowUp ArrowUp ArrowDown ArrowDown ArrowLeft ArrowRight ArrowLeft ArrowRight B A
+                             ArrowUp ArrowUp ArrowDown ArrowDown ArrowLeft ArrowRight ArrowLeft ArrowRight B A };
+        app.world_mut()
+            .add(KeySequence::new(action::send_event(MyEvent), code).rolling());
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, code[..19].to_vec()), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { X Y }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { ArrowUp }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, code), 1);
+    }
+
+    #[test]
+    fn layout_change_remaps_text_sequences() {
+        use bevy::input::{keyboard::{Key, KeyboardInput}, ButtonState};
+        use bevy_input_sequence::{keyboard_layout::BindingsRemapped, testing::type_chords};
+        let mut app = new_app();
+        app.world_mut()
+            .add(KeySequence::from_text(action::send_event(MyEvent), "zoo"));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Z O O }), 1);
+
+        // Switch to a German layout, where Y and Z trade places.
+        let window = app.world_mut().spawn_empty().id();
+        let mut cursor = app.world().resource::<Events<BindingsRemapped>>().get_cursor();
+        app.world_mut().send_event(KeyboardInput {
+            key_code: KeyCode::KeyY,
+            logical_key: Key::Character("z".into()),
+            state: ButtonState::Pressed,
+            repeat: false,
+            window,
+        });
+        app.update();
+        let remapped = app.world().resource::<Events<BindingsRemapped>>();
+        assert_eq!(cursor.read(remapped).count(), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Z O O }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Y O O }), 1);
+    }
+
+    #[test]
+    fn test_modifier() {
+        let mut app = new_app();
+
+        app.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            [KeyCode::KeyA, KeyCode::KeyB],
+        ));
+
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_none());
+
+        clear_just_pressed(&mut app, KeyCode::KeyA);
+        app.update();
+
+        press_key(&mut app, KeyCode::ControlLeft);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_none());
+        release(&mut app, KeyCode::ControlLeft);
+        app.update();
+
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_some());
+    }
+
+    #[test]
+    fn custom_modifier() {
+        use bevy::prelude::Res;
+        use bevy_input_sequence::modifier::{self, AddCustomModifier};
+        let mut app = new_app();
+        app.add_custom_modifier(modifier::CUSTOM_1, |r: Res<R>| r.0 == 1);
+
+        app.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            [KeyChord(modifier::CUSTOM_1, KeyCode::KeyA)],
+        ));
+
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_none());
+        release(&mut app, KeyCode::KeyA);
+        app.update();
+
+        app.world_mut().resource_mut::<R>().0 = 1;
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_some());
+    }
+
+    #[test]
+    fn guard_suppresses_matching() {
+        use bevy_input_sequence::guard::InputSequenceGuard;
+        let mut app = new_app();
+        app.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            [KeyCode::KeyA, KeyCode::KeyB],
+        ));
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyA);
+        release(&mut app, KeyCode::KeyA);
+
+        let token = app.world().resource::<InputSequenceGuard>().suppress();
+        let other = app.world().resource::<InputSequenceGuard>().suppress();
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyB);
+        release(&mut app, KeyCode::KeyB);
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 0);
+
+        // Matching stays suppressed until the last token is dropped.
+        drop(token);
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyA);
+        release(&mut app, KeyCode::KeyA);
+        drop(other);
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyB);
+        release(&mut app, KeyCode::KeyB);
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 0);
+
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyA);
+        release(&mut app, KeyCode::KeyA);
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        assert_eq!(app.world_mut().query::<&EventSent>().iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn held_until_next() {
+        let mut app = new_app();
+
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), [KeyCode::KeyS, KeyCode::KeyA])
+                .press(0, Press::HeldUntilNext),
+        );
+
+        press_key(&mut app, KeyCode::KeyS);
+        app.update();
+        release(&mut app, KeyCode::KeyS);
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_none());
+        release(&mut app, KeyCode::KeyA);
+        app.update();
+
+        press_key(&mut app, KeyCode::KeyS);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyS);
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_some());
+    }
+
+    #[test]
+    fn held_for_duration() {
+        use bevy::time::TimeUpdateStrategy;
+        use std::time::Duration;
+        let mut app = new_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(200)));
+
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), [KeyCode::KeyS, KeyCode::KeyA])
+                .press(0, Press::HeldFor(Duration::from_millis(500))),
+        );
+
+        // Held for 200ms.
+        press_key(&mut app, KeyCode::KeyS);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyS);
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_none());
+        clear_just_pressed(&mut app, KeyCode::KeyA);
+        release(&mut app, KeyCode::KeyA);
+        release(&mut app, KeyCode::KeyS);
+        app.update();
+
+        // Held for 600ms.
+        press_key(&mut app, KeyCode::KeyS);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyS);
+        app.update();
+        app.update();
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_some());
+    }
+
+    #[test]
+    fn repeated_steps() {
+        use bevy::time::TimeUpdateStrategy;
+        use bevy_input_sequence::testing::type_chords;
+        use std::time::Duration;
+        let mut app = new_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(200)));
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { Space A })
+                .repeat(0, 3, Some(Duration::from_millis(300))),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Space Space A }), 0);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Space Space Space A }), 1);
+        // Pause too long between the second and third taps.
+        press_key(&mut app, KeyCode::Space);
+        app.update();
+        release(&mut app, KeyCode::Space);
+        clear_just_pressed(&mut app, KeyCode::Space);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Space }), 0);
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Space A }), 0);
+    }
+
+    #[test]
+    fn interval_schedule() {
+        use bevy::time::TimeUpdateStrategy;
+        use bevy_input_sequence::testing::type_chords;
+        use std::time::Duration;
+        let ms = Duration::from_millis;
+        let mut app = new_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(ms(200)));
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), keyseq! { A B C })
+                .intervals([ms(500), ms(300)]),
+        );
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { A B C }), 1);
+        // A pause makes a 400ms gap, inside the first window but not the
+        // second.
+        for pause_after in [0, 1] {
+            let mut sent = 0;
+            for (i, chord) in keyseq! { A B C }.into_iter().enumerate() {
+                sent += type_chords::<MyEvent>(&mut app, [chord]);
+                if i == pause_after {
+                    app.update();
+                }
+            }
+            assert_eq!(sent, usize::from(pause_after == 0));
+        }
+    }
+
+    #[test]
+    fn pressed_accepts_held_key() {
+        let mut app = new_app();
+
+        app.world_mut().add(
+            KeySequence::new(action::send_event(MyEvent), [KeyCode::KeyS, KeyCode::KeyA])
+                .press(0, Press::Pressed),
+        );
+
+        // Hold S and break the match with Z.
+        press_key(&mut app, KeyCode::KeyS);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyS);
+        press_key(&mut app, KeyCode::KeyZ);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyZ);
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_some());
+    }
+
+    #[test]
+    fn just_pressed_ignores_held_key() {
+        let mut app = new_app();
+
+        app.world_mut().add(KeySequence::new(
+            action::send_event(MyEvent),
+            [KeyCode::KeyS, KeyCode::KeyA],
+        ));
+
+        press_key(&mut app, KeyCode::KeyS);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyS);
+        press_key(&mut app, KeyCode::KeyZ);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyZ);
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        assert!(app
+            .world_mut()
+            .query::<&EventSent>()
+            .iter(app.world_mut())
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn focus_routes_sequences() {
+        use bevy_input_sequence::focus::{FocusOwner, SequenceFocus};
+        let mut app = new_app();
+        let left = app.world_mut().spawn_empty().id();
+        let right = app.world_mut().spawn_empty().id();
+        app.world_mut().entity_mut(left).insert(FocusOwner(left));
+        app.world_mut().entity_mut(right).insert(FocusOwner(right));
+        EntityCommand::apply(
+            KeySequence::new(set(1), [KeyCode::KeyA, KeyCode::KeyB]),
+            left,
+            app.world_mut(),
+        );
+        EntityCommand::apply(
+            KeySequence::new(set(2), [KeyCode::KeyA, KeyCode::KeyB]),
+            right,
+            app.world_mut(),
+        );
+
+        app.world_mut().insert_resource(SequenceFocus(Some(left)));
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        clear_just_pressed(&mut app, KeyCode::KeyA);
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        assert_eq!(get(app.world()), 1);
+        release(&mut app, KeyCode::KeyA);
+        release(&mut app, KeyCode::KeyB);
+
+        // Switch focus mid-sequence; progress is handed off.
+        press_key(&mut app, KeyCode::KeyA);
+        app.update();
+        release(&mut app, KeyCode::KeyA);
+        app.world_mut().insert_resource(SequenceFocus(Some(right)));
+        press_key(&mut app, KeyCode::KeyB);
+        app.update();
+        assert_eq!(get(app.world()), 2);
+    }
+
+    #[test]
+    fn sequence_round_trips_through_reflection() {
+        use bevy::{
+            ecs::reflect::{AppTypeRegistry, ReflectComponent},
+            reflect::FromReflect,
+        };
+        let mut app = new_app();
+        let id = app.world_mut().spawn_empty().id();
+        EntityCommand::apply(
+            KeySequence::new(set(1), [KeyCode::KeyA, KeyCode::KeyB])
+                .time_limit(TimeLimit::Frames(2)),
+            id,
+            app.world_mut(),
+        );
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let reflect_component = registry
+            .get_type_data::<ReflectComponent>(std::any::TypeId::of::<InputSequence<KeyChord, ()>>())
+            .expect("registered");
+        let reflected = reflect_component
+            .reflect(app.world().entity(id))
+            .expect("reflected");
+        let copy = InputSequence::<KeyChord, ()>::from_reflect(reflected.as_partial_reflect())
+            .expect("from reflect");
+        assert_eq!(
+            copy.acts,
+            vec![KeyChord::from(KeyCode::KeyA), KeyChord::from(KeyCode::KeyB)]
+        );
+        assert_eq!(copy.time_limit, Some(TimeLimit::Frames(2)));
+    }
+
+    #[test]
+    fn scene_event_sent_on_match() {
+        use bevy::{
+            ecs::{event::Events, system::SystemId},
+            reflect::Reflect,
+        };
+        use bevy_input_sequence::scene::AddSceneEvent;
+
+        #[derive(Event, Reflect, Clone, Debug, PartialEq)]
+        struct Teleport(u8);
+
+        let mut app = new_app();
+        app.add_scene_event::<Teleport>();
+        // As if spawned from a scene: no system, the event alongside.
+        app.world_mut().spawn((
+            KeySequence {
+                system_id: SystemId::from_entity(Entity::PLACEHOLDER),
+                acts: vec![KeyChord::from(KeyCode::KeyT)],
+                time_limit: None,
+                presses: vec![],
+                intervals: vec![],
+            },
+            Teleport(4),
+        ));
+        app.update();
+        press_key(&mut app, KeyCode::KeyT);
+        app.update();
+        let events: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Events<Teleport>>()
+            .drain()
+            .collect();
+        assert_eq!(events, vec![Teleport(4)]);
+    }
+
+    #[test]
+    fn simon_says_round() {
+        use bevy_input_sequence::{
+            simon::{SimonOutcome, SimonRound, SimonSays},
+            testing::type_chords,
+        };
+        use std::time::Duration;
+        let round = SimonSays::new(keyseq! { A B C }, 4, Duration::from_secs(60)).seed(7);
+        assert_eq!(round.generate(7), round.generate(7));
+        assert_eq!(round.generate(7).len(), 4);
+
+        let mut app = new_app();
+        app.world_mut().add(round);
+        app.update();
+        let acts = app
+            .world_mut()
+            .query::<&SimonRound>()
+            .single(app.world())
+            .acts
+            .clone();
+        assert_eq!(type_chords::<SimonOutcome>(&mut app, acts), 1);
+        assert!(app
+            .world_mut()
+            .query::<&SimonRound>()
+            .iter(app.world())
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn global_input_sequence() {
+        use bevy_input_sequence::{global::AddInputSequence, testing::type_chords};
+        let mut app = new_app();
+        app.add_input_sequence(keyseq! { Ctrl-K Ctrl-B }, MyEvent);
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Ctrl-K Ctrl-B }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Ctrl-B }), 0);
+    }
+
+    #[test]
+    fn qte_hit_or_miss() {
+        use bevy::ecs::event::Events;
+        use bevy_input_sequence::{qte::Qte, testing::type_chords};
+        use std::time::Duration;
+
+        #[derive(Event, Clone)]
+        struct Missed;
+
+        let mut app = new_app();
+        app.add_event::<Missed>();
+        app.world_mut()
+            .add(Qte::new(KeyCode::KeyQ, Duration::from_secs(60), MyEvent, Missed));
+        app.update();
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Q }), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Q }), 0);
+
+        app.world_mut()
+            .add(Qte::new(KeyCode::KeyQ, Duration::ZERO, MyEvent, Missed));
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Events<Missed>>().len(), 1);
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Q }), 0);
+    }
+
+    #[test]
+    fn qte_hit_on_deadline_frame() {
+        use bevy::{ecs::event::Events, time::TimeUpdateStrategy};
+        use bevy_input_sequence::{matched::MatchPriority, qte::Qte, testing::type_chords};
+        use std::time::Duration;
+
+        #[derive(Event, Clone)]
+        struct Missed;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(
+                InputSequencePlugin::default()
+                    .match_key(true)
+                    .priority(MatchPriority::LongestWins),
+            )
+            .add_event::<MyEvent>()
+            .add_event::<Missed>()
+            .init_resource::<Input<KeyCode>>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.update();
+        app.update();
+        app.world_mut()
+            .add(Qte::new(KeyCode::KeyQ, Duration::from_millis(200), MyEvent, Missed));
+        app.update();
+        // The press lands on the frame the deadline passes; the held back hit
+        // is the only outcome.
+        assert_eq!(type_chords::<MyEvent>(&mut app, keyseq! { Q }), 1);
+        app.update();
+        assert_eq!(app.world().resource::<Events<Missed>>().len(), 0);
     }
 
     #[test]